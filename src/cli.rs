@@ -0,0 +1,296 @@
+//! # Command-Line Interface Module
+//!
+//! This module parses the command line of the Ninja editor into the options
+//! that `run_editor` starts with. All argument handling lives here.
+//!
+//! ## Features
+//!
+//! - **Multiple Files**: Every file argument opens in its own buffer
+//! - **Start Positions**: `file.rs:42:7`, `file.rs:42` and `+42 file.rs`
+//! - **Standard Input**: `-` reads stdin into an untitled buffer
+//! - **Pipe Filter**: `--pipe` writes the saved buffer to stdout on quit
+//! - **Read-Only**: `--readonly` opens buffers without allowing changes
+//! - **Viewer**: `ninja view` pages through files with less-like keys
+//! - **Configuration**: `--config <path>` and `--no-config`
+//! - **Information**: `--help` and `--version`
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use ninja::cli::{self, Command, FileSource};
+//! use std::path::PathBuf;
+//!
+//! let command = cli::parse(["+3", "notes.txt", "--readonly"]).unwrap();
+//! let Command::Edit(options) = command else { panic!() };
+//! assert!(options.read_only);
+//! assert_eq!(options.files[0].source, FileSource::Path(PathBuf::from("notes.txt")));
+//! assert_eq!(options.files[0].line, Some(3));
+//! ```
+
+use std::ffi::OsString;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Help text printed by `--help`.
+pub const USAGE: &str = "\
+Usage: ninja [OPTIONS] [FILE[:LINE[:COLUMN]]]...
+       ninja view [OPTIONS] [FILE[:LINE[:COLUMN]]]...
+
+Open each FILE in its own buffer. With no FILE, an untitled buffer is opened.
+`ninja view` opens the buffers read-only with less-like keys.
+
+Arguments:
+  FILE:LINE:COLUMN   Open FILE with the cursor at LINE and COLUMN
+  +LINE              Open the next FILE with the cursor at LINE
+  -                  Read standard input into an untitled buffer
+
+Options:
+  -R, --readonly     Open all buffers read-only
+  -p, --pipe         Write the buffer to standard output when saved and quit;
+                     with no FILE, standard input is read if it is not a terminal
+      --config PATH  Load configuration from PATH instead of the default location
+      --no-config    Ignore configuration files and use the built-in defaults
+  -h, --help         Print this help and exit
+  -V, --version      Print the version and exit
+
+Keys:
+  Ctrl-PageDown / Ctrl-PageUp   Switch to the next / previous buffer
+  Alt-R                         Turn read-only mode on or off
+  Ctrl-Q                        Close the current buffer
+
+View keys:
+  Space / b                     Page down / up
+  j, Enter / k                  Scroll down / up one line
+  /                             Search
+  q                             Close the current buffer
+";
+
+/// Where a buffer's contents come from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileSource {
+    /// A file on disk
+    Path(PathBuf),
+    /// Standard input, read into an untitled buffer
+    Stdin,
+}
+
+/// A buffer to open, with an optional starting cursor position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileArg {
+    /// Where the buffer's contents come from
+    pub source: FileSource,
+    /// Line to place the cursor on (1-based)
+    pub line: Option<usize>,
+    /// Column to place the cursor on (1-based)
+    pub column: Option<usize>,
+}
+
+/// Where the configuration is loaded from.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ConfigSource {
+    /// The default configuration file, if it exists
+    #[default]
+    Default,
+    /// A configuration file given on the command line
+    File(PathBuf),
+    /// No configuration file; built-in defaults only
+    None,
+}
+
+/// Options for an editing session.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CliOptions {
+    /// Buffers to open, in order
+    pub files: Vec<FileArg>,
+    /// Whether buffers are opened read-only
+    pub read_only: bool,
+    /// Whether the less-like viewer keys are active (`ninja view`)
+    pub view: bool,
+    /// Whether the saved buffer is written to standard output on quit
+    pub pipe: bool,
+    /// Where the configuration is loaded from
+    pub config: ConfigSource,
+}
+
+impl CliOptions {
+    /// Returns `true` if one of the buffers is read from standard input.
+    pub fn reads_stdin(&self) -> bool {
+        self.files.iter().any(|file| file.source == FileSource::Stdin)
+    }
+}
+
+/// What the command line asks Ninja to do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Start the editor
+    Edit(CliOptions),
+    /// Print the help text
+    Help,
+    /// Print the version
+    Version,
+}
+
+/// An invalid command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliError(pub String);
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// Parses command-line arguments (without the program name).
+///
+/// # Arguments
+///
+/// * `args` - The arguments, e.g. `std::env::args_os().skip(1)`
+///
+/// # Returns
+///
+/// Returns the parsed `Command`, or a `CliError` describing the problem.
+///
+/// # Example
+///
+/// ```rust
+/// use ninja::cli::{self, Command, ConfigSource};
+///
+/// assert_eq!(cli::parse(["--version"]).unwrap(), Command::Version);
+/// assert!(cli::parse(["--bogus"]).is_err());
+/// assert!(cli::parse(["--pipe", "a.txt", "b.txt"]).is_err());
+///
+/// let Command::Edit(options) = cli::parse(["--no-config", "view", "notes.txt"]).unwrap() else { panic!() };
+/// assert!(options.view && options.read_only);
+/// assert_eq!(options.files.len(), 1);
+///
+/// let Command::Edit(options) = cli::parse(["--no-config", "a.rs:10:4"]).unwrap() else { panic!() };
+/// assert_eq!(options.config, ConfigSource::None);
+/// assert_eq!((options.files[0].line, options.files[0].column), (Some(10), Some(4)));
+/// ```
+pub fn parse<I, S>(args: I) -> Result<Command, CliError>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let mut options = CliOptions::default();
+    let mut pending_line: Option<usize> = None;
+    let mut only_files = false;
+    let mut args = args.into_iter().map(Into::into);
+
+    while let Some(arg) = args.next() {
+        let Some(text) = arg.to_str() else {
+            // Not valid UTF-8, so it can only be a file name
+            push_file(&mut options, FileSource::Path(arg.into()), &mut pending_line, None);
+            continue;
+        };
+        if only_files {
+            push_file(&mut options, FileSource::Path(text.into()), &mut pending_line, None);
+            continue;
+        }
+        match text {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            "-R" | "--readonly" => options.read_only = true,
+            "-p" | "--pipe" => options.pipe = true,
+            "--no-config" => options.config = ConfigSource::None,
+            "--config" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| CliError("--config requires a path".into()))?;
+                options.config = ConfigSource::File(path.into());
+            }
+            "--" => only_files = true,
+            // `view` is a subcommand only in place of the first file; use `./view` for a file
+            "view" if options.files.is_empty() && pending_line.is_none() && !options.view => {
+                options.view = true;
+                options.read_only = true;
+            }
+            "-" => {
+                if options.reads_stdin() {
+                    return Err(CliError("standard input can only be read once".into()));
+                }
+                push_file(&mut options, FileSource::Stdin, &mut pending_line, None);
+            }
+            _ if text.starts_with("--config=") => {
+                options.config = ConfigSource::File(PathBuf::from(&text["--config=".len()..]));
+            }
+            _ if text.starts_with('+') => {
+                let line = text[1..]
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&line| line > 0)
+                    .ok_or_else(|| CliError(format!("invalid line number: {}", text)))?;
+                pending_line = Some(line);
+            }
+            _ if text.starts_with('-') && text.len() > 1 => {
+                return Err(CliError(format!("unknown option: {}", text)));
+            }
+            _ => {
+                let (path, line, column) = split_position(text);
+                let position = line.map(|line| (line, column));
+                push_file(&mut options, FileSource::Path(path), &mut pending_line, position);
+            }
+        }
+    }
+
+    // A trailing +LINE applies to the last file, as in `ninja file.rs +42`
+    if let Some(line) = pending_line {
+        let last = options
+            .files
+            .last_mut()
+            .ok_or_else(|| CliError(format!("+{} must be followed by a file", line)))?;
+        last.line = Some(line);
+        last.column = None;
+    }
+    if options.pipe && options.files.len() > 1 {
+        return Err(CliError("--pipe takes at most one file".into()));
+    }
+    Ok(Command::Edit(options))
+}
+
+/// Adds a buffer to the options, applying a pending `+LINE`.
+fn push_file(
+    options: &mut CliOptions,
+    source: FileSource,
+    pending_line: &mut Option<usize>,
+    position: Option<(usize, Option<usize>)>,
+) {
+    let (line, column) = match (position, pending_line.take()) {
+        (Some((line, column)), _) => (Some(line), column),
+        (None, line) => (line, None),
+    };
+    options.files.push(FileArg {
+        source,
+        line,
+        column,
+    });
+}
+
+/// Splits a `path:line:column` or `path:line` argument.
+///
+/// Arguments naming an existing file are never split, so file names that
+/// contain colons keep working.
+fn split_position(arg: &str) -> (PathBuf, Option<usize>, Option<usize>) {
+    let unsplit = (PathBuf::from(arg), None, None);
+    if Path::new(arg).exists() {
+        return unsplit;
+    }
+    let number = |part: &str| part.parse::<usize>().ok().filter(|&n| n > 0);
+    let mut parts = arg.rsplitn(3, ':');
+    let (Some(last), Some(middle)) = (parts.next(), parts.next()) else {
+        return unsplit;
+    };
+    match (parts.next(), number(middle), number(last)) {
+        (Some(path), Some(line), Some(column)) if !path.is_empty() => {
+            (PathBuf::from(path), Some(line), Some(column))
+        }
+        _ => match number(last) {
+            Some(line) if !arg[..arg.len() - last.len() - 1].is_empty() => {
+                (PathBuf::from(&arg[..arg.len() - last.len() - 1]), Some(line), None)
+            }
+            _ => unsplit,
+        },
+    }
+}
@@ -20,6 +20,7 @@
 //! - **`display`**: Visual appearance and UI settings
 //! - **`behavior`**: Editor behavior and interaction settings
 //! - **`syntax`**: Syntax highlighting configuration
+//! - **`languages`**: Per-language overrides of the indentation settings
 //! 
 //! ## Example Configuration
 //! 
@@ -44,11 +45,16 @@
 //! [syntax]
 //! enable_syntax_highlighting = true
 //! auto_detect_file_type = true
+//! 
+//! [languages.go]
+//! tab_size = 8
+//! soft_tabs = false
 //! ```
 
 use config::{Config, ConfigError, Environment, File};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 /// Main configuration structure for the Ninja editor.
 /// 
@@ -76,6 +82,9 @@ pub struct NinjaConfig {
     pub behavior: BehaviorConfig,
     /// Syntax highlighting configuration
     pub syntax: SyntaxConfig,
+    /// Indentation overrides keyed by language name or file extension
+    #[serde(default)]
+    pub languages: HashMap<String, LanguageConfig>,
 }
 
 /// Configuration for text editing behavior and appearance.
@@ -84,12 +93,16 @@ pub struct NinjaConfig {
 /// of the text content.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct EditorConfig {
-    /// Number of spaces to use for indentation
+    /// Number of columns per indentation level, and the width of a tab character
     pub tab_size: usize,
     /// Whether to use spaces instead of tab characters
     pub soft_tabs: bool,
+    /// Follow the indentation already used in a file instead of the settings above
+    pub detect_indentation: bool,
     /// Automatically indent new lines based on the previous line
     pub auto_indent: bool,
+    /// Insert closing brackets and quotes as their opening ones are typed
+    pub auto_close_pairs: bool,
     /// Show line numbers in the left gutter
     pub show_line_numbers: bool,
     /// Width of the line number gutter in characters
@@ -122,14 +135,58 @@ pub struct DisplayConfig {
 pub struct BehaviorConfig {
     /// Number of Ctrl-Q presses required to quit when file is modified
     pub quit_times: u8,
-    /// Automatically save files (future feature)
+    /// Automatically save named files using the auto-save triggers below
     pub auto_save: bool,
-    /// Create backup files (future feature)
+    /// Seconds a modified buffer must be idle before it is auto-saved (0 disables)
+    pub auto_save_delay_secs: u64,
+    /// Auto-save when the terminal loses focus
+    pub auto_save_on_focus_loss: bool,
+    /// Auto-save when switching to another buffer
+    pub auto_save_on_buffer_switch: bool,
+    /// Back up files on their first save of each session
     pub backup_files: bool,
+    /// Directory for backup files (empty = next to the original file)
+    pub backup_dir: String,
+    /// Number of timestamped backups to keep (0 = a single `file~` backup)
+    pub backup_versions: u32,
     /// Whether search operations are case sensitive
     pub search_case_sensitive: bool,
     /// Whether search wraps around to the beginning/end of the file
     pub search_wrap_around: bool,
+    /// Write crash-recovery swap files for modified buffers
+    pub swap_files: bool,
+    /// Seconds a modified buffer must be idle before its swap file is written
+    pub swap_idle_secs: u64,
+    /// Notice when the open file is changed by another program
+    pub detect_external_changes: bool,
+    /// Reload unmodified buffers automatically when their file changes on disk
+    pub auto_reload: bool,
+    /// Command that writes its standard input to `{file}` with elevated
+    /// privileges, offered when a save is denied (empty to disable)
+    pub elevated_save_command: String,
+}
+
+/// Indentation settings for one language.
+/// 
+/// Each setting that is present replaces the one in the `editor` section
+/// for files of that language. Languages are looked up by name, as shown
+/// in the status bar, or by file extension, ignoring case.
+/// 
+/// # Example
+/// 
+/// ```toml
+/// [languages.python]
+/// tab_size = 4
+/// 
+/// [languages.makefile]
+/// soft_tabs = false
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct LanguageConfig {
+    /// Number of columns per indentation level and per tab
+    pub tab_size: Option<usize>,
+    /// Whether to use spaces instead of tab characters
+    pub soft_tabs: Option<bool>,
 }
 
 /// Configuration for syntax highlighting behavior.
@@ -156,7 +213,9 @@ impl Default for NinjaConfig {
             editor: EditorConfig {
                 tab_size: 4,
                 soft_tabs: true,
+                detect_indentation: true,
                 auto_indent: true,
+                auto_close_pairs: true,
                 show_line_numbers: true,
                 gutter_width: 6,
             },
@@ -170,15 +229,26 @@ impl Default for NinjaConfig {
             behavior: BehaviorConfig {
                 quit_times: 3,
                 auto_save: false,
+                auto_save_delay_secs: 30,
+                auto_save_on_focus_loss: true,
+                auto_save_on_buffer_switch: true,
                 backup_files: false,
+                backup_dir: String::new(),
+                backup_versions: 0,
                 search_case_sensitive: false,
                 search_wrap_around: true,
+                swap_files: true,
+                swap_idle_secs: 4,
+                detect_external_changes: true,
+                auto_reload: true,
+                elevated_save_command: "sudo tee {file}".to_string(),
             },
             syntax: SyntaxConfig {
                 enable_syntax_highlighting: true,
                 default_file_type: "text".to_string(),
                 auto_detect_file_type: true,
             },
+            languages: HashMap::new(),
         }
     }
 }
@@ -214,16 +284,48 @@ impl NinjaConfig {
     /// }
     /// ```
     pub fn load() -> Result<Self, ConfigError> {
-        let config_path = Self::get_config_path();
-        
+        Self::load_with(File::from(Self::get_config_path()).required(false))
+    }
+
+    /// Loads configuration from a specific file.
+    /// 
+    /// This works like [`NinjaConfig::load`], except that the given file is
+    /// used instead of the default location and must exist.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - The configuration file to load
+    /// 
+    /// # Returns
+    /// 
+    /// Returns the loaded `NinjaConfig`, or a `ConfigError` if the file is
+    /// missing or malformed.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use std::path::Path;
+    /// 
+    /// assert!(NinjaConfig::load_from(Path::new("does-not-exist.toml")).is_err());
+    /// ```
+    pub fn load_from(path: &Path) -> Result<Self, ConfigError> {
+        Self::load_with(File::from(path).required(true))
+    }
+
+    /// Layers defaults, the given configuration file and environment variables.
+    fn load_with<F>(file: F) -> Result<Self, ConfigError>
+    where
+        F: config::Source + Send + Sync + 'static,
+    {
         let config = Config::builder()
             // Start with default values
             .add_source(File::from_str(
                 &Self::default_config_toml(),
                 config::FileFormat::Toml,
             ))
-            // Add config file if it exists
-            .add_source(File::from(config_path).required(false))
+            // Add the config file
+            .add_source(file)
             // Add environment variables with prefix "NINJA_"
             .add_source(Environment::with_prefix("NINJA").separator("_"))
             .build()?;
@@ -231,6 +333,52 @@ impl NinjaConfig {
         config.try_deserialize()
     }
 
+    /// Returns the editor settings for a language, with its overrides applied.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `language` - The language name, such as `"Rust"`, if known
+    /// * `extension` - The file extension, such as `"rs"`, if any
+    /// 
+    /// # Returns
+    /// 
+    /// Returns a copy of the `editor` section where `tab_size` and `soft_tabs`
+    /// come from the matching `languages` entry when it sets them.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::{LanguageConfig, NinjaConfig};
+    /// 
+    /// let mut config = NinjaConfig::default();
+    /// config.languages.insert(
+    ///     "go".to_string(),
+    ///     LanguageConfig { tab_size: Some(8), soft_tabs: Some(false) },
+    /// );
+    /// let editor = config.editor_for(Some("Go"), Some("go"));
+    /// assert_eq!(editor.tab_size, 8);
+    /// assert!(!editor.soft_tabs);
+    /// assert_eq!(config.editor_for(Some("Rust"), Some("rs")).tab_size, 4);
+    /// ```
+    pub fn editor_for(&self, language: Option<&str>, extension: Option<&str>) -> EditorConfig {
+        let mut editor = self.editor.clone();
+        let matches = |key: &str| {
+            [language, extension]
+                .into_iter()
+                .flatten()
+                .any(|name| name.eq_ignore_ascii_case(key))
+        };
+        if let Some((_, language)) = self.languages.iter().find(|(key, _)| matches(key)) {
+            if let Some(tab_size) = language.tab_size {
+                editor.tab_size = tab_size;
+            }
+            if let Some(soft_tabs) = language.soft_tabs {
+                editor.soft_tabs = soft_tabs;
+            }
+        }
+        editor
+    }
+
     /// Gets the path to the configuration file.
     /// 
     /// The configuration file is typically located at `~/.config/ninja/config.toml`.
@@ -316,12 +464,16 @@ impl NinjaConfig {
 # This file allows you to customize how Ninja operates, interacts, and looks
 
 [editor]
-# Number of spaces for indentation
+# Columns per indentation level, also used as the width of a tab character
 tab_size = 4
 # Use spaces instead of tabs
 soft_tabs = true
+# Follow the indentation already used in a file instead of the two settings above
+detect_indentation = true
 # Automatically indent new lines
 auto_indent = true
+# Close brackets and quotes as they are opened
+auto_close_pairs = true
 # Show line numbers in the gutter
 show_line_numbers = true
 # Width of the line number gutter
@@ -342,14 +494,35 @@ show_syntax_info = true
 [behavior]
 # Number of Ctrl-Q presses required to quit when file is modified
 quit_times = 3
-# Automatically save files (future feature)
+# Automatically save named files using the triggers below
 auto_save = false
-# Create backup files (future feature)
+# Seconds a modified buffer must be idle before it is auto-saved (0 disables)
+auto_save_delay_secs = 30
+# Auto-save when the terminal loses focus
+auto_save_on_focus_loss = true
+# Auto-save when switching to another buffer
+auto_save_on_buffer_switch = true
+# Back up files on their first save of each session
 backup_files = false
+# Directory for backup files (empty = next to the original file)
+backup_dir = ""
+# Number of timestamped backups to keep (0 = a single `file~` backup)
+backup_versions = 0
 # Case sensitive search
 search_case_sensitive = false
 # Wrap around when searching
 search_wrap_around = true
+# Write crash-recovery swap files for modified buffers
+swap_files = true
+# Seconds a modified buffer must be idle before its swap file is written
+swap_idle_secs = 4
+# Notice when the open file is changed by another program
+detect_external_changes = true
+# Reload unmodified buffers automatically when their file changes on disk
+auto_reload = true
+# Command that writes its standard input to {file} with elevated privileges,
+# offered when a save is denied (e.g. "doas tee {file}"; empty to disable)
+elevated_save_command = "sudo tee {file}"
 
 [syntax]
 # Enable syntax highlighting
@@ -358,6 +531,11 @@ enable_syntax_highlighting = true
 default_file_type = "text"
 # Automatically detect file type from extension
 auto_detect_file_type = true
+
+# Per-language indentation, keyed by language name or file extension
+# [languages.go]
+# tab_size = 8
+# soft_tabs = false
 "#.to_string()
     }
 }
\ No newline at end of file
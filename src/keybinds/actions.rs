@@ -41,6 +41,8 @@
 
 use crossterm::event::KeyCode;
 use std::collections::HashMap;
+use crate::modules::cursor::CursorMotion;
+use crate::modules::file_format::LineEnding;
 use crate::transput::transput::Output;
 
 /// Represents an action that can be performed by a keybind.
@@ -53,13 +55,22 @@ use crate::transput::transput::Output;
 /// 
 /// - **No Operation**: `NoOp` - Does nothing (for unbound keys)
 /// - **Application Control**: `Quit` - Exit the application
-/// - **File Operations**: `Save` - Save the current file
+/// - **File Operations**: `Save`, `RestoreBackup` - Save or restore the current file
+/// - **File Format**: `SetLineEnding`, `ChooseLineEnding` - Convert line endings
+/// - **Encoding**: `ReopenWithEncoding`, `SaveWithEncoding` - Decode or encode the file differently
+/// - **Buffers**: `NextBuffer`, `PreviousBuffer` - Switch between open files
+/// - **Protection**: `ToggleReadOnly` - Allow or refuse changes to the buffer
 /// - **Search**: `Find` - Initiate search functionality
+/// - **Jumps**: `GoToLine`, `JumpBack`, `JumpForward` - Go to a position and walk the jump list
 /// - **Clipboard**: `Copy`, `Cut`, `Paste` - Clipboard operations
 /// - **History**: `Undo`, `Redo` - Undo/redo operations
-/// - **Navigation**: `MoveCursor`, `PageUp`, `PageDown` - Movement
+/// - **Navigation**: `MoveCursor`, `Motion`, `PageUp`, `PageDown` - Movement
 /// - **Selection**: `StartSelection`, `ClearSelection` - Text selection
+/// - **Multiple Cursors**: `AddCursorAbove`, `AddCursorBelow`, `AddNextOccurrence`,
+///   `SelectAllOccurrences`, `SplitSelectionIntoLines`, `ClearCursors` - Edit several places at once
+/// - **Block Selection**: `BlockSelect` - Select a rectangle of display columns
 /// - **Editing**: `InsertChar`, `InsertNewline`, `DeleteChar` - Text editing
+/// - **Indentation**: `Indent`, `Dedent` - Shift the selected lines by one level
 /// - **Custom**: `Custom` - User-defined actions with parameters
 /// 
 /// # Example
@@ -81,8 +92,30 @@ pub enum Action {
     Quit,
     /// Save the current file
     Save,
+    /// Restore the current file from a backup
+    RestoreBackup,
+    /// Convert the file to the given line ending style
+    SetLineEnding(LineEnding),
+    /// Pick a line ending style to convert the file to
+    ChooseLineEnding,
+    /// Re-read the current file from disk using a chosen encoding
+    ReopenWithEncoding,
+    /// Save the current file using a chosen encoding
+    SaveWithEncoding,
+    /// Switch to the next open buffer
+    NextBuffer,
+    /// Switch to the previous open buffer
+    PreviousBuffer,
+    /// Turn read-only mode on or off for the current buffer
+    ToggleReadOnly,
     /// Find/search in the current file
     Find,
+    /// Prompt for a line, column, relative offset or percentage to go to
+    GoToLine,
+    /// Return to the position before the last jump
+    JumpBack,
+    /// Go forward again after jumping back
+    JumpForward,
     /// Copy selected text
     Copy,
     /// Cut selected text
@@ -95,16 +128,36 @@ pub enum Action {
     Redo,
     /// Move cursor in a direction
     MoveCursor(KeyCode),
+    /// Move cursor by a word, paragraph, file or bracket motion
+    Motion(CursorMotion),
     /// Start text selection
     StartSelection,
     /// Clear text selection
     ClearSelection,
+    /// Add a cursor on the line above
+    AddCursorAbove,
+    /// Add a cursor on the line below
+    AddCursorBelow,
+    /// Add a cursor at the next occurrence of the selection
+    AddNextOccurrence,
+    /// Add a cursor at every occurrence of the selection
+    SelectAllOccurrences,
+    /// Split the selection into one cursor per line
+    SplitSelectionIntoLines,
+    /// Return to a single cursor
+    ClearCursors,
+    /// Extend a rectangular block selection in a direction
+    BlockSelect(KeyCode),
     /// Insert a character
     InsertChar(char),
     /// Insert a newline
     InsertNewline,
     /// Delete a character
     DeleteChar,
+    /// Indent the selected lines by one level
+    Indent,
+    /// Remove one level of indentation from the selected lines
+    Dedent,
     /// Page up/down
     PageUp,
     PageDown,
@@ -137,18 +190,39 @@ impl Action {
             Action::NoOp => "NoOp",
             Action::Quit => "Quit",
             Action::Save => "Save",
+            Action::RestoreBackup => "RestoreBackup",
+            Action::SetLineEnding(_) => "SetLineEnding",
+            Action::ChooseLineEnding => "ChooseLineEnding",
+            Action::ReopenWithEncoding => "ReopenWithEncoding",
+            Action::SaveWithEncoding => "SaveWithEncoding",
+            Action::NextBuffer => "NextBuffer",
+            Action::PreviousBuffer => "PreviousBuffer",
+            Action::ToggleReadOnly => "ToggleReadOnly",
             Action::Find => "Find",
+            Action::GoToLine => "GoToLine",
+            Action::JumpBack => "JumpBack",
+            Action::JumpForward => "JumpForward",
             Action::Copy => "Copy",
             Action::Cut => "Cut",
             Action::Paste => "Paste",
             Action::Undo => "Undo",
             Action::Redo => "Redo",
             Action::MoveCursor(_) => "MoveCursor",
+            Action::Motion(_) => "Motion",
             Action::StartSelection => "StartSelection",
             Action::ClearSelection => "ClearSelection",
+            Action::AddCursorAbove => "AddCursorAbove",
+            Action::AddCursorBelow => "AddCursorBelow",
+            Action::AddNextOccurrence => "AddNextOccurrence",
+            Action::SelectAllOccurrences => "SelectAllOccurrences",
+            Action::SplitSelectionIntoLines => "SplitSelectionIntoLines",
+            Action::ClearCursors => "ClearCursors",
+            Action::BlockSelect(_) => "BlockSelect",
             Action::InsertChar(_) => "InsertChar",
             Action::InsertNewline => "InsertNewline",
             Action::DeleteChar => "DeleteChar",
+            Action::Indent => "Indent",
+            Action::Dedent => "Dedent",
             Action::PageUp => "PageUp",
             Action::PageDown => "PageDown",
             Action::Custom(name, _) => name,
@@ -178,18 +252,41 @@ impl Action {
             Action::NoOp => "No operation".to_string(),
             Action::Quit => "Quit the application".to_string(),
             Action::Save => "Save the current file".to_string(),
+            Action::RestoreBackup => "Restore the current file from a backup".to_string(),
+            Action::SetLineEnding(line_ending) => {
+                format!("Convert line endings to {}", line_ending.name())
+            }
+            Action::ChooseLineEnding => "Choose line ending style".to_string(),
+            Action::ReopenWithEncoding => "Reopen the current file with a different encoding".to_string(),
+            Action::SaveWithEncoding => "Save the current file with a different encoding".to_string(),
+            Action::NextBuffer => "Switch to the next open buffer".to_string(),
+            Action::PreviousBuffer => "Switch to the previous open buffer".to_string(),
+            Action::ToggleReadOnly => "Turn read-only mode on or off".to_string(),
             Action::Find => "Find/search in the current file".to_string(),
+            Action::GoToLine => "Go to a line and column".to_string(),
+            Action::JumpBack => "Jump back to the previous position".to_string(),
+            Action::JumpForward => "Jump forward to the next position".to_string(),
             Action::Copy => "Copy selected text".to_string(),
             Action::Cut => "Cut selected text".to_string(),
             Action::Paste => "Paste from clipboard".to_string(),
             Action::Undo => "Undo last action".to_string(),
             Action::Redo => "Redo last action".to_string(),
             Action::MoveCursor(direction) => format!("Move cursor {:?}", direction),
+            Action::Motion(motion) => format!("Move cursor {:?}", motion),
             Action::StartSelection => "Start text selection".to_string(),
             Action::ClearSelection => "Clear text selection".to_string(),
+            Action::AddCursorAbove => "Add a cursor on the line above".to_string(),
+            Action::AddCursorBelow => "Add a cursor on the line below".to_string(),
+            Action::AddNextOccurrence => "Add a cursor at the next occurrence of the selection".to_string(),
+            Action::SelectAllOccurrences => "Add a cursor at every occurrence of the selection".to_string(),
+            Action::SplitSelectionIntoLines => "Split the selection into one cursor per line".to_string(),
+            Action::ClearCursors => "Return to a single cursor".to_string(),
+            Action::BlockSelect(direction) => format!("Extend block selection {:?}", direction),
             Action::InsertChar(ch) => format!("Insert character '{}'", ch),
             Action::InsertNewline => "Insert newline".to_string(),
             Action::DeleteChar => "Delete character".to_string(),
+            Action::Indent => "Indent selected lines".to_string(),
+            Action::Dedent => "Dedent selected lines".to_string(),
             Action::PageUp => "Page up".to_string(),
             Action::PageDown => "Page down".to_string(),
             Action::Custom(name, params) => {
@@ -235,9 +332,15 @@ impl Default for Action {
 /// - **Clipboard**: `copy`, `cut`, `paste`
 /// - **History**: `undo`, `redo`
 /// - **Selection**: `start_selection`, `clear_selection`
-/// - **Editing**: `insert_newline`, `delete_char`
+/// - **Multiple Cursors**: `add_cursor_above`, `add_cursor_below`, `add_next_occurrence`,
+///   `select_all_occurrences`, `split_selection_into_lines`, `clear_cursors`
+/// - **Block Selection**: `block_select_up`, `block_select_down`, `block_select_left`, `block_select_right`
+/// - **Editing**: `insert_char`, `insert_newline`, `delete_char`
+/// - **Indentation**: `indent`, `dedent`
 /// - **Navigation**: `page_up`, `page_down`
 /// - **Movement**: `move_up`, `move_down`, `move_left`, `move_right`, `move_home`, `move_end`
+/// - **Motions**: `move_word_left`, `move_word_right`, `move_paragraph_up`, `move_paragraph_down`,
+///   `move_file_start`, `move_file_end`, `move_matching_bracket`
 /// 
 /// # Example
 /// 
@@ -302,7 +405,20 @@ impl ActionRegistry {
     fn register_default_actions(&mut self) {
         self.register("quit", Action::Quit);
         self.register("save", Action::Save);
+        self.register("restore_backup", Action::RestoreBackup);
+        self.register("line_endings_lf", Action::SetLineEnding(LineEnding::Lf));
+        self.register("line_endings_crlf", Action::SetLineEnding(LineEnding::CrLf));
+        self.register("line_endings_cr", Action::SetLineEnding(LineEnding::Cr));
+        self.register("choose_line_ending", Action::ChooseLineEnding);
+        self.register("reopen_with_encoding", Action::ReopenWithEncoding);
+        self.register("save_with_encoding", Action::SaveWithEncoding);
+        self.register("next_buffer", Action::NextBuffer);
+        self.register("previous_buffer", Action::PreviousBuffer);
+        self.register("toggle_read_only", Action::ToggleReadOnly);
         self.register("find", Action::Find);
+        self.register("goto_line", Action::GoToLine);
+        self.register("jump_back", Action::JumpBack);
+        self.register("jump_forward", Action::JumpForward);
         self.register("copy", Action::Copy);
         self.register("cut", Action::Cut);
         self.register("paste", Action::Paste);
@@ -310,8 +426,22 @@ impl ActionRegistry {
         self.register("redo", Action::Redo);
         self.register("start_selection", Action::StartSelection);
         self.register("clear_selection", Action::ClearSelection);
+        self.register("add_cursor_above", Action::AddCursorAbove);
+        self.register("add_cursor_below", Action::AddCursorBelow);
+        self.register("add_next_occurrence", Action::AddNextOccurrence);
+        self.register("select_all_occurrences", Action::SelectAllOccurrences);
+        self.register("split_selection_into_lines", Action::SplitSelectionIntoLines);
+        self.register("clear_cursors", Action::ClearCursors);
+        self.register("block_select_up", Action::BlockSelect(KeyCode::Up));
+        self.register("block_select_down", Action::BlockSelect(KeyCode::Down));
+        self.register("block_select_left", Action::BlockSelect(KeyCode::Left));
+        self.register("block_select_right", Action::BlockSelect(KeyCode::Right));
+        // Bound to Space and Tab; the editor turns it into indentation for Tab
+        self.register("insert_char", Action::InsertChar(' '));
         self.register("insert_newline", Action::InsertNewline);
         self.register("delete_char", Action::DeleteChar);
+        self.register("indent", Action::Indent);
+        self.register("dedent", Action::Dedent);
         self.register("page_up", Action::PageUp);
         self.register("page_down", Action::PageDown);
         
@@ -322,6 +452,13 @@ impl ActionRegistry {
         self.register("move_right", Action::MoveCursor(KeyCode::Right));
         self.register("move_home", Action::MoveCursor(KeyCode::Home));
         self.register("move_end", Action::MoveCursor(KeyCode::End));
+        self.register("move_word_left", Action::Motion(CursorMotion::WordLeft));
+        self.register("move_word_right", Action::Motion(CursorMotion::WordRight));
+        self.register("move_paragraph_up", Action::Motion(CursorMotion::ParagraphUp));
+        self.register("move_paragraph_down", Action::Motion(CursorMotion::ParagraphDown));
+        self.register("move_file_start", Action::Motion(CursorMotion::FileStart));
+        self.register("move_file_end", Action::Motion(CursorMotion::FileEnd));
+        self.register("move_matching_bracket", Action::Motion(CursorMotion::MatchingBracket));
     }
 
     /// Registers a new action with the given name.
@@ -533,14 +670,54 @@ impl ActionExecutor {
                 output.find().map_err(|e| e.to_string())?;
                 Ok(true)
             }
+            Action::GoToLine => {
+                output.goto_prompt().map_err(|e| e.to_string())?;
+                Ok(true)
+            }
+            Action::JumpBack => {
+                output.jump_back();
+                Ok(true)
+            }
+            Action::JumpForward => {
+                output.jump_forward();
+                Ok(true)
+            }
+            Action::RestoreBackup => {
+                output.restore_backup().map_err(|e| e.to_string())?;
+                Ok(true)
+            }
+            Action::SetLineEnding(line_ending) => {
+                output.set_line_ending(*line_ending);
+                Ok(true)
+            }
+            Action::ChooseLineEnding => {
+                output.choose_line_ending().map_err(|e| e.to_string())?;
+                Ok(true)
+            }
+            Action::ReopenWithEncoding => {
+                output.reopen_with_encoding().map_err(|e| e.to_string())?;
+                Ok(true)
+            }
+            Action::SaveWithEncoding => {
+                // This will be handled by the editor's save logic
+                Ok(true)
+            }
+            Action::NextBuffer | Action::PreviousBuffer => {
+                // Buffers are owned by the editor, which switches them
+                Ok(true)
+            }
+            Action::ToggleReadOnly => {
+                output.toggle_read_only();
+                Ok(true)
+            }
             Action::Copy => {
-                if output.has_selection() {
+                if output.has_selection() || output.has_block_selection() {
                     output.copy_selection();
                 }
                 Ok(true)
             }
             Action::Cut => {
-                if output.has_selection() {
+                if output.has_selection() || output.has_block_selection() {
                     output.cut_selection();
                 }
                 Ok(true)
@@ -550,7 +727,9 @@ impl ActionExecutor {
                 Ok(true)
             }
             Action::Undo => {
-                output.pop_undo();
+                if output.ensure_writable() {
+                    output.pop_undo();
+                }
                 Ok(true)
             }
             Action::Redo => {
@@ -561,6 +740,10 @@ impl ActionExecutor {
                 output.move_cursor(*direction);
                 Ok(true)
             }
+            Action::Motion(motion) => {
+                output.apply_motion(*motion);
+                Ok(true)
+            }
             Action::StartSelection => {
                 output.start_selection();
                 Ok(true)
@@ -569,6 +752,34 @@ impl ActionExecutor {
                 output.clear_selection();
                 Ok(true)
             }
+            Action::AddCursorAbove => {
+                output.add_cursor_vertical(true);
+                Ok(true)
+            }
+            Action::AddCursorBelow => {
+                output.add_cursor_vertical(false);
+                Ok(true)
+            }
+            Action::AddNextOccurrence => {
+                output.add_next_occurrence();
+                Ok(true)
+            }
+            Action::SelectAllOccurrences => {
+                output.select_all_occurrences();
+                Ok(true)
+            }
+            Action::SplitSelectionIntoLines => {
+                output.split_selection_into_lines();
+                Ok(true)
+            }
+            Action::ClearCursors => {
+                output.clear_cursors();
+                Ok(true)
+            }
+            Action::BlockSelect(direction) => {
+                output.extend_block_selection(*direction);
+                Ok(true)
+            }
             Action::InsertChar(ch) => {
                 output.insert_char(*ch);
                 Ok(true)
@@ -581,16 +792,27 @@ impl ActionExecutor {
                 output.delete_char();
                 Ok(true)
             }
+            Action::Indent => {
+                output.indent_lines();
+                Ok(true)
+            }
+            Action::Dedent => {
+                output.dedent_lines();
+                Ok(true)
+            }
             Action::PageUp => {
                 // Handle page up logic
+                let column = output.cursor_controller.remember_column(&output.editor_rows);
                 output.cursor_controller.cursor_y = output.cursor_controller.row_offset;
                 for _ in 0..output.win_size.1 {
                     output.move_cursor(KeyCode::Up);
                 }
+                output.cursor_controller.place_at_column(column, &output.editor_rows);
                 Ok(true)
             }
             Action::PageDown => {
                 // Handle page down logic
+                let column = output.cursor_controller.remember_column(&output.editor_rows);
                 output.cursor_controller.cursor_y = std::cmp::min(
                     output.win_size.1 + output.cursor_controller.row_offset - 1,
                     output.editor_rows.number_of_rows(),
@@ -598,6 +820,7 @@ impl ActionExecutor {
                 for _ in 0..output.win_size.1 {
                     output.move_cursor(KeyCode::Down);
                 }
+                output.cursor_controller.place_at_column(column, &output.editor_rows);
                 Ok(true)
             }
             Action::Custom(name, _params) => {
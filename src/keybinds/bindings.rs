@@ -106,6 +106,8 @@ pub enum KeybindContext {
     Debug,
     /// Clipboard mode keybinds
     Clipboard,
+    /// Read-only viewer keybinds (`ninja view`)
+    View,
     /// Custom context
     Custom(String),
 }
@@ -120,6 +122,7 @@ impl KeybindContext {
             KeybindContext::Prompt => "Prompt",
             KeybindContext::Debug => "Debug",
             KeybindContext::Clipboard => "Clipboard",
+            KeybindContext::View => "View",
             KeybindContext::Custom(name) => name,
         }
     }
@@ -22,6 +22,7 @@
 //! - **Prompt**: Prompt/input mode keybinds
 //! - **Debug**: Debug mode keybinds
 //! - **Clipboard**: Clipboard management keybinds
+//! - **View**: Pager keybinds for `ninja view`, checked before the editor keybinds
 //! 
 //! ## Default Keybinds
 //! 
@@ -503,6 +504,7 @@ impl KeybindManager {
     /// - **Global**: Ctrl+Q (quit)
     /// - **Editor**: File operations, editing, navigation, search
     /// - **Search**: Search-specific navigation and commands
+    /// - **View**: Paging, searching and quitting in `ninja view`
     /// - **Prompt**: Input handling and confirmation
     fn register_default_keybinds(&mut self) {
         use crossterm::event::KeyCode;
@@ -535,6 +537,76 @@ impl KeybindManager {
                 KeybindContext::Editor,
                 "Find/search in the current file".to_string(),
             ),
+            Keybind::with_description(
+                KeyCode::Char('g'),
+                KeyModifiers::CONTROL,
+                "goto_line".to_string(),
+                KeybindContext::Editor,
+                "Go to a line and column".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Left,
+                KeyModifiers::ALT,
+                "jump_back".to_string(),
+                KeybindContext::Editor,
+                "Jump back to the previous position".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Right,
+                KeyModifiers::ALT,
+                "jump_forward".to_string(),
+                KeybindContext::Editor,
+                "Jump forward to the next position".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Char('b'),
+                KeyModifiers::ALT,
+                "restore_backup".to_string(),
+                KeybindContext::Editor,
+                "Restore the current file from a backup".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Char('l'),
+                KeyModifiers::ALT,
+                "choose_line_ending".to_string(),
+                KeybindContext::Editor,
+                "Choose line ending style".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Char('e'),
+                KeyModifiers::ALT,
+                "reopen_with_encoding".to_string(),
+                KeybindContext::Editor,
+                "Reopen the current file with a different encoding".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Char('s'),
+                KeyModifiers::ALT,
+                "save_with_encoding".to_string(),
+                KeybindContext::Editor,
+                "Save the current file with a different encoding".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::PageDown,
+                KeyModifiers::CONTROL,
+                "next_buffer".to_string(),
+                KeybindContext::Editor,
+                "Switch to the next open buffer".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::PageUp,
+                KeyModifiers::CONTROL,
+                "previous_buffer".to_string(),
+                KeybindContext::Editor,
+                "Switch to the previous open buffer".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Char('r'),
+                KeyModifiers::ALT,
+                "toggle_read_only".to_string(),
+                KeybindContext::Editor,
+                "Turn read-only mode on or off".to_string(),
+            ),
             
             // Edit operations
             Keybind::with_description(
@@ -600,7 +672,7 @@ impl KeybindManager {
                 KeyModifiers::NONE,
                 "move_home".to_string(),
                 KeybindContext::Editor,
-                "Move cursor to beginning of line".to_string(),
+                "Move cursor to first non-blank or beginning of line".to_string(),
             ),
             Keybind::with_description(
                 KeyCode::End,
@@ -609,6 +681,55 @@ impl KeybindManager {
                 KeybindContext::Editor,
                 "Move cursor to end of line".to_string(),
             ),
+            Keybind::with_description(
+                KeyCode::Left,
+                KeyModifiers::CONTROL,
+                "move_word_left".to_string(),
+                KeybindContext::Editor,
+                "Move cursor to the previous word".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Right,
+                KeyModifiers::CONTROL,
+                "move_word_right".to_string(),
+                KeybindContext::Editor,
+                "Move cursor to the next word".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Up,
+                KeyModifiers::CONTROL,
+                "move_paragraph_up".to_string(),
+                KeybindContext::Editor,
+                "Move cursor to the previous paragraph".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Down,
+                KeyModifiers::CONTROL,
+                "move_paragraph_down".to_string(),
+                KeybindContext::Editor,
+                "Move cursor to the next paragraph".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Home,
+                KeyModifiers::CONTROL,
+                "move_file_start".to_string(),
+                KeybindContext::Editor,
+                "Move cursor to the start of the file".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::End,
+                KeyModifiers::CONTROL,
+                "move_file_end".to_string(),
+                KeybindContext::Editor,
+                "Move cursor to the end of the file".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Char('m'),
+                KeyModifiers::ALT,
+                "move_matching_bracket".to_string(),
+                KeybindContext::Editor,
+                "Move cursor to the matching bracket".to_string(),
+            ),
             Keybind::with_description(
                 KeyCode::PageUp,
                 KeyModifiers::NONE,
@@ -658,7 +779,7 @@ impl KeybindManager {
                 KeyModifiers::SHIFT,
                 "move_home".to_string(),
                 KeybindContext::Editor,
-                "Move cursor to beginning of line with selection".to_string(),
+                "Move cursor to first non-blank or beginning of line with selection".to_string(),
             ),
             Keybind::with_description(
                 KeyCode::End,
@@ -667,6 +788,129 @@ impl KeybindManager {
                 KeybindContext::Editor,
                 "Move cursor to end of line with selection".to_string(),
             ),
+            Keybind::with_description(
+                KeyCode::Left,
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+                "move_word_left".to_string(),
+                KeybindContext::Editor,
+                "Move cursor to the previous word with selection".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Right,
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+                "move_word_right".to_string(),
+                KeybindContext::Editor,
+                "Move cursor to the next word with selection".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Up,
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+                "move_paragraph_up".to_string(),
+                KeybindContext::Editor,
+                "Move cursor to the previous paragraph with selection".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Down,
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+                "move_paragraph_down".to_string(),
+                KeybindContext::Editor,
+                "Move cursor to the next paragraph with selection".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Home,
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+                "move_file_start".to_string(),
+                KeybindContext::Editor,
+                "Move cursor to the start of the file with selection".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::End,
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+                "move_file_end".to_string(),
+                KeybindContext::Editor,
+                "Move cursor to the end of the file with selection".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Char('M'),
+                KeyModifiers::ALT | KeyModifiers::SHIFT,
+                "move_matching_bracket".to_string(),
+                KeybindContext::Editor,
+                "Move cursor to the matching bracket with selection".to_string(),
+            ),
+            
+            // Multiple cursors
+            Keybind::with_description(
+                KeyCode::Up,
+                KeyModifiers::CONTROL | KeyModifiers::ALT,
+                "add_cursor_above".to_string(),
+                KeybindContext::Editor,
+                "Add a cursor on the line above".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Down,
+                KeyModifiers::CONTROL | KeyModifiers::ALT,
+                "add_cursor_below".to_string(),
+                KeybindContext::Editor,
+                "Add a cursor on the line below".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Char('d'),
+                KeyModifiers::CONTROL,
+                "add_next_occurrence".to_string(),
+                KeybindContext::Editor,
+                "Add a cursor at the next occurrence of the selection".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Char('d'),
+                KeyModifiers::ALT,
+                "select_all_occurrences".to_string(),
+                KeybindContext::Editor,
+                "Add a cursor at every occurrence of the selection".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Char('i'),
+                KeyModifiers::ALT,
+                "split_selection_into_lines".to_string(),
+                KeybindContext::Editor,
+                "Split the selection into one cursor per line".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Esc,
+                KeyModifiers::NONE,
+                "clear_cursors".to_string(),
+                KeybindContext::Editor,
+                "Return to a single cursor".to_string(),
+            ),
+            
+            // Block selection
+            Keybind::with_description(
+                KeyCode::Up,
+                KeyModifiers::SHIFT | KeyModifiers::ALT,
+                "block_select_up".to_string(),
+                KeybindContext::Editor,
+                "Extend block selection up".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Down,
+                KeyModifiers::SHIFT | KeyModifiers::ALT,
+                "block_select_down".to_string(),
+                KeybindContext::Editor,
+                "Extend block selection down".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Left,
+                KeyModifiers::SHIFT | KeyModifiers::ALT,
+                "block_select_left".to_string(),
+                KeybindContext::Editor,
+                "Extend block selection left".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Right,
+                KeyModifiers::SHIFT | KeyModifiers::ALT,
+                "block_select_right".to_string(),
+                KeybindContext::Editor,
+                "Extend block selection right".to_string(),
+            ),
             
             // Text editing
             Keybind::with_description(
@@ -697,6 +941,13 @@ impl KeybindManager {
                 KeybindContext::Editor,
                 "Insert tab".to_string(),
             ),
+            Keybind::with_description(
+                KeyCode::BackTab,
+                KeyModifiers::SHIFT,
+                "dedent".to_string(),
+                KeybindContext::Editor,
+                "Dedent selected lines".to_string(),
+            ),
             // Character input (for unbound characters)
             Keybind::with_description(
                 KeyCode::Char(' '),
@@ -707,6 +958,59 @@ impl KeybindManager {
             ),
         ];
 
+        // View keybinds, modelled on less
+        let view_keybinds = vec![
+            Keybind::with_description(
+                KeyCode::Char(' '),
+                KeyModifiers::NONE,
+                "page_down".to_string(),
+                KeybindContext::View,
+                "Page down".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Char('b'),
+                KeyModifiers::NONE,
+                "page_up".to_string(),
+                KeybindContext::View,
+                "Page up".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Char('j'),
+                KeyModifiers::NONE,
+                "move_down".to_string(),
+                KeybindContext::View,
+                "Scroll down one line".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Enter,
+                KeyModifiers::NONE,
+                "move_down".to_string(),
+                KeybindContext::View,
+                "Scroll down one line".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Char('k'),
+                KeyModifiers::NONE,
+                "move_up".to_string(),
+                KeybindContext::View,
+                "Scroll up one line".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Char('/'),
+                KeyModifiers::NONE,
+                "find".to_string(),
+                KeybindContext::View,
+                "Search forward".to_string(),
+            ),
+            Keybind::with_description(
+                KeyCode::Char('q'),
+                KeyModifiers::NONE,
+                "quit".to_string(),
+                KeybindContext::View,
+                "Quit".to_string(),
+            ),
+        ];
+
         // Prompt keybinds
         let prompt_keybinds = vec![
             Keybind::with_description(
@@ -735,6 +1039,7 @@ impl KeybindManager {
         // Register all keybinds
         self.register_multiple(global_keybinds);
         self.register_multiple(editor_keybinds);
+        self.register_multiple(view_keybinds);
         self.register_multiple(prompt_keybinds);
     }
 }
@@ -16,10 +16,11 @@
 //! ## Usage
 //! 
 //! ```rust
+//! use ninja::cli::CliOptions;
 //! use ninja::run_editor;
 //! 
 //! fn main() -> crossterm::Result<()> {
-//!     run_editor()
+//!     run_editor(CliOptions::default())
 //! }
 //! ```
 //! 
@@ -27,6 +28,7 @@
 //! 
 //! The editor is organized into several modules:
 //! 
+//! - **`cli`**: Command-line argument parsing
 //! - **`config`**: Configuration management and settings
 //! - **`modules`**: Core editor functionality (cursor, highlighting, search, etc.)
 //! - **`screens`**: Different editor screens and UI management
@@ -38,6 +40,7 @@
 //! The editor can be configured via a TOML file. See the `config` module for details
 //! on available options.
 
+pub mod cli;
 pub mod modules;
 pub mod config;
 pub mod transput;
@@ -47,7 +50,7 @@ pub mod keybinds;
 /// Current version of the Ninja editor
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
-/// Default tab stop width in spaces
+/// Tab width used for rows that are not part of a configured buffer
 pub const TAB_STOP: usize = 8;
 
 /// Runs the main editor application.
@@ -56,6 +59,10 @@ pub const TAB_STOP: usize = 8;
 /// and starts the main editor loop. It handles the complete lifecycle of the editor
 /// from startup to shutdown.
 /// 
+/// # Arguments
+/// 
+/// * `options` - The files to open and flags, usually from `cli::parse`
+/// 
 /// # Returns
 /// 
 /// Returns `Ok(())` on successful completion, or an error if the editor
@@ -65,39 +72,93 @@ pub const TAB_STOP: usize = 8;
 /// 
 /// This function will return an error if:
 /// - Terminal raw mode cannot be enabled
-/// - Configuration cannot be loaded
+/// - A configuration file given with `--config` cannot be loaded
+/// - Standard input cannot be read, or the terminal cannot be reopened after it
+/// - In pipe mode, the buffer was never saved
 /// - The editor encounters an unrecoverable error
 /// 
 /// # Example
 /// 
 /// ```rust
+/// use ninja::cli::CliOptions;
 /// use ninja::run_editor;
 /// 
 /// fn main() -> crossterm::Result<()> {
-///     run_editor()
+///     run_editor(CliOptions::default())
 /// }
 /// ```
-pub fn run_editor() -> crossterm::Result<()> {
+pub fn run_editor(mut options: cli::CliOptions) -> crossterm::Result<()> {
+    use crate::cli::{ConfigSource, FileArg, FileSource};
     use crate::config::NinjaConfig;
+    use crate::modules::tty;
     use crate::screens::screens::ScreenManager;
-    use crossterm::terminal;
+    use crossterm::{event, execute, terminal};
+    use std::io::{self, stdout, IsTerminal, Read, Write};
     
     // Load configuration
-    let config = NinjaConfig::load().unwrap_or_else(|e| {
-        eprintln!("Failed to load configuration: {}", e);
-        eprintln!("Using default configuration...");
-        NinjaConfig::default()
-    });
+    let config = match &options.config {
+        ConfigSource::Default => {
+            let config = NinjaConfig::load().unwrap_or_else(|e| {
+                eprintln!("Failed to load configuration: {}", e);
+                eprintln!("Using default configuration...");
+                NinjaConfig::default()
+            });
+            
+            // Create default config file if it doesn't exist
+            if let Err(e) = NinjaConfig::create_default_config() {
+                eprintln!("Warning: Could not create default config file: {}", e);
+            }
+            config
+        }
+        ConfigSource::File(path) => NinjaConfig::load_from(path).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("could not load {}: {}", path.display(), e),
+            )
+        })?,
+        ConfigSource::None => NinjaConfig::default(),
+    };
     
-    // Create default config file if it doesn't exist
-    if let Err(e) = NinjaConfig::create_default_config() {
-        eprintln!("Warning: Could not create default config file: {}", e);
+    // As a filter without a file, edit whatever is piped in
+    if options.pipe && options.files.is_empty() && !io::stdin().is_terminal() {
+        options.files.push(FileArg {
+            source: FileSource::Stdin,
+            line: None,
+            column: None,
+        });
     }
     
+    // Standard input must be read before the terminal switches to raw mode
+    let stdin = if options.reads_stdin() {
+        let mut bytes = Vec::new();
+        io::stdin().read_to_end(&mut bytes)?;
+        tty::reattach_stdin()?;
+        Some(bytes)
+    } else {
+        None
+    };
+    // The screen is drawn on the terminal; the pipe only gets the result
+    let mut pipe = if options.pipe {
+        Some(tty::detach_stdout()?)
+    } else {
+        None
+    };
+    
     terminal::enable_raw_mode()?;
+    // Focus events drive auto-save on focus loss
+    execute!(stdout(), event::EnableFocusChange)?;
     let mut screen_manager = ScreenManager::new();
-    screen_manager.show_editor_screen(config);
+    screen_manager.open_files(config, &options, stdin);
     screen_manager.run_active();
+    execute!(stdout(), event::DisableFocusChange)?;
+    
+    if let Some(pipe) = &mut pipe {
+        let contents = screen_manager.take_pipe_output().ok_or_else(|| {
+            io::Error::other("buffer was not saved; nothing written to standard output")
+        })?;
+        pipe.write_all(&contents)?;
+        pipe.flush()?;
+    }
     Ok(())
 }
 
@@ -176,6 +237,7 @@ macro_rules! prompt {
                 KeyEvent {
                     code: KeyCode::Enter,
                     modifiers: KeyModifiers::NONE,
+                    ..
                 } => {
                     if !input.is_empty() {
                         output.status_message.set_message(String::new());
@@ -194,12 +256,14 @@ macro_rules! prompt {
                 KeyEvent {
                     code: KeyCode::Backspace | KeyCode::Delete,
                     modifiers: KeyModifiers::NONE,
+                    ..
                 } => {
                     input.pop();
                 }
                 KeyEvent {
                     code: code @ (KeyCode::Char(..) | KeyCode::Tab),
                     modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+                    ..
                 } => {
                     input.push(match code {
                         KeyCode::Tab => '\t',
@@ -4,7 +4,9 @@
 //! It provides a simple wrapper around the library's `run_editor` function
 //! and handles cleanup when the program exits.
 
-use ninja::run_editor;
+use ninja::cli::{self, Command};
+use ninja::{run_editor, VERSION};
+use std::{env, process};
 
 /// Cleanup handler that ensures proper terminal restoration on exit.
 /// 
@@ -20,20 +22,34 @@ impl Drop for CleanUp {
 
 /// Main entry point for the Ninja editor.
 /// 
-/// This function initializes the cleanup handler and starts the editor.
-/// The cleanup handler ensures that the terminal is properly restored
-/// when the program exits, regardless of how it terminates.
+/// This function parses the command line, initializes the cleanup handler
+/// and starts the editor. The cleanup handler ensures that the terminal is
+/// properly restored when the program exits, regardless of how it terminates.
 /// 
-/// # Returns
+/// # Exit Status
 /// 
-/// Returns `Ok(())` on successful completion, or propagates any errors
-/// from the editor.
-/// 
-/// # Errors
-/// 
-/// This function will return an error if the editor encounters any
-/// fatal errors during execution.
-fn main() -> crossterm::Result<()> {
+/// - `0` when the editor exits normally, or after `--help` / `--version`
+/// - `1` if the editor encounters a fatal error
+/// - `2` if the command line is invalid
+fn main() {
+    let options = match cli::parse(env::args_os().skip(1)) {
+        Ok(Command::Edit(options)) => options,
+        Ok(Command::Help) => {
+            print!("{}", cli::USAGE);
+            return;
+        }
+        Ok(Command::Version) => {
+            println!("ninja {}", VERSION);
+            return;
+        }
+        Err(e) => {
+            eprintln!("ninja: {}\nTry 'ninja --help' for more information.", e);
+            process::exit(2);
+        }
+    };
     let _clean_up = CleanUp;
-    run_editor()
-}
\ No newline at end of file
+    if let Err(e) = run_editor(options) {
+        eprintln!("ninja: {}", e);
+        process::exit(1);
+    }
+}
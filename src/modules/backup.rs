@@ -0,0 +1,261 @@
+//! # Backup Module
+//!
+//! This module creates and lists backup copies of files before they are
+//! overwritten by a save. Backups are taken on the first save of a file in
+//! each editing session.
+//!
+//! ## Features
+//!
+//! - **Simple Backups**: A single `file~` copy next to the original
+//! - **Backup Directory**: Optional central directory with path-mangled names
+//! - **Versioned Backups**: Keep the N most recent timestamped copies
+//! - **Listing**: Find all backups of a file for restoring
+//!
+//! ## Backup Names
+//!
+//! - `notes.txt~` - single backup (`backup_versions = 0`)
+//! - `notes.txt.20250101-120000~` - timestamped backup (`backup_versions > 0`)
+//! - `%home%user%notes.txt~` - either form inside a configured `backup_dir`
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use ninja::config::NinjaConfig;
+//! use ninja::modules::backup;
+//! use std::path::Path;
+//!
+//! let config = NinjaConfig::default();
+//! for entry in backup::list(Path::new("notes.txt"), &config.behavior) {
+//!     println!("{}", entry.label());
+//! }
+//! ```
+
+use crate::config::BehaviorConfig;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Suffix appended to every backup file name.
+const BACKUP_SUFFIX: char = '~';
+
+/// A backup copy of a file found on disk.
+#[derive(Debug, Clone)]
+pub struct BackupEntry {
+    /// Location of the backup file
+    pub path: PathBuf,
+    /// When the backup was last modified
+    pub modified: SystemTime,
+}
+
+impl BackupEntry {
+    /// Returns a human-readable description of the backup.
+    ///
+    /// # Returns
+    ///
+    /// Returns the modification time (UTC) followed by the backup file name.
+    pub fn label(&self) -> String {
+        let secs = self
+            .modified
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        format!(
+            "{}  {}",
+            format_timestamp(secs, false),
+            self.path
+                .file_name()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_default()
+        )
+    }
+}
+
+/// Creates a backup of a file before it is overwritten.
+///
+/// Nothing is done if the file does not exist yet. With `backup_versions`
+/// set to 0, a single `file~` backup is (re)written; otherwise a new
+/// timestamped backup is created and the oldest ones beyond the configured
+/// count are removed.
+///
+/// # Arguments
+///
+/// * `file` - The file about to be overwritten
+/// * `config` - Behavior settings controlling the backup location and count
+///
+/// # Returns
+///
+/// Returns `Ok(Some(path))` with the created backup, `Ok(None)` if the file
+/// does not exist, or an `io::Error` if the copy failed.
+///
+/// # Example
+///
+/// ```rust
+/// use ninja::config::NinjaConfig;
+/// use ninja::modules::backup;
+/// use std::path::Path;
+///
+/// let config = NinjaConfig::default();
+/// let created = backup::create(Path::new("does-not-exist.txt"), &config.behavior);
+/// assert!(matches!(created, Ok(None)));
+/// ```
+pub fn create(file: &Path, config: &BehaviorConfig) -> io::Result<Option<PathBuf>> {
+    if !file.is_file() {
+        return Ok(None);
+    }
+    let (dir, base) = location(file, config)?;
+    fs::create_dir_all(&dir)?;
+
+    let name = if config.backup_versions == 0 {
+        format!("{}{}", base, BACKUP_SUFFIX)
+    } else {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        format!("{}.{}{}", base, format_timestamp(secs, true), BACKUP_SUFFIX)
+    };
+    let path = dir.join(name);
+    fs::copy(file, &path)?;
+
+    if config.backup_versions > 0 {
+        let mut versions: Vec<PathBuf> = entries(&dir, &base)
+            .into_iter()
+            .filter(|candidate| is_versioned(candidate, &base))
+            .collect();
+        // Timestamps sort lexicographically, newest last
+        versions.sort();
+        let excess = versions.len().saturating_sub(config.backup_versions as usize);
+        for old in &versions[..excess] {
+            let _ = fs::remove_file(old);
+        }
+    }
+    Ok(Some(path))
+}
+
+/// Lists all backups of a file, newest first.
+///
+/// # Arguments
+///
+/// * `file` - The file whose backups should be listed
+/// * `config` - Behavior settings controlling the backup location
+///
+/// # Returns
+///
+/// Returns the backups found, sorted by modification time (newest first).
+pub fn list(file: &Path, config: &BehaviorConfig) -> Vec<BackupEntry> {
+    let Ok((dir, base)) = location(file, config) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<BackupEntry> = entries(&dir, &base)
+        .into_iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some(BackupEntry { path, modified })
+        })
+        .collect();
+    backups.sort_by_key(|entry| std::cmp::Reverse(entry.modified));
+    backups
+}
+
+/// Returns the directory holding backups of `file` and their common name prefix.
+fn location(file: &Path, config: &BehaviorConfig) -> io::Result<(PathBuf, String)> {
+    let no_name = || io::Error::new(io::ErrorKind::InvalidInput, "file has no name");
+    if config.backup_dir.is_empty() {
+        let base = file.file_name().ok_or_else(no_name)?.to_string_lossy().into_owned();
+        let dir = match file.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        Ok((dir, base))
+    } else {
+        let absolute = match fs::canonicalize(file) {
+            Ok(path) => path,
+            Err(_) => std::env::current_dir()?.join(file),
+        };
+        let base = absolute.to_string_lossy().replace(['/', '\\', ':'], "%");
+        Ok((expand_home(&config.backup_dir), base))
+    }
+}
+
+/// Expands a leading `~` in a configured directory to the home directory.
+fn expand_home(dir: &str) -> PathBuf {
+    match (dir.strip_prefix("~"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest.trim_start_matches(['/', '\\'])),
+        _ => PathBuf::from(dir),
+    }
+}
+
+/// Returns all backup files for `base` inside `dir`.
+fn entries(dir: &Path, base: &str) -> Vec<PathBuf> {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    read_dir
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+            name.strip_prefix(base)
+                .and_then(|rest| rest.strip_suffix(BACKUP_SUFFIX))
+                .is_some_and(|stamp| stamp.is_empty() || is_timestamp(stamp))
+        })
+        .collect()
+}
+
+/// Returns `true` if `path` is a timestamped backup of `base`.
+fn is_versioned(path: &Path, base: &str) -> bool {
+    path.file_name()
+        .map(|name| name.to_string_lossy())
+        .and_then(|name| {
+            name.strip_prefix(base)
+                .and_then(|rest| rest.strip_suffix(BACKUP_SUFFIX))
+                .map(|stamp| !stamp.is_empty())
+        })
+        .unwrap_or(false)
+}
+
+/// Returns `true` for a `.YYYYMMDD-HHMMSS` timestamp suffix.
+fn is_timestamp(stamp: &str) -> bool {
+    let Some(stamp) = stamp.strip_prefix('.') else {
+        return false;
+    };
+    stamp.len() == 15
+        && stamp.char_indices().all(|(i, ch)| {
+            if i == 8 {
+                ch == '-'
+            } else {
+                ch.is_ascii_digit()
+            }
+        })
+}
+
+/// Formats seconds since the Unix epoch as a UTC date and time.
+///
+/// The compact form (`20250101-120000`) is used in file names; the long
+/// form (`2025-01-01 12:00:00 UTC`) is used for display.
+fn format_timestamp(secs: u64, compact: bool) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    let (hour, minute, second) = (rem / 3600, (rem % 3600) / 60, rem % 60);
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    if compact {
+        format!("{:04}{:02}{:02}-{:02}{:02}{:02}", year, month, day, hour, minute, second)
+    } else {
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+            year, month, day, hour, minute, second
+        )
+    }
+}
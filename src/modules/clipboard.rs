@@ -11,6 +11,7 @@
 //! - **Thread Safety**: Safe for use across multiple threads
 //! - **Persistent Storage**: Maintains clipboard history during editor session
 //! - **Flexible Access**: Peek, paste, and remove items from any position
+//! - **Rectangles**: Remembers text copied from a block selection so it pastes column-wise
 //! 
 //! ## Usage
 //! 
@@ -70,7 +71,9 @@ pub struct Clipboard {
     /// Internal stack of clipboard items
     stack: Vec<String>,
     /// System clipboard context for external integration
-    ctx: ClipboardContext
+    ctx: ClipboardContext,
+    /// The most recent item if it was copied from a block selection
+    rectangle: Option<String>,
 }
 
 impl Clipboard {
@@ -96,7 +99,8 @@ impl Clipboard {
         //Clipboard { stack: Vec::new(), ctx: ClipboardContext::new().unwrap() }
         let mut cb = Clipboard {
             stack: Vec::new(),
-            ctx: ClipboardContext::new().unwrap()
+            ctx: ClipboardContext::new().unwrap(),
+            rectangle: None,
         };
         if let Ok(contents) = cb.ctx.get_contents() {
             cb.stack.push(contents);
@@ -148,11 +152,50 @@ impl Clipboard {
     /// ```
     pub fn add(&mut self, text: String) {
         if !text.is_empty() {
+            self.rectangle = None;
             self.stack.push(text.clone());
             self.ctx.set_contents(text.clone()).unwrap();
         }
     }
 
+    /// Adds text copied from a block selection, one line per row of the block.
+    /// 
+    /// The text is added like any other item, and is also remembered as a
+    /// rectangle so that pasting it inserts the lines column-wise.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `text` - The rows of the block, separated by newlines
+    /// 
+    /// # Example
+    /// 
+    /// The clipboard needs a system clipboard, so this example is not run.
+    /// 
+    /// ```rust,no_run
+    /// use ninja::modules::clipboard::Clipboard;
+    /// 
+    /// let mut clipboard = Clipboard::new();
+    /// clipboard.add_rectangle("ab\ncd".to_string());
+    /// assert!(clipboard.is_rectangle("ab\ncd"));
+    /// ```
+    pub fn add_rectangle(&mut self, text: String) {
+        self.add(text.clone());
+        self.rectangle = Some(text);
+    }
+
+    /// Checks whether `text` is the most recent item and was copied from a block selection.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `text` - The text to check
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `true` if the text should be pasted column-wise.
+    pub fn is_rectangle(&self, text: &str) -> bool {
+        self.rectangle.as_deref() == Some(text) && self.stack.last().map(String::as_str) == Some(text)
+    }
+
     /// Removes and returns the most recent item from the clipboard stack.
     /// 
     /// This method removes the top item from the stack and returns it.
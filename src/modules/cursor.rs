@@ -12,6 +12,10 @@
 //! - **Tab Handling**: Properly calculates cursor position with tab characters
 //! - **Unicode Support**: Handles multi-byte characters correctly
 //! - **Boundary Checking**: Prevents cursor from moving to invalid positions
+//! - **Preferred Column**: Vertical movement keeps the display column across short lines
+//! - **Motions**: Word, paragraph, file start/end and matching-bracket jumps
+//! - **Bracket Matching**: Finds the partner of the bracket at the cursor, outside strings and comments
+//! - **Go To**: Jumps to a line, line and column, relative line or percentage, centered on screen
 //! 
 //! ## Coordinate Systems
 //! 
@@ -34,8 +38,9 @@
 use std::cmp;
 use std::cmp::Ordering;
 use crossterm::event::KeyCode;
+use crate::modules::graphemes;
+use crate::modules::highlighting::HighlightType;
 use crate::screens::editor::{EditorRows, Row};
-use crate::TAB_STOP;
 
 /// Controls cursor positioning and movement in the editor.
 /// 
@@ -52,6 +57,7 @@ use crate::TAB_STOP;
 /// - `row_offset`: Vertical scroll offset
 /// - `column_offset`: Horizontal scroll offset
 /// - `render_x`: Visual horizontal position accounting for tabs and Unicode
+/// - `preferred_column`: Display column kept across vertical movement
 /// 
 /// # Example
 /// 
@@ -81,6 +87,9 @@ pub struct CursorController {
     pub column_offset: usize,
     /// Visual horizontal position accounting for tabs and Unicode characters
     pub render_x: usize,
+    /// Display column kept across vertical movement, with the `(row, column)`
+    /// it was last applied at; any other cursor change makes it stale
+    preferred_column: Option<(usize, (usize, usize))>,
 }
 
 impl CursorController {
@@ -114,6 +123,7 @@ impl CursorController {
             row_offset: 0,
             column_offset: 0,
             render_x: 0,
+            preferred_column: None,
         }
     }
 
@@ -137,17 +147,182 @@ impl CursorController {
     /// // render_x accounts for tabs and Unicode characters
     /// ```
     fn get_render_x(&self, row: &Row) -> usize {
-        row.row_content
-            .chars()
-            .take(self.cursor_x)
-            .fold(0, |render_x, c| {
-                if c == '\t' {
-                    render_x + (TAB_STOP - 1) - (render_x % TAB_STOP) + 1
-                } else {
-                    // Use the shared Unicode width calculation
-                    render_x + Row::char_width(c)
-                }
-            })
+        graphemes::clusters(&row.row_content)
+            .take_while(|(index, _)| *index < self.cursor_x)
+            .fold(0, |column, (_, grapheme)| graphemes::advance(column, grapheme, row.tab_width))
+    }
+
+    /// Finds the character index in `row` that is displayed at `render_x`.
+    /// 
+    /// A character that straddles the column, such as a tab or a wide
+    /// character, places the cursor before it. Columns past the end of the
+    /// row map to the end of the row.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `row` - The row to look in
+    /// * `render_x` - The display column
+    /// 
+    /// # Returns
+    /// 
+    /// Returns the character index.
+    pub fn cursor_x_for_render_x(row: &Row, render_x: usize) -> usize {
+        row.get_row_content_x(render_x)
+    }
+
+    /// Returns the number of display columns `row` takes up.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `row` - The row to measure
+    pub fn row_width(row: &Row) -> usize {
+        graphemes::clusters(&row.row_content)
+            .fold(0, |column, (_, grapheme)| graphemes::advance(column, grapheme, row.tab_width))
+    }
+
+    /// Finds the grapheme clusters of `row` that start within a range of display columns.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `row` - The row to look in
+    /// * `left` - The first display column of the range
+    /// * `right` - The display column just past the range
+    /// 
+    /// # Returns
+    /// 
+    /// Returns the range of character indices.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::modules::cursor::CursorController;
+    /// use ninja::screens::editor::EditorRows;
+    /// 
+    /// let editor_rows = EditorRows::from_bytes("a漢字bc\n".as_bytes(), &None);
+    /// let row = editor_rows.get_editor_row(0);
+    /// // "漢" covers columns 1-2 and "字" covers columns 3-4
+    /// assert_eq!(CursorController::char_range_for_columns(row, 1, 4), 1..3);
+    /// assert_eq!(CursorController::char_range_for_columns(row, 2, 6), 2..4);
+    /// ```
+    pub fn char_range_for_columns(row: &Row, left: usize, right: usize) -> std::ops::Range<usize> {
+        let mut start = None;
+        let mut end = 0;
+        let mut current = 0;
+        for (index, grapheme) in graphemes::clusters(&row.row_content) {
+            if current >= right {
+                break;
+            }
+            if current >= left {
+                start.get_or_insert(index);
+                end = index + grapheme.chars().count();
+            }
+            current = graphemes::advance(current, grapheme, row.tab_width);
+        }
+        match start {
+            Some(start) => start..end,
+            None => {
+                let index = Self::cursor_x_for_render_x(row, left);
+                index..index
+            }
+        }
+    }
+
+    /// Returns the display column that vertical movement would keep.
+    /// 
+    /// This is the remembered preferred column if the cursor has not moved
+    /// since it was set, or the cursor's display column otherwise.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `editor_rows` - The editor's row collection
+    pub fn preferred_display_column(&self, editor_rows: &EditorRows) -> usize {
+        match self.preferred_column {
+            Some((column, at)) if at == (self.cursor_y, self.cursor_x) => column,
+            _ => self.display_column(editor_rows),
+        }
+    }
+
+    /// Returns the display column to keep during vertical movement.
+    /// 
+    /// The column is taken from the cursor unless the cursor is still where
+    /// the last vertical movement left it, in which case the remembered
+    /// column is kept. This lets the cursor pass through short lines without
+    /// drifting left.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `editor_rows` - The editor's row collection
+    /// 
+    /// # Returns
+    /// 
+    /// Returns the preferred display column.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use crossterm::event::KeyCode;
+    /// use ninja::modules::cursor::CursorController;
+    /// use ninja::screens::editor::EditorRows;
+    /// 
+    /// let editor_rows = EditorRows::from_bytes("abcdef\nab\n漢字漢字\n".as_bytes(), &None);
+    /// let mut cursor = CursorController::new((80, 24));
+    /// cursor.cursor_x = 4;
+    /// cursor.move_cursor(KeyCode::Down, &editor_rows);
+    /// assert_eq!(cursor.cursor_x, 2);
+    /// cursor.move_cursor(KeyCode::Down, &editor_rows);
+    /// // Display column 4 is after the second double-width character
+    /// assert_eq!(cursor.cursor_x, 2);
+    /// assert_eq!(cursor.remember_column(&editor_rows), 4);
+    /// ```
+    pub fn remember_column(&mut self, editor_rows: &EditorRows) -> usize {
+        match self.preferred_column {
+            Some((column, at)) if at == (self.cursor_y, self.cursor_x) => column,
+            _ => {
+                let column = self.display_column(editor_rows);
+                self.preferred_column = Some((column, (self.cursor_y, self.cursor_x)));
+                column
+            }
+        }
+    }
+
+    /// Returns the display column of the cursor, accounting for tabs and wide characters.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `editor_rows` - The editor's row collection
+    /// 
+    /// # Returns
+    /// 
+    /// Returns the column, before horizontal scrolling, at which the cursor is drawn.
+    pub fn display_column(&self, editor_rows: &EditorRows) -> usize {
+        if self.cursor_y < editor_rows.number_of_rows() {
+            self.get_render_x(editor_rows.get_editor_row(self.cursor_y))
+        } else {
+            0
+        }
+    }
+
+    /// Places the cursor on the current row as close as possible to a display column.
+    /// 
+    /// The column stays remembered for further vertical movement.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `column` - The display column, usually from `remember_column`
+    /// * `editor_rows` - The editor's row collection
+    pub fn place_at_column(&mut self, column: usize, editor_rows: &EditorRows) {
+        self.cursor_x = if self.cursor_y < editor_rows.number_of_rows() {
+            Self::cursor_x_for_render_x(editor_rows.get_editor_row(self.cursor_y), column)
+        } else {
+            0
+        };
+        self.preferred_column = Some((column, (self.cursor_y, self.cursor_x)));
+    }
+
+    /// Forgets the preferred column, so the next vertical movement starts
+    /// from the cursor's current column.
+    pub fn reset_preferred_column(&mut self) {
+        self.preferred_column = None;
     }
 
     /// Updates scroll offsets to keep the cursor visible on screen.
@@ -200,9 +375,15 @@ impl CursorController {
     /// - `KeyCode::Down`: Move cursor down one line
     /// - `KeyCode::Left`: Move cursor left one character, wrap to previous line
     /// - `KeyCode::Right`: Move cursor right one character, wrap to next line
-    /// - `KeyCode::Home`: Move cursor to beginning of current line
+    /// - `KeyCode::Home`: Move cursor to the first non-blank character, or to
+    ///   the beginning of the line if it is already there
     /// - `KeyCode::End`: Move cursor to end of current line
     /// 
+    /// Any other key leaves the cursor where it is.
+    /// 
+    /// Up and Down keep the display column the cursor had before the first
+    /// vertical move, so passing through a short line does not pull it left.
+    /// 
     /// # Example
     /// 
     /// ```rust
@@ -215,6 +396,13 @@ impl CursorController {
     /// ```
     pub fn move_cursor(&mut self, direction: KeyCode, editor_rows: &EditorRows) {
         let number_of_rows = editor_rows.number_of_rows();
+        let preferred_column = match direction {
+            KeyCode::Up | KeyCode::Down => Some(self.remember_column(editor_rows)),
+            _ => {
+                self.reset_preferred_column();
+                None
+            }
+        };
 
         match direction {
             KeyCode::Up => {
@@ -222,43 +410,498 @@ impl CursorController {
             }
             KeyCode::Left => {
                 if self.cursor_x != 0 {
-                    self.cursor_x -= 1;
+                    let row = &editor_rows.get_editor_row(self.cursor_y).row_content;
+                    self.cursor_x = graphemes::previous_boundary(row, self.cursor_x);
                 } else if self.cursor_y > 0 {
                     self.cursor_y -= 1;
                     self.cursor_x = editor_rows.get_editor_row(self.cursor_y).char_count();
                 }
             }
-            KeyCode::Down => {
-                if self.cursor_y < number_of_rows {
-                    self.cursor_y += 1;
+            KeyCode::Down if self.cursor_y < number_of_rows => {
+                self.cursor_y += 1;
+            }
+            KeyCode::Right if self.cursor_y < number_of_rows => {
+                let row = editor_rows.get_editor_row(self.cursor_y);
+                match self.cursor_x.cmp(&row.char_count()) {
+                    Ordering::Less => {
+                        self.cursor_x = graphemes::next_boundary(&row.row_content, self.cursor_x)
+                    }
+                    Ordering::Equal => {
+                        self.cursor_y += 1;
+                        self.cursor_x = 0
+                    }
+                    _ => {}
                 }
             }
-            KeyCode::Right => {
-                if self.cursor_y < number_of_rows {
-                    let row_char_count = editor_rows.get_editor_row(self.cursor_y).char_count();
-                    match self.cursor_x.cmp(&row_char_count) {
-                        Ordering::Less => self.cursor_x += 1,
-                        Ordering::Equal => {
-                            self.cursor_y += 1;
-                            self.cursor_x = 0
-                        }
-                        _ => {}
+            KeyCode::End if self.cursor_y < number_of_rows => {
+                self.cursor_x = editor_rows.get_editor_row(self.cursor_y).char_count();
+            }
+            KeyCode::Home => {
+                let first_non_blank = if self.cursor_y < number_of_rows {
+                    editor_rows
+                        .get_editor_row(self.cursor_y)
+                        .row_content
+                        .chars()
+                        .take_while(|c| c.is_whitespace())
+                        .count()
+                } else {
+                    0
+                };
+                self.cursor_x = if self.cursor_x == first_non_blank {
+                    0
+                } else {
+                    first_non_blank
+                };
+            }
+            _ => {}
+        }
+        if let Some(column) = preferred_column {
+            self.place_at_column(column, editor_rows);
+            return;
+        }
+        let row_char_count = if self.cursor_y < number_of_rows {
+            editor_rows.get_editor_row(self.cursor_y).char_count()
+        } else {
+            0
+        };
+        self.cursor_x = cmp::min(self.cursor_x, row_char_count);
+    }
+
+    /// Moves the cursor by a motion larger than a single arrow-key step.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `motion` - The motion to perform
+    /// * `editor_rows` - The editor's row collection for boundary checking
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::modules::cursor::{CursorController, CursorMotion};
+    /// use ninja::screens::editor::EditorRows;
+    /// 
+    /// let editor_rows = EditorRows::from_bytes("let naïve = f(x);\n".as_bytes(), &None);
+    /// let mut cursor = CursorController::new((80, 24));
+    /// cursor.apply_motion(CursorMotion::WordRight, &editor_rows);
+    /// assert_eq!(cursor.cursor_x, 3);
+    /// cursor.apply_motion(CursorMotion::WordRight, &editor_rows);
+    /// assert_eq!(cursor.cursor_x, 9);
+    /// cursor.apply_motion(CursorMotion::WordLeft, &editor_rows);
+    /// assert_eq!(cursor.cursor_x, 4);
+    /// ```
+    pub fn apply_motion(&mut self, motion: CursorMotion, editor_rows: &EditorRows) {
+        let number_of_rows = editor_rows.number_of_rows();
+        if number_of_rows == 0 {
+            return;
+        }
+        self.reset_preferred_column();
+        self.cursor_y = cmp::min(self.cursor_y, number_of_rows - 1);
+        let (row, column) = match motion {
+            CursorMotion::WordLeft => self.word_left(editor_rows),
+            CursorMotion::WordRight => self.word_right(editor_rows),
+            CursorMotion::ParagraphUp => (Self::paragraph_up(self.cursor_y, editor_rows), 0),
+            CursorMotion::ParagraphDown => (Self::paragraph_down(self.cursor_y, editor_rows), 0),
+            CursorMotion::FileStart => (0, 0),
+            CursorMotion::FileEnd => (
+                number_of_rows - 1,
+                editor_rows.get_editor_row(number_of_rows - 1).char_count(),
+            ),
+            CursorMotion::MatchingBracket => self
+                .bracket_pair(editor_rows)
+                .map_or((self.cursor_y, self.cursor_x), |(_, partner)| partner),
+        };
+        self.cursor_y = row;
+        self.cursor_x = cmp::min(column, editor_rows.get_editor_row(row).char_count());
+    }
+
+    /// Finds the start of the word before the cursor, wrapping to the end
+    /// of the previous line at column 0.
+    fn word_left(&self, editor_rows: &EditorRows) -> (usize, usize) {
+        if self.cursor_x == 0 {
+            return match self.cursor_y.checked_sub(1) {
+                Some(row) => (row, editor_rows.get_editor_row(row).char_count()),
+                None => (0, 0),
+            };
+        }
+        let chars: Vec<char> = editor_rows.get_editor_row(self.cursor_y).row_content.chars().collect();
+        let mut x = cmp::min(self.cursor_x, chars.len());
+        while x > 0 && CharClass::of(chars[x - 1]) == CharClass::Blank {
+            x -= 1;
+        }
+        if x > 0 {
+            let class = CharClass::of(chars[x - 1]);
+            while x > 0 && CharClass::of(chars[x - 1]) == class {
+                x -= 1;
+            }
+        }
+        (self.cursor_y, x)
+    }
+
+    /// Finds the end of the word after the cursor, wrapping to the start
+    /// of the next line at the end of a line.
+    fn word_right(&self, editor_rows: &EditorRows) -> (usize, usize) {
+        let chars: Vec<char> = editor_rows.get_editor_row(self.cursor_y).row_content.chars().collect();
+        if self.cursor_x >= chars.len() {
+            return if self.cursor_y + 1 < editor_rows.number_of_rows() {
+                (self.cursor_y + 1, 0)
+            } else {
+                (self.cursor_y, chars.len())
+            };
+        }
+        let mut x = self.cursor_x;
+        while x < chars.len() && CharClass::of(chars[x]) == CharClass::Blank {
+            x += 1;
+        }
+        if x < chars.len() {
+            let class = CharClass::of(chars[x]);
+            while x < chars.len() && CharClass::of(chars[x]) == class {
+                x += 1;
+            }
+        }
+        (self.cursor_y, x)
+    }
+
+    /// Finds the blank line above the current paragraph, or the first row.
+    fn paragraph_up(row: usize, editor_rows: &EditorRows) -> usize {
+        let is_blank = |at: usize| editor_rows.get_editor_row(at).row_content.trim().is_empty();
+        let mut at = row;
+        while at > 0 && is_blank(at - 1) {
+            at -= 1;
+        }
+        while at > 0 && !is_blank(at - 1) {
+            at -= 1;
+        }
+        at.saturating_sub(1)
+    }
+
+    /// Finds the blank line below the current paragraph, or the last row.
+    fn paragraph_down(row: usize, editor_rows: &EditorRows) -> usize {
+        let last_row = editor_rows.number_of_rows() - 1;
+        let is_blank = |at: usize| editor_rows.get_editor_row(at).row_content.trim().is_empty();
+        let mut at = row;
+        while at < last_row && is_blank(at + 1) {
+            at += 1;
+        }
+        while at < last_row && !is_blank(at + 1) {
+            at += 1;
+        }
+        cmp::min(at + 1, last_row)
+    }
+
+    /// Finds the bracket under or just before the cursor and the bracket matching it.
+    /// 
+    /// Brackets inside strings and comments are skipped, as shown by each
+    /// row's highlighting. The scan gives up once the rows it has read hold
+    /// more than `BRACKET_SCAN_CHARS` characters, so unmatched brackets in
+    /// huge files and very long lines stay cheap.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `editor_rows` - The rows to search
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `Some((bracket, partner))` as `(row, column)` positions, or
+    /// `None` if there is no bracket at the cursor or it has no match.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::modules::cursor::CursorController;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let editor_rows = EditorRows::from_bytes(b"if (a) {\n}\n", &None);
+    /// let mut cursor = CursorController::new((80, 24));
+    /// cursor.cursor_x = 8;
+    /// assert_eq!(cursor.bracket_pair(&editor_rows), Some(((0, 7), (1, 0))));
+    /// 
+    /// // The `)` in the string and the `(` in the comment are not brackets
+    /// let syntax = Output::select_syntax("rs");
+    /// let editor_rows = EditorRows::from_bytes(b"call(\")\", x) // (\n", &syntax);
+    /// cursor.cursor_x = 4;
+    /// assert_eq!(cursor.bracket_pair(&editor_rows), Some(((0, 4), (0, 11))));
+    /// cursor.cursor_x = 16;
+    /// assert_eq!(cursor.bracket_pair(&editor_rows), None);
+    /// ```
+    pub fn bracket_pair(&self, editor_rows: &EditorRows) -> Option<((usize, usize), (usize, usize))> {
+        if self.cursor_y >= editor_rows.number_of_rows() {
+            return None;
+        }
+        let mut budget = BRACKET_SCAN_CHARS;
+        let mut read_row = |row: usize| {
+            let row = editor_rows.get_editor_row(row);
+            budget = budget.checked_sub(row.char_count())?;
+            Some(Self::code_chars(row))
+        };
+        let chars = read_row(self.cursor_y)?;
+        let (x, bracket) = [Some(self.cursor_x), self.cursor_x.checked_sub(1)]
+            .into_iter()
+            .flatten()
+            .filter_map(|x| chars.get(x).map(|&(c, code)| (x, c, code)))
+            .find(|&(_, c, code)| code && BRACKETS.iter().any(|&(open, close)| c == open || c == close))
+            .map(|(x, c, _)| (x, c))?;
+        let (open, close, forward) = BRACKETS.iter().find_map(|&(open, close)| {
+            if bracket == open {
+                Some((open, close, true))
+            } else if bracket == close {
+                Some((open, close, false))
+            } else {
+                None
+            }
+        })?;
+        let mut depth = 0usize;
+        let mut row = self.cursor_y;
+        let mut row_chars = chars;
+        let mut column = x;
+        loop {
+            let (c, code) = row_chars[column];
+            if code && (c == open || c == close) {
+                if (c == open) == forward {
+                    depth += 1;
+                } else {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(((self.cursor_y, x), (row, column)));
                     }
                 }
             }
-            KeyCode::End => {
-                if self.cursor_y < number_of_rows {
-                    self.cursor_x = editor_rows.get_editor_row(self.cursor_y).char_count();
+            // Step to the next character in the scan direction, crossing rows as needed
+            loop {
+                if forward {
+                    if column + 1 < row_chars.len() {
+                        column += 1;
+                        break;
+                    }
+                    row += 1;
+                    if row >= editor_rows.number_of_rows() {
+                        return None;
+                    }
+                    row_chars = read_row(row)?;
+                    if !row_chars.is_empty() {
+                        column = 0;
+                        break;
+                    }
+                } else {
+                    if column > 0 {
+                        column -= 1;
+                        break;
+                    }
+                    row = row.checked_sub(1)?;
+                    row_chars = read_row(row)?;
+                    if !row_chars.is_empty() {
+                        column = row_chars.len() - 1;
+                        break;
+                    }
                 }
             }
-            KeyCode::Home => self.cursor_x = 0,
-            _ => unimplemented!(),
         }
+    }
+
+    /// Returns each character of a row with whether it is code, rather than
+    /// part of a string or comment.
+    fn code_chars(row: &Row) -> Vec<(char, bool)> {
+        row.row_content
+            .chars()
+            .zip(row.char_highlights())
+            .map(|(c, highlight)| {
+                let code = !matches!(
+                    highlight,
+                    HighlightType::String
+                        | HighlightType::CharLiteral
+                        | HighlightType::Comment
+                        | HighlightType::MultilineComment
+                );
+                (c, code)
+            })
+            .collect()
+    }
+
+    /// Moves the cursor to a position and centers it vertically on screen.
+    /// 
+    /// The position is clamped to the buffer, so out-of-range rows and
+    /// columns land on the last row and the end of the line.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `row` - Zero-based target row
+    /// * `column` - Zero-based target character column
+    /// * `editor_rows` - The editor's row collection for boundary checking
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::modules::cursor::CursorController;
+    /// use ninja::screens::editor::EditorRows;
+    /// 
+    /// let text = "line\n".repeat(100);
+    /// let editor_rows = EditorRows::from_bytes(text.as_bytes(), &None);
+    /// let mut cursor = CursorController::new((80, 20));
+    /// cursor.jump_to(49, 2, &editor_rows);
+    /// assert_eq!((cursor.cursor_y, cursor.cursor_x), (49, 2));
+    /// assert_eq!(cursor.row_offset, 39);
+    /// ```
+    pub fn jump_to(&mut self, row: usize, column: usize, editor_rows: &EditorRows) {
+        let number_of_rows = editor_rows.number_of_rows();
+        self.reset_preferred_column();
+        self.cursor_y = cmp::min(row, number_of_rows.saturating_sub(1));
         let row_char_count = if self.cursor_y < number_of_rows {
             editor_rows.get_editor_row(self.cursor_y).char_count()
         } else {
             0
         };
-        self.cursor_x = cmp::min(self.cursor_x, row_char_count);
+        self.cursor_x = cmp::min(column, row_char_count);
+        self.row_offset = self.cursor_y.saturating_sub(self.screen_rows / 2);
+    }
+}
+
+/// Bracket pairs recognised by the matching-bracket motion.
+const BRACKETS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+
+/// Characters read, starting with the cursor's row, when looking for a matching bracket.
+const BRACKET_SCAN_CHARS: usize = 100_000;
+
+/// Character classes used to find word boundaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    /// Whitespace
+    Blank,
+    /// Letters, digits and underscores in any script
+    Word,
+    /// Everything else
+    Punctuation,
+}
+
+impl CharClass {
+    /// Classifies a character.
+    fn of(c: char) -> CharClass {
+        if c.is_whitespace() {
+            CharClass::Blank
+        } else if c.is_alphanumeric() || c == '_' {
+            CharClass::Word
+        } else {
+            CharClass::Punctuation
+        }
     }
-}
\ No newline at end of file
+}
+
+/// A cursor movement larger than a single arrow-key step.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use ninja::modules::cursor::{CursorController, CursorMotion};
+/// use ninja::screens::editor::EditorRows;
+/// 
+/// let editor_rows = EditorRows::from_bytes(b"fn main() {\n    call();\n}\n", &None);
+/// let mut cursor = CursorController::new((80, 24));
+/// cursor.cursor_x = 10;
+/// cursor.apply_motion(CursorMotion::MatchingBracket, &editor_rows);
+/// assert_eq!((cursor.cursor_y, cursor.cursor_x), (2, 0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorMotion {
+    /// To the start of the previous word
+    WordLeft,
+    /// To the end of the next word
+    WordRight,
+    /// To the blank line above the current paragraph
+    ParagraphUp,
+    /// To the blank line below the current paragraph
+    ParagraphDown,
+    /// To the start of the file
+    FileStart,
+    /// To the end of the file
+    FileEnd,
+    /// To the bracket matching the one at the cursor
+    MatchingBracket,
+}
+
+/// A position typed into the go-to prompt.
+/// 
+/// # Accepted Forms
+/// 
+/// - `42` - Line 42
+/// - `42:7` - Line 42, column 7
+/// - `+10` / `-10` - Ten lines below / above the cursor
+/// - `50%` - Halfway through the file
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use ninja::modules::cursor::GotoTarget;
+/// 
+/// assert_eq!(GotoTarget::parse("42:7"), Some(GotoTarget::Line(42, Some(7))));
+/// assert_eq!(GotoTarget::parse("-3"), Some(GotoTarget::Relative(-3)));
+/// assert_eq!(GotoTarget::parse("50%"), Some(GotoTarget::Percent(50)));
+/// assert_eq!(GotoTarget::parse("abc"), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GotoTarget {
+    /// A line (1-based) and optional column (1-based)
+    Line(usize, Option<usize>),
+    /// A number of lines relative to the cursor
+    Relative(isize),
+    /// A percentage of the way through the file
+    Percent(usize),
+}
+
+impl GotoTarget {
+    /// Parses the text typed into the go-to prompt.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `input` - The text to parse
+    /// 
+    /// # Returns
+    /// 
+    /// Returns the parsed target, or `None` if the text is not a valid position.
+    pub fn parse(input: &str) -> Option<GotoTarget> {
+        let input = input.trim();
+        let number = |text: &str| text.trim().parse::<usize>().ok().filter(|&n| n > 0);
+        if let Some(percent) = input.strip_suffix('%') {
+            return percent
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .map(|percent| GotoTarget::Percent(percent.min(100)));
+        }
+        if input.starts_with('+') || input.starts_with('-') {
+            return input.parse::<isize>().ok().map(GotoTarget::Relative);
+        }
+        match input.split_once(':') {
+            Some((line, column)) => Some(GotoTarget::Line(number(line)?, Some(number(column)?))),
+            None => Some(GotoTarget::Line(number(input)?, None)),
+        }
+    }
+
+    /// Resolves the target to a zero-based row and optional column.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `current_row` - The cursor's current row (0-based)
+    /// * `number_of_rows` - The number of rows in the buffer
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `(row, column)`, both zero-based and clamped to the buffer's rows.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::modules::cursor::GotoTarget;
+    /// 
+    /// assert_eq!(GotoTarget::Line(10, Some(3)).resolve(0, 100), (9, Some(2)));
+    /// assert_eq!(GotoTarget::Relative(-20).resolve(5, 100), (0, None));
+    /// assert_eq!(GotoTarget::Percent(100).resolve(0, 100), (99, None));
+    /// ```
+    pub fn resolve(self, current_row: usize, number_of_rows: usize) -> (usize, Option<usize>) {
+        let last_row = number_of_rows.saturating_sub(1);
+        let (row, column) = match self {
+            GotoTarget::Line(line, column) => (line - 1, column.map(|column| column - 1)),
+            GotoTarget::Relative(offset) => (current_row.saturating_add_signed(offset), None),
+            GotoTarget::Percent(percent) => (last_row * percent / 100, None),
+        };
+        (cmp::min(row, last_row), column)
+    }
+}
@@ -0,0 +1,183 @@
+//! # Diff Module
+//!
+//! This module provides a small line-based diff used to compare two versions
+//! of a buffer, for example a recovered swap file against the file on disk.
+//!
+//! ## Features
+//!
+//! - **Line Diff**: Longest-common-subsequence diff over lines
+//! - **Prefix/Suffix Trimming**: Unchanged leading and trailing lines are skipped cheaply
+//! - **Unified Output**: Renders hunks with `+`/`-` markers and surrounding context
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use ninja::modules::diff::unified_diff;
+//!
+//! let lines = unified_diff("a\nb\nc", "a\nB\nc", 1);
+//! assert_eq!(lines, vec!["@@ line 1 @@", " a", "-b", "+B", " c"]);
+//! ```
+
+/// Maximum size of the comparison table before falling back to a coarse diff.
+const MAX_TABLE_CELLS: usize = 4_000_000;
+
+/// A single line of a diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    /// A line present in both versions
+    Same(String),
+    /// A line only present in the old version
+    Removed(String),
+    /// A line only present in the new version
+    Added(String),
+}
+
+/// Computes a line diff between two texts.
+///
+/// Common leading and trailing lines are matched directly; the remaining
+/// middle section is compared with a longest-common-subsequence table. If
+/// that table would be too large, the middle section is reported as fully
+/// removed and re-added.
+///
+/// # Arguments
+///
+/// * `old` - The original text
+/// * `new` - The changed text
+///
+/// # Returns
+///
+/// Returns the diff as a sequence of `DiffLine`s in file order.
+///
+/// # Example
+///
+/// ```rust
+/// use ninja::modules::diff::{diff_lines, DiffLine};
+///
+/// let diff = diff_lines("a\nb", "a\nc");
+/// assert_eq!(diff[1], DiffLine::Removed("b".to_string()));
+/// assert_eq!(diff[2], DiffLine::Added("c".to_string()));
+/// ```
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut result: Vec<DiffLine> = old[..prefix]
+        .iter()
+        .map(|line| DiffLine::Same(line.to_string()))
+        .collect();
+
+    if (old_mid.len() + 1) * (new_mid.len() + 1) > MAX_TABLE_CELLS {
+        result.extend(old_mid.iter().map(|line| DiffLine::Removed(line.to_string())));
+        result.extend(new_mid.iter().map(|line| DiffLine::Added(line.to_string())));
+    } else {
+        // lcs[i][j] = length of the LCS of old_mid[i..] and new_mid[j..]
+        let width = new_mid.len() + 1;
+        let mut lcs = vec![0usize; (old_mid.len() + 1) * width];
+        for i in (0..old_mid.len()).rev() {
+            for j in (0..new_mid.len()).rev() {
+                lcs[i * width + j] = if old_mid[i] == new_mid[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < old_mid.len() && j < new_mid.len() {
+            if old_mid[i] == new_mid[j] {
+                result.push(DiffLine::Same(old_mid[i].to_string()));
+                i += 1;
+                j += 1;
+            } else if lcs[(i + 1) * width + j] >= lcs[i * width + j + 1] {
+                result.push(DiffLine::Removed(old_mid[i].to_string()));
+                i += 1;
+            } else {
+                result.push(DiffLine::Added(new_mid[j].to_string()));
+                j += 1;
+            }
+        }
+        result.extend(old_mid[i..].iter().map(|line| DiffLine::Removed(line.to_string())));
+        result.extend(new_mid[j..].iter().map(|line| DiffLine::Added(line.to_string())));
+    }
+
+    result.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|line| DiffLine::Same(line.to_string())),
+    );
+    result
+}
+
+/// Renders a diff between two texts in a compact unified format.
+///
+/// Each hunk starts with an `@@ line N @@` header giving the 1-based line
+/// number in the old text, followed by the changed lines prefixed with `-`
+/// or `+` and up to `context` unchanged lines (prefixed with a space) on
+/// either side.
+///
+/// # Arguments
+///
+/// * `old` - The original text
+/// * `new` - The changed text
+/// * `context` - Number of unchanged lines to show around each change
+///
+/// # Returns
+///
+/// Returns the rendered diff lines, or an empty vector if the texts are identical.
+///
+/// # Example
+///
+/// ```rust
+/// use ninja::modules::diff::unified_diff;
+///
+/// assert!(unified_diff("same", "same", 3).is_empty());
+/// ```
+pub fn unified_diff(old: &str, new: &str, context: usize) -> Vec<String> {
+    let diff = diff_lines(old, new);
+    let changed: Vec<usize> = diff
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Same(_)))
+        .map(|(i, _)| i)
+        .collect();
+
+    let mut output = Vec::new();
+    let mut index = 0;
+    while index < changed.len() {
+        let start = changed[index].saturating_sub(context);
+        let mut end = changed[index];
+        // Merge changes whose context windows overlap into one hunk
+        while index < changed.len() && changed[index] <= end + 2 * context + 1 {
+            end = changed[index];
+            index += 1;
+        }
+        let end = (end + context + 1).min(diff.len());
+
+        let old_line = diff[..start]
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Added(_)))
+            .count()
+            + 1;
+        output.push(format!("@@ line {} @@", old_line));
+        output.extend(diff[start..end].iter().map(|line| match line {
+            DiffLine::Same(text) => format!(" {}", text),
+            DiffLine::Removed(text) => format!("-{}", text),
+            DiffLine::Added(text) => format!("+{}", text),
+        }));
+    }
+    output
+}
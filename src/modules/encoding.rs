@@ -0,0 +1,320 @@
+//! # Encoding Module
+//!
+//! This module detects, decodes and encodes the character encoding of files
+//! so that files which are not UTF-8 survive being opened and saved.
+//!
+//! ## Features
+//!
+//! - **Detection**: Byte order marks, UTF-8 validity and UTF-16 heuristics
+//! - **Legacy Encodings**: Latin-1, Windows code pages, Shift_JIS, GBK, Big5 and more
+//! - **Round-Tripping**: Files are saved back in the encoding they were read in
+//! - **Strict Encoding**: Characters that the target encoding cannot represent are reported
+//!
+//! ## Detection Order
+//!
+//! 1. A byte order mark selects UTF-8, UTF-16LE or UTF-16BE
+//! 2. Text with many NUL bytes in alternating positions is read as UTF-16
+//! 3. Valid UTF-8 is read as UTF-8
+//! 4. Anything else falls back to Windows-1252, which accepts every byte
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use ninja::modules::encoding::FileEncoding;
+//!
+//! let (encoding, bom) = FileEncoding::detect(b"caf\xE9");
+//! assert_eq!(encoding, FileEncoding::WINDOWS_1252);
+//! assert!(!bom);
+//! let (text, _) = encoding.decode(b"caf\xE9");
+//! assert_eq!(text, "café");
+//! assert_eq!(encoding.encode(&text).unwrap(), b"caf\xE9");
+//! ```
+
+use encoding_rs::Encoding;
+
+/// A character encoding supported for reading and writing files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FileEncoding {
+    /// UTF-8
+    #[default]
+    Utf8,
+    /// UTF-16, little endian
+    Utf16Le,
+    /// UTF-16, big endian
+    Utf16Be,
+    /// ISO-8859-1, mapping every byte to the code point of the same value
+    Latin1,
+    /// Any other encoding provided by `encoding_rs`
+    Legacy(&'static Encoding),
+}
+
+/// Error returned when text cannot be represented in the target encoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnmappableChar {
+    /// The character that could not be encoded
+    pub ch: char,
+    /// Zero-based line of the character
+    pub line: usize,
+    /// Zero-based character column of the character
+    pub column: usize,
+}
+
+impl std::fmt::Display for UnmappableChar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "character {:?} (U+{:04X}) at {}:{} cannot be encoded",
+            self.ch,
+            self.ch as u32,
+            self.line + 1,
+            self.column + 1
+        )
+    }
+}
+
+impl std::error::Error for UnmappableChar {}
+
+impl FileEncoding {
+    /// Windows-1252, the fallback for text that is neither UTF-8 nor UTF-16.
+    pub const WINDOWS_1252: FileEncoding = FileEncoding::Legacy(encoding_rs::WINDOWS_1252);
+
+    /// Encodings offered when choosing an encoding explicitly.
+    pub const ALL: [FileEncoding; 15] = [
+        FileEncoding::Utf8,
+        FileEncoding::Utf16Le,
+        FileEncoding::Utf16Be,
+        FileEncoding::Latin1,
+        FileEncoding::WINDOWS_1252,
+        FileEncoding::Legacy(encoding_rs::ISO_8859_15),
+        FileEncoding::Legacy(encoding_rs::WINDOWS_1250),
+        FileEncoding::Legacy(encoding_rs::WINDOWS_1251),
+        FileEncoding::Legacy(encoding_rs::KOI8_R),
+        FileEncoding::Legacy(encoding_rs::SHIFT_JIS),
+        FileEncoding::Legacy(encoding_rs::EUC_JP),
+        FileEncoding::Legacy(encoding_rs::EUC_KR),
+        FileEncoding::Legacy(encoding_rs::GBK),
+        FileEncoding::Legacy(encoding_rs::GB18030),
+        FileEncoding::Legacy(encoding_rs::BIG5),
+    ];
+
+    /// Returns the display name of the encoding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ninja::modules::encoding::FileEncoding;
+    ///
+    /// assert_eq!(FileEncoding::Utf16Le.name(), "UTF-16LE");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            FileEncoding::Utf8 => "UTF-8",
+            FileEncoding::Utf16Le => "UTF-16LE",
+            FileEncoding::Utf16Be => "UTF-16BE",
+            FileEncoding::Latin1 => "ISO-8859-1",
+            FileEncoding::Legacy(encoding) => encoding.name(),
+        }
+    }
+
+    /// Looks up an encoding by name or alias (case-insensitive).
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - An encoding name such as `"latin1"`, `"cp1252"` or `"sjis"`
+    ///
+    /// # Returns
+    ///
+    /// Returns the matching encoding, or `None` if the label is unknown.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ninja::modules::encoding::FileEncoding;
+    ///
+    /// assert_eq!(FileEncoding::from_label("latin1"), Some(FileEncoding::Latin1));
+    /// assert_eq!(FileEncoding::from_label("utf-16"), Some(FileEncoding::Utf16Le));
+    /// ```
+    pub fn from_label(label: &str) -> Option<FileEncoding> {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "latin1" | "latin-1" | "iso-8859-1" | "iso8859-1" | "l1" => Some(FileEncoding::Latin1),
+            "utf-16be" | "utf16be" => Some(FileEncoding::Utf16Be),
+            label => Encoding::for_label(label.as_bytes()).map(|encoding| {
+                if encoding == encoding_rs::UTF_8 {
+                    FileEncoding::Utf8
+                } else if encoding == encoding_rs::UTF_16LE {
+                    FileEncoding::Utf16Le
+                } else if encoding == encoding_rs::UTF_16BE {
+                    FileEncoding::Utf16Be
+                } else {
+                    FileEncoding::Legacy(encoding)
+                }
+            }),
+        }
+    }
+
+    /// Returns the byte order mark of the encoding (empty if it has none).
+    pub fn bom(&self) -> &'static [u8] {
+        match self {
+            FileEncoding::Utf8 => &[0xEF, 0xBB, 0xBF],
+            FileEncoding::Utf16Le => &[0xFF, 0xFE],
+            FileEncoding::Utf16Be => &[0xFE, 0xFF],
+            _ => &[],
+        }
+    }
+
+    /// Returns `true` for the Unicode encodings (UTF-8 and UTF-16).
+    pub fn is_unicode(&self) -> bool {
+        !self.bom().is_empty()
+    }
+
+    /// Detects the encoding of raw file contents.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The raw file contents
+    ///
+    /// # Returns
+    ///
+    /// Returns the detected encoding and whether the contents start with its
+    /// byte order mark.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ninja::modules::encoding::FileEncoding;
+    ///
+    /// assert_eq!(FileEncoding::detect(b"\xFF\xFEh\0i\0"), (FileEncoding::Utf16Le, true));
+    /// assert_eq!(FileEncoding::detect(b"h\0i\0"), (FileEncoding::Utf16Le, false));
+    /// assert_eq!(FileEncoding::detect("héllo".as_bytes()), (FileEncoding::Utf8, false));
+    /// ```
+    pub fn detect(bytes: &[u8]) -> (FileEncoding, bool) {
+        for encoding in [FileEncoding::Utf8, FileEncoding::Utf16Le, FileEncoding::Utf16Be] {
+            if bytes.starts_with(encoding.bom()) {
+                return (encoding, true);
+            }
+        }
+        if bytes.len() >= 2 && bytes.len().is_multiple_of(2) {
+            let pairs = bytes.len() / 2;
+            let (even_nuls, odd_nuls) = bytes.chunks_exact(2).fold((0, 0), |(even, odd), pair| {
+                (even + (pair[0] == 0) as usize, odd + (pair[1] == 0) as usize)
+            });
+            // ASCII-heavy UTF-16 has a NUL in every other byte
+            if odd_nuls * 10 >= pairs * 3 && even_nuls * 10 < pairs {
+                return (FileEncoding::Utf16Le, false);
+            }
+            if even_nuls * 10 >= pairs * 3 && odd_nuls * 10 < pairs {
+                return (FileEncoding::Utf16Be, false);
+            }
+        }
+        if std::str::from_utf8(bytes).is_ok() {
+            return (FileEncoding::Utf8, false);
+        }
+        (FileEncoding::WINDOWS_1252, false)
+    }
+
+    /// Decodes raw bytes (without byte order mark) into text.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to decode
+    ///
+    /// # Returns
+    ///
+    /// Returns the decoded text and `true` if some bytes were invalid and
+    /// had to be replaced with U+FFFD.
+    pub fn decode(&self, bytes: &[u8]) -> (String, bool) {
+        match self {
+            FileEncoding::Utf8 => match String::from_utf8(bytes.to_vec()) {
+                Ok(text) => (text, false),
+                Err(e) => (String::from_utf8_lossy(e.as_bytes()).into_owned(), true),
+            },
+            FileEncoding::Utf16Le | FileEncoding::Utf16Be => {
+                let units = bytes.chunks(2).map(|pair| match (self, pair) {
+                    (FileEncoding::Utf16Le, [lo, hi]) => u16::from_le_bytes([*lo, *hi]),
+                    (_, [hi, lo]) => u16::from_be_bytes([*hi, *lo]),
+                    // A dangling odd byte cannot be a valid code unit
+                    _ => 0xDC00,
+                });
+                let mut had_errors = false;
+                let text = char::decode_utf16(units)
+                    .map(|ch| {
+                        ch.unwrap_or_else(|_| {
+                            had_errors = true;
+                            char::REPLACEMENT_CHARACTER
+                        })
+                    })
+                    .collect();
+                (text, had_errors)
+            }
+            FileEncoding::Latin1 => (bytes.iter().map(|&b| b as char).collect(), false),
+            FileEncoding::Legacy(encoding) => {
+                let (text, had_errors) = encoding.decode_without_bom_handling(bytes);
+                (text.into_owned(), had_errors)
+            }
+        }
+    }
+
+    /// Encodes text (without byte order mark) into raw bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to encode
+    ///
+    /// # Returns
+    ///
+    /// Returns the encoded bytes, or the first character that cannot be
+    /// represented in this encoding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ninja::modules::encoding::FileEncoding;
+    ///
+    /// assert_eq!(FileEncoding::Latin1.encode("é").unwrap(), vec![0xE9]);
+    /// assert!(FileEncoding::Latin1.encode("€").is_err());
+    /// ```
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>, UnmappableChar> {
+        match self {
+            FileEncoding::Utf8 => Ok(text.as_bytes().to_vec()),
+            FileEncoding::Utf16Le => Ok(text.encode_utf16().flat_map(u16::to_le_bytes).collect()),
+            FileEncoding::Utf16Be => Ok(text.encode_utf16().flat_map(u16::to_be_bytes).collect()),
+            FileEncoding::Latin1 => text
+                .chars()
+                .map(|ch| u8::try_from(ch as u32).ok())
+                .collect::<Option<Vec<u8>>>()
+                .ok_or_else(|| Self::first_unmappable(text, |ch| (ch as u32) < 0x100)),
+            FileEncoding::Legacy(encoding) => {
+                let (bytes, _, had_errors) = encoding.encode(text);
+                if had_errors {
+                    let mut buffer = [0u8; 4];
+                    Err(Self::first_unmappable(text, |ch| {
+                        !encoding.encode(ch.encode_utf8(&mut buffer)).2
+                    }))
+                } else {
+                    Ok(bytes.into_owned())
+                }
+            }
+        }
+    }
+
+    /// Finds the first character of `text` rejected by `encodable`.
+    fn first_unmappable(text: &str, mut encodable: impl FnMut(char) -> bool) -> UnmappableChar {
+        let (mut line, mut column) = (0, 0);
+        for ch in text.chars() {
+            if !encodable(ch) {
+                return UnmappableChar { ch, line, column };
+            }
+            if ch == '\n' {
+                line += 1;
+                column = 0;
+            } else {
+                column += 1;
+            }
+        }
+        UnmappableChar {
+            ch: char::REPLACEMENT_CHARACTER,
+            line,
+            column,
+        }
+    }
+}
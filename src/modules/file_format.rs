@@ -0,0 +1,345 @@
+//! # File Format Module
+//!
+//! This module detects and preserves the on-disk format of text files:
+//! their character encoding, line endings, whether they end with a newline,
+//! and whether they start with a byte order mark. Opening and saving a file
+//! without editing it therefore reproduces the original bytes.
+//!
+//! ## Features
+//!
+//! - **Line Ending Detection**: LF, CRLF and CR, including files that mix them
+//! - **Final Newline**: Remembers whether the last line was terminated
+//! - **Byte Order Mark**: Remembers and restores a leading BOM
+//! - **Encoding**: Decodes and re-encodes files that are not UTF-8
+//! - **Conversion**: Files can be switched to a different line ending style
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use ninja::modules::file_format::{FileFormat, LineEnding};
+//!
+//! let (format, lines) = FileFormat::detect("one\r\ntwo\r\n");
+//! assert_eq!(format.line_ending, LineEnding::CrLf);
+//! assert!(format.final_newline);
+//! assert_eq!(format.join(lines.iter().map(|line| line.as_str())), "one\r\ntwo\r\n");
+//!
+//! let (format, lines) = FileFormat::from_bytes(b"caf\xE9\n", None);
+//! assert_eq!(lines, vec!["café"]);
+//! assert_eq!(format.to_bytes(lines.iter().map(|line| line.as_str())).unwrap(), b"caf\xE9\n");
+//! ```
+
+use crate::modules::encoding::{FileEncoding, UnmappableChar};
+
+/// The UTF-8 byte order mark.
+pub const UTF8_BOM: char = '\u{FEFF}';
+
+/// A line terminator style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineEnding {
+    /// Unix style `\n`
+    #[default]
+    Lf,
+    /// Windows style `\r\n`
+    CrLf,
+    /// Classic Mac OS style `\r`
+    Cr,
+}
+
+impl LineEnding {
+    /// All line ending styles, in display order.
+    pub const ALL: [LineEnding; 3] = [LineEnding::Lf, LineEnding::CrLf, LineEnding::Cr];
+
+    /// Returns the terminator characters for this style.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ninja::modules::file_format::LineEnding;
+    ///
+    /// assert_eq!(LineEnding::CrLf.as_str(), "\r\n");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+            LineEnding::Cr => "\r",
+        }
+    }
+
+    /// Returns the short name shown in the status bar.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ninja::modules::file_format::LineEnding;
+    ///
+    /// assert_eq!(LineEnding::CrLf.name(), "CRLF");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "LF",
+            LineEnding::CrLf => "CRLF",
+            LineEnding::Cr => "CR",
+        }
+    }
+
+    /// Returns a longer description naming the platform the style comes from.
+    pub fn description(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "LF (Unix, macOS)",
+            LineEnding::CrLf => "CRLF (Windows)",
+            LineEnding::Cr => "CR (Classic Mac OS)",
+        }
+    }
+}
+
+/// The on-disk format of a text file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FileFormat {
+    /// Line ending used when saving
+    pub line_ending: LineEnding,
+    /// Whether the file used more than one line ending style when loaded
+    pub mixed_line_endings: bool,
+    /// Whether the last line is followed by a line ending
+    pub final_newline: bool,
+    /// Whether the file starts with the byte order mark of its encoding
+    pub bom: bool,
+    /// Character encoding used when saving
+    pub encoding: FileEncoding,
+    /// Whether some bytes could not be decoded when loaded
+    pub decode_errors: bool,
+}
+
+impl FileFormat {
+    /// Detects the format of a text and splits it into lines.
+    ///
+    /// The most common line ending becomes the file's line ending (LF wins
+    /// ties); files containing more than one style are flagged as mixed.
+    /// A leading byte order mark is removed from the first line.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The complete file contents
+    ///
+    /// # Returns
+    ///
+    /// Returns the detected `FileFormat` and the lines without terminators.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ninja::modules::file_format::{FileFormat, LineEnding};
+    ///
+    /// let (format, lines) = FileFormat::detect("a\r\nb\nc\r\n");
+    /// assert_eq!(format.line_ending, LineEnding::CrLf);
+    /// assert!(format.mixed_line_endings);
+    /// assert_eq!(lines, vec!["a", "b", "c"]);
+    /// ```
+    pub fn detect(text: &str) -> (FileFormat, Vec<String>) {
+        let (bom, text) = match text.strip_prefix(UTF8_BOM) {
+            Some(rest) => (true, rest),
+            None => (false, text),
+        };
+
+        let (mut lf, mut crlf, mut cr) = (0usize, 0usize, 0usize);
+        let bytes = text.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\r' if bytes.get(i + 1) == Some(&b'\n') => {
+                    crlf += 1;
+                    i += 1;
+                }
+                b'\r' => cr += 1,
+                b'\n' => lf += 1,
+                _ => {}
+            }
+            i += 1;
+        }
+
+        let line_ending = if crlf > lf && crlf >= cr {
+            LineEnding::CrLf
+        } else if cr > lf && cr > crlf {
+            LineEnding::Cr
+        } else {
+            LineEnding::Lf
+        };
+        let styles_used = [lf, crlf, cr].iter().filter(|&&count| count > 0).count();
+
+        let format = FileFormat {
+            line_ending,
+            mixed_line_endings: styles_used > 1,
+            final_newline: text.ends_with(['\n', '\r']),
+            bom,
+            ..Default::default()
+        };
+        (format, Self::split_lines(text))
+    }
+
+    /// Decodes raw file contents and detects their format.
+    ///
+    /// The encoding is detected unless one is given explicitly. A byte
+    /// order mark matching the encoding is removed and remembered.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The raw file contents
+    /// * `encoding` - The encoding to use, or `None` to detect it
+    ///
+    /// # Returns
+    ///
+    /// Returns the detected `FileFormat` and the decoded lines. Bytes that
+    /// were invalid in the encoding are replaced with U+FFFD and flagged
+    /// through `decode_errors`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ninja::modules::encoding::FileEncoding;
+    /// use ninja::modules::file_format::FileFormat;
+    ///
+    /// let (format, lines) = FileFormat::from_bytes(b"\xFF\xFEh\0i\0", None);
+    /// assert_eq!(format.encoding, FileEncoding::Utf16Le);
+    /// assert!(format.bom);
+    /// assert_eq!(lines, vec!["hi"]);
+    /// ```
+    pub fn from_bytes(bytes: &[u8], encoding: Option<FileEncoding>) -> (FileFormat, Vec<String>) {
+        let (encoding, bom) = match encoding {
+            Some(encoding) => (encoding, !encoding.bom().is_empty() && bytes.starts_with(encoding.bom())),
+            None => FileEncoding::detect(bytes),
+        };
+        let body = if bom { &bytes[encoding.bom().len()..] } else { bytes };
+        let (text, decode_errors) = encoding.decode(body);
+        let (mut format, lines) = Self::detect(&text);
+        format.bom = bom;
+        format.encoding = encoding;
+        format.decode_errors = decode_errors;
+        (format, lines)
+    }
+
+    /// Joins lines and encodes them as file contents using this format.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - The lines to write
+    ///
+    /// # Returns
+    ///
+    /// Returns the bytes to write (including any byte order mark), or the
+    /// first character that the encoding cannot represent.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ninja::modules::encoding::FileEncoding;
+    /// use ninja::modules::file_format::FileFormat;
+    ///
+    /// let format = FileFormat { encoding: FileEncoding::Latin1, ..Default::default() };
+    /// assert_eq!(format.to_bytes(["é"].into_iter()).unwrap(), vec![0xE9]);
+    /// assert!(format.to_bytes(["€"].into_iter()).is_err());
+    /// ```
+    pub fn to_bytes<'a>(&self, lines: impl Iterator<Item = &'a str>) -> Result<Vec<u8>, UnmappableChar> {
+        let mut bytes = Vec::new();
+        if self.bom {
+            bytes.extend_from_slice(self.encoding.bom());
+        }
+        bytes.extend(self.encoding.encode(&self.join(lines))?);
+        Ok(bytes)
+    }
+
+    /// Splits a text into lines, accepting any line ending style.
+    ///
+    /// A trailing line ending does not produce an extra empty line, and a
+    /// leading byte order mark is removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to split
+    ///
+    /// # Returns
+    ///
+    /// Returns the lines without their terminators.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ninja::modules::file_format::FileFormat;
+    ///
+    /// assert_eq!(FileFormat::split_lines("a\rb\r\nc\n"), vec!["a", "b", "c"]);
+    /// ```
+    pub fn split_lines(text: &str) -> Vec<String> {
+        let text = text.strip_prefix(UTF8_BOM).unwrap_or(text);
+        let mut lines = Vec::new();
+        let mut current = String::new();
+        let mut chars = text.chars().peekable();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\r' => {
+                    if chars.peek() == Some(&'\n') {
+                        chars.next();
+                    }
+                    lines.push(std::mem::take(&mut current));
+                }
+                '\n' => lines.push(std::mem::take(&mut current)),
+                _ => current.push(ch),
+            }
+        }
+        if !current.is_empty() {
+            lines.push(current);
+        }
+        lines
+    }
+
+    /// Joins lines back into file contents using this format.
+    ///
+    /// The result is neither encoded nor prefixed with a byte order mark;
+    /// use `to_bytes` for the bytes written to disk.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - The lines to join
+    ///
+    /// # Returns
+    ///
+    /// Returns the joined text, with a final line ending if the format has one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ninja::modules::file_format::{FileFormat, LineEnding};
+    ///
+    /// let format = FileFormat { line_ending: LineEnding::CrLf, final_newline: true, ..Default::default() };
+    /// assert_eq!(format.join(["a", "b"].into_iter()), "a\r\nb\r\n");
+    /// ```
+    pub fn join<'a>(&self, lines: impl Iterator<Item = &'a str>) -> String {
+        let lines: Vec<&str> = lines.collect();
+        let mut text = lines.join(self.line_ending.as_str());
+        if self.final_newline && !lines.is_empty() {
+            text.push_str(self.line_ending.as_str());
+        }
+        text
+    }
+
+    /// Returns the short format description shown in the status bar.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ninja::modules::file_format::FileFormat;
+    ///
+    /// let format = FileFormat { bom: true, ..Default::default() };
+    /// assert_eq!(format.label(), "UTF-8 BOM LF");
+    /// ```
+    pub fn label(&self) -> String {
+        let mut label = self.encoding.name().to_string();
+        if self.bom {
+            label.push_str(" BOM");
+        }
+        label.push(' ');
+        label.push_str(self.line_ending.name());
+        if self.mixed_line_endings {
+            label.push_str(" (mixed)");
+        }
+        label
+    }
+}
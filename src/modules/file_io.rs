@@ -0,0 +1,441 @@
+//! # File I/O Module
+//!
+//! This module provides safe file writing for the Ninja editor. Saves are
+//! performed atomically so that a crash or a full disk in the middle of a
+//! write never leaves the user's file truncated.
+//!
+//! ## Features
+//!
+//! - **Atomic Writes**: Content goes to a temporary file that is fsynced and renamed over the original
+//! - **Permission Preservation**: The original file's mode and owner are kept
+//! - **Symlink Handling**: Symlinks are resolved so that the link target is updated, not the link
+//! - **Hard Link Fallback**: Hard-linked files are written in place to keep all links intact
+//! - **Read-Only Detection**: Callers can check whether a save would override a read-only file
+//! - **Change Detection**: Snapshots of a file's state reveal changes made by other programs
+//! - **Elevated Writes**: Files the user cannot write are saved through a helper such as `sudo tee`
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use ninja::modules::file_io;
+//! use std::path::Path;
+//!
+//! let path = std::env::temp_dir().join("ninja-file-io-example.txt");
+//! file_io::write_atomic(&path, b"Hello, world!").unwrap();
+//! assert_eq!(std::fs::read(&path).unwrap(), b"Hello, world!");
+//! ```
+
+use std::collections::hash_map::DefaultHasher;
+use std::fs::{self, File, Metadata, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::io::{self, Write};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::SystemTime;
+
+/// A snapshot of a file on disk, used to notice changes made by other programs.
+///
+/// The modification time and size are compared first, since they are cheap
+/// to query; the contents are only re-read and hashed when those differ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskState {
+    /// Last modification time of the file
+    pub modified: Option<SystemTime>,
+    /// Size of the file in bytes
+    pub len: u64,
+    /// Hash of the file contents
+    pub hash: u64,
+}
+
+/// The result of comparing a file with a previously recorded `DiskState`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiskChange {
+    /// The file has not been touched
+    Unchanged,
+    /// The file was touched but its contents are the same
+    Touched(DiskState),
+    /// The file contents have changed
+    Modified(DiskState),
+    /// The file no longer exists
+    Deleted,
+}
+
+impl DiskState {
+    /// Records the state of a file from its contents and metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `contents` - The bytes read from or written to the file
+    /// * `metadata` - The file's metadata
+    ///
+    /// # Returns
+    ///
+    /// Returns the recorded `DiskState`.
+    pub fn new(contents: &[u8], metadata: &Metadata) -> Self {
+        let mut hasher = DefaultHasher::new();
+        contents.hash(&mut hasher);
+        Self {
+            modified: metadata.modified().ok(),
+            len: contents.len() as u64,
+            hash: hasher.finish(),
+        }
+    }
+
+    /// Compares the file at `path` with this recorded state.
+    ///
+    /// Errors other than the file being missing are treated as no change,
+    /// so that a temporarily unreadable file does not trigger a reload.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file to check
+    ///
+    /// # Returns
+    ///
+    /// Returns how the file differs from the recorded state.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ninja::modules::file_io::{DiskChange, DiskState};
+    ///
+    /// let path = std::env::temp_dir().join("ninja-disk-state.txt");
+    /// std::fs::write(&path, b"one").unwrap();
+    /// let state = DiskState::new(b"one", &std::fs::metadata(&path).unwrap());
+    /// assert_eq!(state.check(&path), DiskChange::Unchanged);
+    ///
+    /// std::fs::write(&path, b"three").unwrap();
+    /// assert!(matches!(state.check(&path), DiskChange::Modified(_)));
+    /// ```
+    pub fn check(&self, path: &Path) -> DiskChange {
+        let metadata = match fs::metadata(path) {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return DiskChange::Deleted,
+            Err(_) => return DiskChange::Unchanged,
+        };
+        if metadata.len() == self.len && metadata.modified().ok() == self.modified {
+            return DiskChange::Unchanged;
+        }
+        let Ok(contents) = fs::read(path) else {
+            return DiskChange::Unchanged;
+        };
+        let current = DiskState::new(&contents, &metadata);
+        if current.len == self.len && current.hash == self.hash {
+            DiskChange::Touched(current)
+        } else {
+            DiskChange::Modified(current)
+        }
+    }
+}
+
+/// Writes `contents` to `path` atomically.
+///
+/// The data is written to a temporary file in the same directory, flushed
+/// to disk, given the original file's permissions and owner, and renamed
+/// over the original. If `path` is a symlink, its target is replaced
+/// instead of the link itself.
+///
+/// The file is written in place instead when it has more than one hard
+/// link (a rename would split the links apart), or when the temporary file
+/// cannot be created or cannot be given the original owner.
+///
+/// # Arguments
+///
+/// * `path` - The file to write
+/// * `contents` - The complete new contents of the file
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an `io::Error` if the file could not be written.
+///
+/// # Example
+///
+/// ```rust
+/// use ninja::modules::file_io;
+///
+/// let path = std::env::temp_dir().join("ninja-write-atomic.txt");
+/// file_io::write_atomic(&path, b"one").unwrap();
+/// file_io::write_atomic(&path, b"two").unwrap();
+/// assert_eq!(std::fs::read_to_string(&path).unwrap(), "two");
+///
+/// #[cfg(unix)]
+/// {
+///     use std::os::unix::fs::PermissionsExt;
+///     let mode = std::fs::Permissions::from_mode(0o640);
+///     std::fs::set_permissions(&path, mode).unwrap();
+///     file_io::write_atomic(&path, b"three").unwrap();
+///     assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o640);
+/// }
+/// ```
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let target = resolve_symlinks(path);
+    let metadata = fs::metadata(&target).ok();
+
+    if metadata.as_ref().is_some_and(is_hard_linked) {
+        return write_in_place(&target, contents);
+    }
+    match write_via_temp(&target, contents, metadata.as_ref()) {
+        Ok(()) => Ok(()),
+        // The directory may not be writable, or the owner could not be kept:
+        // overwrite the existing file directly rather than failing the save
+        Err(e) if metadata.is_some() => write_in_place(&target, contents).map_err(|_| e),
+        Err(e) => Err(e),
+    }
+}
+
+/// Writes a file through a helper command with elevated privileges.
+///
+/// The command template is split on whitespace; every `{file}` is replaced
+/// by the path, which is appended when there is no placeholder. The contents
+/// are passed on the helper's standard input and its standard output is
+/// discarded, so `sudo tee {file}` works as expected. No shell is involved.
+///
+/// The helper overwrites the file in place, so its mode, owner and links are
+/// kept. It runs in the foreground with the terminal's standard error and
+/// `/dev/tty`, so the caller must leave raw mode first if a password prompt
+/// is expected.
+///
+/// # Arguments
+///
+/// * `path` - The file to write
+/// * `contents` - The complete new contents of the file
+/// * `command` - The helper command template, e.g. `"sudo tee {file}"`
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the helper succeeded, or an `io::Error` if it could
+/// not be started or exited unsuccessfully.
+///
+/// # Example
+///
+/// ```rust
+/// use ninja::modules::file_io;
+///
+/// let path = std::env::temp_dir().join("ninja-write-elevated.txt");
+/// file_io::write_elevated(&path, b"hello", "tee {file}").unwrap();
+/// assert_eq!(std::fs::read(&path).unwrap(), b"hello");
+/// assert!(file_io::write_elevated(&path, b"", "").is_err());
+/// ```
+pub fn write_elevated(path: &Path, contents: &[u8], command: &str) -> io::Result<()> {
+    let mut words = command.split_whitespace();
+    let program = words
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no elevated save command configured"))?;
+    let mut helper = Command::new(program);
+    let mut has_placeholder = false;
+    for word in words {
+        if word.contains("{file}") {
+            has_placeholder = true;
+            let mut arg = OsString::new();
+            let mut parts = word.split("{file}").peekable();
+            while let Some(part) = parts.next() {
+                arg.push(part);
+                if parts.peek().is_some() {
+                    arg.push(path.as_os_str());
+                }
+            }
+            helper.arg(arg);
+        } else {
+            helper.arg(word);
+        }
+    }
+    if !has_placeholder {
+        helper.arg(path);
+    }
+
+    let mut child = helper
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|e| io::Error::new(e.kind(), format!("could not run {}: {}", program, e)))?;
+    let written = child
+        .stdin
+        .take()
+        .map_or(Ok(()), |mut stdin| stdin.write_all(contents));
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("{} failed ({})", program, status)));
+    }
+    written
+}
+
+/// Returns `true` if `path` exists but has no write permission, or cannot
+/// be opened for writing by the current user.
+///
+/// This is used to ask the user before overwriting a read-only file, since
+/// an atomic save would otherwise replace it as long as its directory is
+/// writable.
+///
+/// # Arguments
+///
+/// * `path` - The file to check
+///
+/// # Returns
+///
+/// Returns `true` for existing read-only files, `false` otherwise.
+///
+/// # Example
+///
+/// ```rust
+/// use ninja::modules::file_io;
+/// use std::path::Path;
+///
+/// assert!(!file_io::is_read_only(Path::new("does-not-exist.txt")));
+/// ```
+pub fn is_read_only(path: &Path) -> bool {
+    let target = resolve_symlinks(path);
+    let Ok(metadata) = fs::metadata(&target) else {
+        return false;
+    };
+    metadata.is_file()
+        && (metadata.permissions().readonly()
+            || matches!(
+                OpenOptions::new().write(true).open(&target),
+                Err(e) if e.kind() == io::ErrorKind::PermissionDenied
+            ))
+}
+
+/// Follows a chain of symlinks to the file that should actually be written.
+///
+/// Dangling links resolve to the path they point at, so saving creates the
+/// link target.
+fn resolve_symlinks(path: &Path) -> PathBuf {
+    let mut current = path.to_path_buf();
+    // Bound the walk so that symlink loops cannot hang the editor
+    for _ in 0..40 {
+        let is_link = fs::symlink_metadata(&current)
+            .map(|m| m.file_type().is_symlink())
+            .unwrap_or(false);
+        if !is_link {
+            break;
+        }
+        match fs::read_link(&current) {
+            Ok(link) => {
+                current = match current.parent() {
+                    Some(parent) if link.is_relative() => parent.join(link),
+                    _ => link,
+                };
+            }
+            Err(_) => break,
+        }
+    }
+    current
+}
+
+/// Returns `true` if the file has other hard links that a rename would detach.
+#[cfg(unix)]
+fn is_hard_linked(metadata: &Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    metadata.nlink() > 1
+}
+
+/// Returns `true` if the file has other hard links that a rename would detach.
+#[cfg(not(unix))]
+fn is_hard_linked(_metadata: &Metadata) -> bool {
+    false
+}
+
+/// Writes the file through a temporary sibling that is renamed into place.
+fn write_via_temp(target: &Path, contents: &[u8], metadata: Option<&Metadata>) -> io::Result<()> {
+    let name = target
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no file name specified"))?;
+    let dir = match target.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    let (temp, mut file) = create_temp(&dir, &name.to_string_lossy(), metadata.is_some())?;
+
+    let result = (|| {
+        // The owner and mode are settled before any contents reach the file
+        if let Some(metadata) = metadata {
+            copy_owner(&temp, metadata)?;
+            fs::set_permissions(&temp, metadata.permissions())?;
+        }
+        file.write_all(contents)?;
+        file.sync_all()?;
+        fs::rename(&temp, target)
+    })();
+
+    match result {
+        Ok(()) => {
+            // Persist the rename itself; not all platforms allow syncing a directory
+            if let Ok(dir) = File::open(&dir) {
+                let _ = dir.sync_all();
+            }
+            Ok(())
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&temp);
+            Err(e)
+        }
+    }
+}
+
+/// Creates a temporary file in `dir` for saving the file called `name`.
+///
+/// The file is always newly created, so a file or symlink already at the
+/// temporary name is never written through; another name is tried instead.
+/// When it replaces an existing file it starts out `private`, until it is
+/// given that file's owner and mode.
+fn create_temp(dir: &Path, name: &str, private: bool) -> io::Result<(PathBuf, File)> {
+    let mut options = if private { private_file_options() } else { OpenOptions::new() };
+    options.write(true).create_new(true);
+    for attempt in 0..100 {
+        let temp = dir.join(format!(".{}.ninja-tmp{}-{}", name, std::process::id(), attempt));
+        match options.open(&temp) {
+            Ok(file) => return Ok((temp, file)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        "could not create a temporary file to save through",
+    ))
+}
+
+/// Returns options that create files readable and writable only by their owner.
+#[cfg(unix)]
+fn private_file_options() -> OpenOptions {
+    use std::os::unix::fs::OpenOptionsExt;
+    let mut options = OpenOptions::new();
+    options.mode(0o600);
+    options
+}
+
+/// Returns options that create files readable and writable only by their owner.
+#[cfg(not(unix))]
+fn private_file_options() -> OpenOptions {
+    OpenOptions::new()
+}
+
+/// Gives `path` the owner and group recorded in `metadata`.
+#[cfg(unix)]
+fn copy_owner(path: &Path, metadata: &Metadata) -> io::Result<()> {
+    use std::os::unix::fs::MetadataExt;
+    let current = fs::metadata(path)?;
+    if current.uid() == metadata.uid() && current.gid() == metadata.gid() {
+        return Ok(());
+    }
+    std::os::unix::fs::chown(path, Some(metadata.uid()), Some(metadata.gid()))
+}
+
+/// Gives `path` the owner and group recorded in `metadata`.
+#[cfg(not(unix))]
+fn copy_owner(_path: &Path, _metadata: &Metadata) -> io::Result<()> {
+    Ok(())
+}
+
+/// Overwrites the file in place, keeping its inode, links, owner and mode.
+fn write_in_place(target: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(target)?;
+    file.write_all(contents)?;
+    file.sync_all()
+}
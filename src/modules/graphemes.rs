@@ -0,0 +1,193 @@
+//! # Graphemes Module
+//!
+//! This module is the editor's single model of how text is split into
+//! user-perceived characters and how wide they are on screen. Cursor
+//! movement, deletion, rendering and column conversions all go through it,
+//! so an emoji sequence, a flag or a letter with combining accents is always
+//! treated as one unit.
+//!
+//! Positions elsewhere in the editor stay character indices; this module
+//! makes sure they land on grapheme cluster boundaries.
+//!
+//! ## Features
+//!
+//! - **Grapheme Clusters**: Extended grapheme cluster segmentation (UAX #29)
+//! - **Display Width**: East Asian Width and emoji presentation (UAX #11)
+//! - **Tab Stops**: Tabs advance to the next multiple of the buffer's tab width
+//! - **Boundaries**: Stepping a character index to the next or previous cluster
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use ninja::modules::graphemes;
+//!
+//! // A family emoji is several characters joined into one cluster
+//! let text = "a👨\u{200d}👩\u{200d}👧b";
+//! assert_eq!(graphemes::next_boundary(text, 1), 6);
+//! assert_eq!(graphemes::previous_boundary(text, 6), 1);
+//! assert_eq!(graphemes::width(text), 4);
+//! ```
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Returns the display width of a grapheme cluster or string, in terminal columns.
+///
+/// Wide East Asian characters and emoji take two columns, combining marks
+/// take none. Tabs are not expanded here; use `advance` for them.
+///
+/// # Arguments
+///
+/// * `text` - The text to measure
+///
+/// # Example
+///
+/// ```rust
+/// use ninja::modules::graphemes::width;
+///
+/// assert_eq!(width("a"), 1);
+/// assert_eq!(width("中"), 2);
+/// assert_eq!(width("e\u{301}"), 1); // e with a combining acute accent
+/// assert_eq!(width("🇯🇵"), 2);
+/// ```
+pub fn width(text: &str) -> usize {
+    text.width()
+}
+
+/// Returns the display column after drawing `grapheme` at `column`.
+///
+/// # Arguments
+///
+/// * `column` - The display column the grapheme starts at
+/// * `grapheme` - A grapheme cluster
+/// * `tab_width` - The distance between tab stops
+///
+/// # Returns
+///
+/// Returns the column just past the grapheme. A tab advances to the next tab stop.
+///
+/// # Example
+///
+/// ```rust
+/// use ninja::modules::graphemes::advance;
+///
+/// assert_eq!(advance(3, "\t", 8), 8);
+/// assert_eq!(advance(3, "\t", 4), 4);
+/// assert_eq!(advance(3, "中", 4), 5);
+/// ```
+pub fn advance(column: usize, grapheme: &str, tab_width: usize) -> usize {
+    if grapheme == "\t" {
+        let tab_width = tab_width.max(1);
+        column + tab_width - column % tab_width
+    } else {
+        column + width(grapheme)
+    }
+}
+
+/// Splits `text` into grapheme clusters, each with the character index it starts at.
+///
+/// # Arguments
+///
+/// * `text` - The text to split
+///
+/// # Example
+///
+/// ```rust
+/// use ninja::modules::graphemes::clusters;
+///
+/// let parts: Vec<(usize, &str)> = clusters("e\u{301}x").collect();
+/// assert_eq!(parts, vec![(0, "e\u{301}"), (2, "x")]);
+/// ```
+pub fn clusters(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.graphemes(true).scan(0, |index, grapheme| {
+        let start = *index;
+        *index += grapheme.chars().count();
+        Some((start, grapheme))
+    })
+}
+
+/// Returns the character index of the next grapheme cluster boundary after `char_index`.
+///
+/// # Arguments
+///
+/// * `text` - The text to look in
+/// * `char_index` - A character index
+///
+/// # Returns
+///
+/// Returns the start of the following cluster, or the number of characters
+/// in `text` if there is none.
+pub fn next_boundary(text: &str, char_index: usize) -> usize {
+    clusters(text)
+        .map(|(start, grapheme)| start + grapheme.chars().count())
+        .find(|&end| end > char_index)
+        .unwrap_or_else(|| text.chars().count())
+}
+
+/// Returns the character index of the grapheme cluster boundary before `char_index`.
+///
+/// # Arguments
+///
+/// * `text` - The text to look in
+/// * `char_index` - A character index
+///
+/// # Returns
+///
+/// Returns the start of the cluster before `char_index`, or 0.
+pub fn previous_boundary(text: &str, char_index: usize) -> usize {
+    clusters(text)
+        .map(|(start, _)| start)
+        .take_while(|&start| start < char_index)
+        .last()
+        .unwrap_or(0)
+}
+
+/// Finds the characters of `text` that fit on screen when scrolled horizontally.
+///
+/// Only whole grapheme clusters are included: a wide character cut by either
+/// edge of the visible area is left out rather than drawn in half. When one
+/// is cut by the left edge, the visible text starts a column past `offset`
+/// and the gap is drawn blank.
+///
+/// # Arguments
+///
+/// * `text` - Rendered text, with tabs already expanded
+/// * `offset` - The first visible display column
+/// * `width` - The number of visible display columns
+///
+/// # Returns
+///
+/// Returns the range of character indices to draw and the display column
+/// the first of them starts at.
+///
+/// # Example
+///
+/// ```rust
+/// use ninja::modules::graphemes::visible_range;
+///
+/// // "中" covers columns 1-2 and "文" columns 3-4
+/// assert_eq!(visible_range("a中文b", 0, 4), (0..2, 0));
+/// assert_eq!(visible_range("a中文b", 3, 10), (2..4, 3));
+/// // Scrolled to the middle of "中", the text starts one column further right
+/// assert_eq!(visible_range("a中文b", 2, 10), (2..4, 3));
+/// ```
+pub fn visible_range(text: &str, offset: usize, width: usize) -> (std::ops::Range<usize>, usize) {
+    let mut column = 0;
+    let mut start = None;
+    let mut end = 0;
+    for (index, grapheme) in clusters(text) {
+        let next = column + self::width(grapheme);
+        if next > offset + width {
+            break;
+        }
+        if column >= offset {
+            start.get_or_insert((index, column));
+            end = index + grapheme.chars().count();
+        }
+        column = next;
+    }
+    match start {
+        Some((start, column)) => (start..end, column),
+        None => (0..0, offset),
+    }
+}
@@ -13,11 +13,14 @@
 //! - **Comment Support**: Single-line and multi-line comment highlighting
 //! - **String Literals**: Proper highlighting of string and character literals
 //! - **Keyword Recognition**: Language-specific keyword highlighting
+//! - **Indent Rules**: Language-specific rules for indenting new lines
+//! - **Auto-close Pairs**: Language-specific brackets and quotes closed as they are typed
 //! 
 //! ## Architecture
 //! 
 //! The highlighting system consists of:
 //! - **`HighlightType`**: Enumeration of different token types
+//! - **`IndentRules`**: When a language indents or dedents a line
 //! - **`SyntaxHighlight`**: Trait defining the interface for language highlighters
 //! - **`syntax_struct!`**: Macro for easily creating new language highlighters
 //! - **Language-specific structs**: Concrete implementations for each supported language
@@ -52,6 +55,7 @@ use crate::screens::editor::EditorContents;
 /// - **`Comment`**: Single-line comments
 /// - **`MultilineComment`**: Multi-line comments
 /// - **`Selection`**: Currently selected text
+/// - **`MatchingBracket`**: The bracket at the cursor and its partner
 /// - **`Other`**: Custom color for special cases
 /// 
 /// # Example
@@ -82,10 +86,79 @@ pub enum HighlightType {
     MultilineComment,
     /// Currently selected text
     Selection,
+    /// The bracket at the cursor and its partner
+    MatchingBracket,
     /// Custom color for special cases
     Other(Color),
 }
 
+/// Rules for indenting code in a language.
+/// 
+/// New lines are indented one level deeper when the line before them ends
+/// with one of `indent_after` or with one of the words in `indent_words`.
+/// Typing one of `dedent_on` as the first character of a line removes one
+/// level of indentation from it.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use ninja::modules::highlighting::IndentRules;
+/// 
+/// let rules = IndentRules::default();
+/// assert!(rules.indents_after("fn main() {"));
+/// assert!(!rules.indents_after("let x = 1;"));
+/// 
+/// let shell = IndentRules { indent_words: &["do", "then"], ..IndentRules::default() };
+/// assert!(shell.indents_after("if true; then"));
+/// assert!(!shell.indents_after("echo undo"));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct IndentRules {
+    /// Characters that indent the next line when they end a line
+    pub indent_after: &'static [char],
+    /// Words that indent the next line when they end a line
+    pub indent_words: &'static [&'static str],
+    /// Characters that dedent their line when typed at its start
+    pub dedent_on: &'static [char],
+}
+
+impl IndentRules {
+    /// Checks whether the line after `line` should be indented one level deeper.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `line` - The text before the new line
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `true` if `line` ends with an indenting character or word,
+    /// ignoring trailing whitespace.
+    pub fn indents_after(&self, line: &str) -> bool {
+        let line = line.trim_end();
+        if line.ends_with(self.indent_after) {
+            return true;
+        }
+        self.indent_words.iter().any(|word| {
+            line.strip_suffix(word)
+                .is_some_and(|rest| !rest.ends_with(|c: char| c.is_alphanumeric() || c == '_'))
+        })
+    }
+}
+
+/// Brackets and quotes typed in pairs, unless a language declares its own.
+pub const AUTO_PAIRS: &[(char, char)] = &[('(', ')'), ('[', ']'), ('{', '}'), ('"', '"'), ('\'', '\'')];
+
+impl Default for IndentRules {
+    /// Returns rules for languages that indent inside brackets.
+    fn default() -> Self {
+        Self {
+            indent_after: &['{', '(', '['],
+            indent_words: &[],
+            dedent_on: &['}', ')', ']'],
+        }
+    }
+}
+
 /// Trait defining the interface for syntax highlighting implementations.
 /// 
 /// This trait provides the contract that all language-specific syntax
@@ -168,6 +241,25 @@ pub trait SyntaxHighlight {
     /// or `None` if it doesn't.
     fn multiline_comment(&self) -> Option<(&str, &str)>;
     
+    /// Returns the rules for indenting new lines and closing brackets.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns the language's `IndentRules`; by default, indentation follows
+    /// brackets.
+    fn indent_rules(&self) -> IndentRules {
+        IndentRules::default()
+    }
+    
+    /// Returns the opening and closing characters that are typed in pairs.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `(open, close)` pairs; by default `AUTO_PAIRS`.
+    fn auto_pairs(&self) -> &'static [(char, char)] {
+        AUTO_PAIRS
+    }
+    
     /// Returns the color for a given highlight type.
     /// 
     /// # Arguments
@@ -265,6 +357,7 @@ pub trait SyntaxHighlight {
 ///             [Color::Yellow; "keyword1", "keyword2"],
 ///             [Color::Magenta; "type1", "type2"]
 ///         },
+///         pairs: [('(', ')'), ('"', '"')],
 ///         multiline_comment: Some(("/*", "*/"))
 ///     }
 /// }
@@ -277,6 +370,11 @@ pub trait SyntaxHighlight {
 /// - **`file_type`**: Human-readable language name
 /// - **`comment_start`**: String that starts single-line comments
 /// - **`keywords`**: Array of keyword groups with their colors
+/// - **`indent`**: Optional characters and words after which new lines are
+///   indented, and characters that dedent a line when typed at its start;
+///   `IndentRules::default()` if left out
+/// - **`pairs`**: Optional opening and closing characters that are typed
+///   together; `AUTO_PAIRS` if left out
 /// - **`multiline_comment`**: Optional tuple of multi-line comment delimiters
 /// 
 /// # Example
@@ -294,12 +392,34 @@ pub trait SyntaxHighlight {
 ///             [Color::Yellow; "if", "else", "while", "for"],
 ///             [Color::Magenta; "int", "string", "bool"]
 ///         },
+///         indent: {
+///             after: ['{', ':'],
+///             words: ["then"],
+///             dedent: ['}']
+///         },
+///         pairs: [('(', ')'), ('{', '}'), ('"', '"')],
 ///         multiline_comment: Some(("/*", "*/"))
 ///     }
 /// }
 /// ```
 #[macro_export]
 macro_rules! syntax_struct {
+    (@indent) => {
+        $crate::modules::highlighting::IndentRules::default()
+    };
+    (@indent [$($after:expr),*] [$($words:expr),*] [$($dedent:expr),*]) => {
+        $crate::modules::highlighting::IndentRules {
+            indent_after: &[$($after),*],
+            indent_words: &[$($words),*],
+            dedent_on: &[$($dedent),*],
+        }
+    };
+    (@pairs) => {
+        $crate::modules::highlighting::AUTO_PAIRS
+    };
+    (@pairs [$($pair:expr),*]) => {
+        &[$($pair),*]
+    };
     (
         struct $Name:ident {
             extensions:$ext:expr,
@@ -308,6 +428,12 @@ macro_rules! syntax_struct {
             keywords: {
                 $([$color:expr; $($words:expr),*]),*
             },
+            $(indent: {
+                after: [$($after:expr),*],
+                words: [$($indent_words:expr),*],
+                dedent: [$($dedent:expr),*]
+            },)?
+            $(pairs: [$($pair:expr),*],)?
             multiline_comment:$ml_comment:expr
         }
     ) => {
@@ -315,6 +441,8 @@ macro_rules! syntax_struct {
             extensions: &'static [&'static str],
             file_type: &'static str,
             comment_start:&'static str,
+            indent_rules: $crate::modules::highlighting::IndentRules,
+            auto_pairs: &'static [(char, char)],
             multiline_comment:Option<(&'static str,&'static str)>
         }
 
@@ -324,6 +452,10 @@ macro_rules! syntax_struct {
                     extensions: &$ext,
                     file_type: $type,
                     comment_start:$start,
+                    indent_rules: $crate::syntax_struct!(
+                        @indent $([$($after),*] [$($indent_words),*] [$($dedent),*])?
+                    ),
+                    auto_pairs: $crate::syntax_struct!(@pairs $([$($pair),*])?),
                     multiline_comment: $ml_comment
                 }
             }
@@ -339,6 +471,14 @@ macro_rules! syntax_struct {
                 self.multiline_comment
             }
 
+            fn indent_rules(&self) -> $crate::modules::highlighting::IndentRules {
+                self.indent_rules
+            }
+
+            fn auto_pairs(&self) -> &'static [(char, char)] {
+                self.auto_pairs
+            }
+
             fn extensions(&self) -> &[&str] {
                 self.extensions
             }
@@ -356,6 +496,7 @@ macro_rules! syntax_struct {
                     HighlightType::CharLiteral => Color::DarkGreen,
                     HighlightType::Comment | HighlightType::MultilineComment => Color::DarkGrey,
                     HighlightType::Selection => Color::White,
+                    HighlightType::MatchingBracket => Color::Red,
                     HighlightType::Other(color) => *color
                 }
             }
@@ -496,6 +637,7 @@ syntax_struct! {
                 "char","str","bool"
             ]
         },
+        pairs: [('(', ')'), ('[', ']'), ('{', '}'), ('"', '"')],
         multiline_comment: Some(("/*", "*/"))
     }
 }
@@ -553,6 +695,11 @@ syntax_struct! {
             ],
             [Color::Magenta; ]
         },
+        indent: {
+            after: [':', '(', '[', '{'],
+            words: [],
+            dedent: [')', ']', '}']
+        },
         multiline_comment: Some(("", ""))
     }
 }
@@ -766,6 +913,11 @@ syntax_struct! {
             ],
             [Color::Magenta; ]
         },
+        indent: {
+            after: ['{', '(', '[', '|'],
+            words: ["do", "then", "else", "begin"],
+            dedent: ['}', ')', ']']
+        },
         multiline_comment: Some(("", "")) // Ruby does not have multiline comments
     }
 }
@@ -783,6 +935,7 @@ syntax_struct! {
             ],
             [Color::Magenta; ]
         },
+        pairs: [('<', '>'), ('"', '"'), ('\'', '\'')],
         multiline_comment: Some(("<!--", "-->"))
     }
 }
@@ -814,6 +967,7 @@ syntax_struct! {
             ],
             [Color::Magenta; ]
         },
+        pairs: [('[', ']'), ('{', '}'), ('"', '"'), ('\'', '\'')],
         multiline_comment: Some(("#", "#"))
     }
 }
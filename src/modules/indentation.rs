@@ -0,0 +1,96 @@
+//! # Indentation Module
+//!
+//! This module works out how a file is indented, so that new indentation
+//! matches what is already there rather than the configured defaults.
+//!
+//! ## Features
+//!
+//! - **Tabs or Spaces**: Whichever starts more indented lines wins
+//! - **Indent Width**: The most common step between space-indented lines
+//! - **Bounded Scan**: Only the start of large files is examined
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use ninja::modules::indentation::{self, Indentation};
+//!
+//! let lines = ["fn main() {", "  if ready {", "    go();", "  }", "}"];
+//! assert_eq!(
+//!     indentation::detect(lines.iter().copied()),
+//!     Some(Indentation { soft_tabs: true, width: Some(2) })
+//! );
+//! ```
+
+/// Number of lines examined when detecting indentation.
+const MAX_LINES: usize = 1000;
+
+/// Indentation found in a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Indentation {
+    /// Whether the file indents with spaces rather than tabs
+    pub soft_tabs: bool,
+    /// Columns per indentation level, when it could be told from the file
+    pub width: Option<usize>,
+}
+
+/// Detects the indentation used by a file.
+///
+/// Lines are compared with the previous non-blank line; each increase in
+/// leading spaces is counted as one indentation step. Steps of a single
+/// space are ignored, since they mostly come from aligned comments.
+///
+/// # Arguments
+///
+/// * `lines` - The lines of the file
+///
+/// # Returns
+///
+/// Returns the detected `Indentation`, or `None` if no line is indented.
+///
+/// # Example
+///
+/// ```rust
+/// use ninja::modules::indentation::{self, Indentation};
+///
+/// let lines = ["int main() {", "\treturn 0;", "}"];
+/// assert_eq!(
+///     indentation::detect(lines.iter().copied()),
+///     Some(Indentation { soft_tabs: false, width: None })
+/// );
+/// assert_eq!(indentation::detect(["a", "b"].iter().copied()), None);
+/// ```
+pub fn detect<'a>(lines: impl Iterator<Item = &'a str>) -> Option<Indentation> {
+    let mut tab_lines = 0;
+    let mut space_lines = 0;
+    let mut steps = [0usize; 9];
+    let mut previous = 0;
+    for line in lines.take(MAX_LINES) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if line.starts_with('\t') {
+            tab_lines += 1;
+            previous = 0;
+            continue;
+        }
+        let spaces = line.chars().take_while(|&c| c == ' ').count();
+        if spaces > 0 {
+            space_lines += 1;
+        }
+        if spaces > previous && spaces - previous >= 2 && spaces - previous < steps.len() {
+            steps[spaces - previous] += 1;
+        }
+        previous = spaces;
+    }
+    if tab_lines == 0 && space_lines == 0 {
+        return None;
+    }
+    if tab_lines > space_lines {
+        return Some(Indentation { soft_tabs: false, width: None });
+    }
+    // Prefer the smaller step on ties, as nested code also produces its multiples
+    let width = (2..steps.len())
+        .filter(|&step| steps[step] > 0)
+        .max_by_key(|&step| (steps[step], std::cmp::Reverse(step)));
+    Some(Indentation { soft_tabs: true, width })
+}
@@ -0,0 +1,115 @@
+//! # Jump List Module
+//!
+//! This module remembers where the cursor was before large jumps, such as a
+//! search or a go-to, so the user can step back to earlier locations and
+//! forward again.
+//!
+//! ## Features
+//!
+//! - **Recording**: The position before each jump is stored
+//! - **Back / Forward**: Walk through earlier positions like browser history
+//! - **Bounded History**: Only the most recent positions are kept
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use ninja::modules::jump_list::JumpList;
+//!
+//! let mut jumps = JumpList::new();
+//! jumps.record((0, 0));
+//! // ... the cursor jumps to line 40 ...
+//! assert_eq!(jumps.back((39, 0)), Some((0, 0)));
+//! assert_eq!(jumps.forward(), Some((39, 0)));
+//! ```
+
+/// Maximum number of positions kept in the jump list.
+const MAX_JUMPS: usize = 100;
+
+/// A history of cursor positions, stored as `(row, column)` pairs.
+///
+/// Recording a new jump after stepping back discards the positions ahead of
+/// the current one, the same way a browser drops its forward history.
+///
+/// # Example
+///
+/// ```rust
+/// use ninja::modules::jump_list::JumpList;
+///
+/// let mut jumps = JumpList::new();
+/// jumps.record((3, 1));
+/// jumps.record((10, 0));
+/// assert_eq!(jumps.back((20, 4)), Some((10, 0)));
+/// assert_eq!(jumps.back((10, 0)), Some((3, 1)));
+/// assert_eq!(jumps.back((3, 1)), None);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct JumpList {
+    /// Recorded positions, oldest first
+    entries: Vec<(usize, usize)>,
+    /// Index of the current position; equal to `entries.len()` when not navigating
+    index: usize,
+}
+
+impl JumpList {
+    /// Creates an empty jump list.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `JumpList` with no recorded positions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the position the cursor is about to jump away from.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The `(row, column)` before the jump
+    pub fn record(&mut self, position: (usize, usize)) {
+        self.entries.truncate(self.index);
+        if self.entries.last() != Some(&position) {
+            self.entries.push(position);
+        }
+        if self.entries.len() > MAX_JUMPS {
+            self.entries.remove(0);
+        }
+        self.index = self.entries.len();
+    }
+
+    /// Steps back to the previous position.
+    ///
+    /// # Arguments
+    ///
+    /// * `current` - The cursor position, kept so that `forward` can return to it
+    ///
+    /// # Returns
+    ///
+    /// Returns the position to move to, or `None` if there is no earlier position.
+    pub fn back(&mut self, current: (usize, usize)) -> Option<(usize, usize)> {
+        if self.index == 0 {
+            return None;
+        }
+        if self.index == self.entries.len() {
+            self.record(current);
+            self.index = self.entries.len() - 1;
+            if self.index == 0 {
+                return None;
+            }
+        }
+        self.index -= 1;
+        Some(self.entries[self.index])
+    }
+
+    /// Steps forward to the position that was left with `back`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the position to move to, or `None` if there is no later position.
+    pub fn forward(&mut self) -> Option<(usize, usize)> {
+        if self.index + 1 >= self.entries.len() {
+            return None;
+        }
+        self.index += 1;
+        Some(self.entries[self.index])
+    }
+}
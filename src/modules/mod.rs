@@ -5,13 +5,24 @@
 //! 
 //! ## Module Overview
 //! 
+//! - **`backup`**: Backup copies of files taken before they are overwritten
 //! - **`cursor`**: Cursor positioning and movement logic
 //! - **`clipboard`**: Multi-item clipboard management
+//! - **`diff`**: Line-based diffs between two versions of a buffer
+//! - **`encoding`**: Character encoding detection and conversion
+//! - **`file_format`**: Encoding, line ending, final newline and BOM detection
+//! - **`file_io`**: Atomic, permission-preserving file writes
+//! - **`graphemes`**: Grapheme cluster segmentation and display width
+//! - **`indentation`**: Detecting whether and how a file is indented
+//! - **`jump_list`**: History of cursor positions before large jumps
 //! - **`highlighting`**: Syntax highlighting for various programming languages
+//! - **`multi_cursor`**: Additional cursors for editing several places at once
 //! - **`message_bar`**: User message display and management
 //! - **`search`**: Text search functionality and state management
 //! - **`status`**: Status message handling and display
 //! - **`statusbar`**: Status bar rendering and information display
+//! - **`swap`**: Crash-recovery swap files for unsaved changes
+//! - **`tty`**: Pointing piped standard streams back at the terminal
 //! 
 //! ## Architecture
 //! 
@@ -25,4 +36,15 @@ pub mod search;
 pub mod highlighting;
 pub mod clipboard;
 pub mod status;
-pub mod cursor; 
\ No newline at end of file
+pub mod cursor;
+pub mod swap;
+pub mod diff;
+pub mod backup;
+pub mod file_io;
+pub mod file_format;
+pub mod encoding;
+pub mod tty;
+pub mod jump_list;
+pub mod multi_cursor;
+pub mod graphemes;
+pub mod indentation;
@@ -0,0 +1,187 @@
+//! # Multi-Cursor Module
+//!
+//! This module provides the building blocks for editing with several cursors
+//! at once. The editor's primary cursor stays in `Output::cursor_controller`;
+//! every additional cursor is a `Caret` with its own position, preferred
+//! column and optional selection.
+//!
+//! ## Features
+//!
+//! - **Independent Carets**: Each caret moves with its own `CursorController`
+//! - **Per-Caret Selections**: A caret can select text from an anchor to its position
+//! - **Edit Tracking**: Positions are shifted to account for edits made at other cursors
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use ninja::modules::multi_cursor::shift_position;
+//!
+//! // A newline typed at (2, 4) moves a cursor at (2, 10) to the start of the next row
+//! assert_eq!(shift_position((2, 10), (2, 4), (3, 0)), (3, 6));
+//! ```
+
+use crate::modules::cursor::CursorController;
+
+/// An additional cursor used for multi-cursor editing.
+///
+/// # Example
+///
+/// ```rust
+/// use ninja::modules::cursor::CursorController;
+/// use ninja::modules::multi_cursor::Caret;
+///
+/// let mut cursor = CursorController::new((80, 24));
+/// cursor.cursor_y = 3;
+/// cursor.cursor_x = 8;
+/// let caret = Caret::new(cursor, Some((3, 2)));
+/// assert_eq!(caret.position(), (3, 8));
+/// assert_eq!(caret.selection_bounds(), Some(((3, 2), (3, 8))));
+/// ```
+#[derive(Copy, Clone)]
+pub struct Caret {
+    /// Position and movement state of the caret
+    pub cursor: CursorController,
+    /// Where the caret's selection started, as `(row, column)`
+    pub anchor: Option<(usize, usize)>,
+}
+
+impl Caret {
+    /// Creates a caret from a cursor and an optional selection anchor.
+    ///
+    /// # Arguments
+    ///
+    /// * `cursor` - The caret's cursor
+    /// * `anchor` - The start of the caret's selection, if any
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `Caret`.
+    pub fn new(cursor: CursorController, anchor: Option<(usize, usize)>) -> Self {
+        Self { cursor, anchor }
+    }
+
+    /// Returns the caret's position as `(row, column)`.
+    pub fn position(&self) -> (usize, usize) {
+        (self.cursor.cursor_y, self.cursor.cursor_x)
+    }
+
+    /// Moves the caret to a position without changing its selection anchor.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The new `(row, column)`
+    pub fn set_position(&mut self, position: (usize, usize)) {
+        (self.cursor.cursor_y, self.cursor.cursor_x) = position;
+    }
+
+    /// Returns the caret's selection, ordered from start to end.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some((start, end))`, or `None` if the caret selects nothing.
+    pub fn selection_bounds(&self) -> Option<((usize, usize), (usize, usize))> {
+        let anchor = self.anchor?;
+        let position = self.position();
+        match anchor.cmp(&position) {
+            std::cmp::Ordering::Less => Some((anchor, position)),
+            std::cmp::Ordering::Greater => Some((position, anchor)),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+/// Moves a position to account for an edit made at another cursor.
+///
+/// An edit is described by where its cursor was before (`from`) and after
+/// (`to`). If the cursor moved forward, the text between the two positions
+/// was inserted; if it moved backward, the text between them was deleted.
+/// Positions before the edit are unchanged and positions inside deleted text
+/// collapse to where the deletion ended.
+///
+/// # Arguments
+///
+/// * `position` - The `(row, column)` to move
+/// * `from` - The editing cursor's position before the edit
+/// * `to` - The editing cursor's position after the edit
+///
+/// # Returns
+///
+/// Returns the position after the edit.
+///
+/// # Example
+///
+/// ```rust
+/// use ninja::modules::multi_cursor::shift_position;
+///
+/// // Typing a character at (0, 2) pushes a later cursor on the same row right
+/// assert_eq!(shift_position((0, 5), (0, 2), (0, 3)), (0, 6));
+/// // Backspace at the start of row 4 joins it onto row 3, which was 7 long
+/// assert_eq!(shift_position((4, 2), (4, 0), (3, 7)), (3, 9));
+/// assert_eq!(shift_position((6, 1), (4, 0), (3, 7)), (5, 1));
+/// // Earlier positions are not affected
+/// assert_eq!(shift_position((1, 1), (4, 0), (3, 7)), (1, 1));
+/// ```
+pub fn shift_position(
+    position: (usize, usize),
+    from: (usize, usize),
+    to: (usize, usize),
+) -> (usize, usize) {
+    if position < from {
+        // Only a deletion reaches back before `from`; positions inside it collapse
+        return if to < from && position > to { to } else { position };
+    }
+    let row = position.0 + to.0 - from.0;
+    if position.0 == from.0 {
+        (row, to.1 + position.1 - from.1)
+    } else {
+        (row, position.1)
+    }
+}
+
+/// Moves a position to account for an edit that stayed within one row.
+///
+/// The edit started at column `at` of `row` and changed the row's length by
+/// `delta` characters. Positions after an insertion move right by its length;
+/// positions after a deletion move left, and those inside it collapse to `at`.
+/// Positions on other rows or before `at` are unchanged.
+///
+/// # Arguments
+///
+/// * `position` - The `(row, column)` to move
+/// * `row` - The row that was edited
+/// * `at` - The column where the edit started
+/// * `delta` - How many characters the row gained, negative if it lost some
+///
+/// # Returns
+///
+/// Returns the position after the edit.
+///
+/// # Example
+///
+/// ```rust
+/// use ninja::modules::multi_cursor::shift_in_row;
+///
+/// // Typing `(` at (0, 2) also inserted its closer, so a later cursor moves by two
+/// assert_eq!(shift_in_row((0, 6), 0, 2, 2), (0, 8));
+/// // Backspace inside an empty pair at (0, 3) removes columns 2 and 3
+/// assert_eq!(shift_in_row((0, 8), 0, 2, -2), (0, 6));
+/// assert_eq!(shift_in_row((0, 3), 0, 2, -2), (0, 2));
+/// // Stepping over a closer leaves the row as it was
+/// assert_eq!(shift_in_row((0, 6), 0, 2, 0), (0, 6));
+/// assert_eq!(shift_in_row((1, 6), 0, 2, 2), (1, 6));
+/// ```
+pub fn shift_in_row(
+    position: (usize, usize),
+    row: usize,
+    at: usize,
+    delta: isize,
+) -> (usize, usize) {
+    if position.0 != row || position.1 < at {
+        return position;
+    }
+    if position.1 < at + delta.min(0).unsigned_abs() {
+        (row, at)
+    } else {
+        (row, position.1.saturating_add_signed(delta))
+    }
+}
@@ -6,7 +6,7 @@
 //! 
 //! ## Features
 //! 
-//! - **File Information**: Shows current filename and modification status
+//! - **File Information**: Shows current filename, modification status and a `[RO]` marker
 //! - **Cursor Position**: Displays current line and column numbers
 //! - **Syntax Information**: Shows detected file type and syntax highlighting
 //! - **Visual Styling**: Uses reverse video for status bar appearance
@@ -15,12 +15,13 @@
 //! ## Layout
 //! 
 //! The status bar is divided into two sections:
-//! - **Left side**: Filename and modification status
-//! - **Right side**: File type, line number, and column number
+//! - **Left side**: Filename, read-only marker and modification status
+//! - **Right side**: File type, file format, line number, and column number
 //! 
 //! ## Usage
 //! 
 //! ```rust
+//! use ninja::modules::file_format::FileFormat;
 //! use ninja::modules::statusbar::StatusBar;
 //! use ninja::modules::cursor::CursorController;
 //! use ninja::screens::editor::EditorContents;
@@ -31,6 +32,7 @@
 //! let filename = Some(PathBuf::from("example.rs"));
 //! let dirty = 0; // File is not modified
 //! let syntax_highlight = None;
+//! let file_format = FileFormat::default();
 //! let cursor_controller = CursorController::new((80, 24));
 //! 
 //! StatusBar::draw_status_bar(
@@ -38,12 +40,15 @@
 //!     win_size,
 //!     &filename,
 //!     dirty,
+//!     false,
 //!     &syntax_highlight,
+//!     &file_format,
 //!     &cursor_controller,
 //! );
 //! ```
 
 use super::super::screens::editor::EditorContents;
+use crate::modules::file_format::FileFormat;
 use crate::modules::highlighting::SyntaxHighlight;
 use crate::modules::cursor::CursorController;
 use crossterm::style;
@@ -58,8 +63,10 @@ use std::cmp;
 /// 
 /// The status bar displays:
 /// - **Filename**: Name of the current file (or "[No Name]" if none)
+/// - **Read-Only Marker**: "[RO]" if the buffer is read-only
 /// - **Modification Status**: "(modified)" indicator if file has unsaved changes
 /// - **File Type**: Detected or configured syntax highlighting language
+/// - **File Format**: Line endings and byte order mark (e.g. "CRLF BOM")
 /// - **Cursor Position**: Current line and column numbers (1-based)
 /// 
 /// # Visual Appearance
@@ -71,6 +78,7 @@ use std::cmp;
 /// # Example
 /// 
 /// ```rust
+/// use ninja::modules::file_format::FileFormat;
 /// use ninja::modules::statusbar::StatusBar;
 /// use ninja::modules::cursor::CursorController;
 /// use ninja::screens::editor::EditorContents;
@@ -81,6 +89,7 @@ use std::cmp;
 /// let filename = Some(PathBuf::from("main.rs"));
 /// let dirty = 1; // File is modified
 /// let syntax_highlight = None;
+/// let file_format = FileFormat::default();
 /// let cursor_controller = CursorController::new((80, 24));
 /// 
 /// StatusBar::draw_status_bar(
@@ -88,7 +97,9 @@ use std::cmp;
 ///     win_size,
 ///     &filename,
 ///     dirty,
+///     false,
 ///     &syntax_highlight,
+///     &file_format,
 ///     &cursor_controller,
 /// );
 /// ```
@@ -107,22 +118,26 @@ impl StatusBar {
     /// * `win_size` - The terminal window size as (width, height)
     /// * `filename` - The current file path (optional)
     /// * `dirty` - Modification counter (0 = clean, >0 = modified)
+    /// * `read_only` - Whether the buffer is read-only
     /// * `syntax_highlight` - The current syntax highlighter (optional)
+    /// * `file_format` - The file's encoding, line endings, final newline and BOM
     /// * `cursor_controller` - The cursor controller for position information
     /// 
     /// # Layout Algorithm
     /// 
     /// 1. **Apply Styling**: Sets reverse video attribute for status bar
     /// 2. **Build File Info**: Creates filename and modification status string
-    /// 3. **Build Position Info**: Creates file type, line, and column string
+    /// 3. **Build Position Info**: Creates file type, file format, line, and column string
     /// 4. **Layout Calculation**: Distributes information across available width
     /// 5. **Render**: Writes the formatted status bar to the buffer
     /// 
     /// # Information Display
     /// 
     /// - **Filename**: Extracted from the path, falls back to "[No Name]"
+    /// - **Read-Only Marker**: Shows "[RO]" if `read_only` is set
     /// - **Modification Status**: Shows "(modified)" if `dirty > 0`
     /// - **File Type**: From syntax highlighter or "Detecting..."
+    /// - **File Format**: Line ending style, "(mixed)" and "BOM" markers
     /// - **Position**: Line and column numbers (1-based display)
     /// 
     /// # Responsive Behavior
@@ -134,6 +149,7 @@ impl StatusBar {
     /// # Example
     /// 
     /// ```rust
+    /// use ninja::modules::file_format::FileFormat;
     /// use ninja::modules::statusbar::StatusBar;
     /// use ninja::modules::cursor::CursorController;
     /// use ninja::screens::editor::EditorContents;
@@ -144,6 +160,7 @@ impl StatusBar {
     /// let filename = Some(PathBuf::from("/path/to/example.rs"));
     /// let dirty = 2; // File has been modified twice
     /// let syntax_highlight = None;
+    /// let file_format = FileFormat::default();
     /// let mut cursor_controller = CursorController::new((80, 24));
     /// cursor_controller.cursor_y = 4; // 5th line (0-based)
     /// cursor_controller.cursor_x = 15; // 16th column (0-based)
@@ -153,28 +170,34 @@ impl StatusBar {
     ///     win_size,
     ///     &filename,
     ///     dirty,
+    ///     false,
     ///     &syntax_highlight,
+    ///     &file_format,
     ///     &cursor_controller,
     /// );
-    /// // Status bar will show: "example.rs (modified)                    Detecting... | 5:16"
+    /// // Status bar will show: "example.rs (modified)               Detecting... | LF | 5:16"
     /// ```
+    #[allow(clippy::too_many_arguments)]
     pub fn draw_status_bar(
         editor_contents: &mut EditorContents,
         win_size: (usize, usize),
         filename: &Option<std::path::PathBuf>,
         dirty: u64,
+        read_only: bool,
         syntax_highlight: &Option<Box<dyn SyntaxHighlight>>,
+        file_format: &FileFormat,
         cursor_controller: &CursorController,
     ) {
         editor_contents.push_str(&style::Attribute::Reverse.to_string());
         
         let info = format!(
-            "{} {}",
+            "{}{} {}",
             filename
                 .as_ref()
                 .and_then(|path| path.file_name())
                 .and_then(|name| name.to_str())
                 .unwrap_or("[No Name]"),
+            if read_only { " [RO]" } else { "" },
             if dirty > 0 { "(modified)" } else { "" }
         );
         
@@ -182,11 +205,12 @@ impl StatusBar {
         
         /* LINES AND COLUMNS */
         let line_info = format!(
-            "{} | {}:{}",
+            "{} | {} | {}:{}",
             syntax_highlight
                 .as_ref()
                 .map(|highlight| highlight.file_type())
                 .unwrap_or("Detecting..."),
+            file_format.label(),
             cursor_controller.cursor_y + 1,
             cursor_controller.cursor_x + 1
         );
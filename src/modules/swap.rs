@@ -0,0 +1,422 @@
+//! # Swap File Module
+//!
+//! This module provides crash-recovery swap files for the Ninja editor.
+//! While a buffer has unsaved changes, its contents are periodically written
+//! to a swap file so that work survives a crash or a dropped SSH session.
+//!
+//! ## Features
+//!
+//! - **Idle Writes**: The swap file is only written once the buffer has been idle
+//! - **Change Tracking**: Nothing is written unless the buffer changed since the last write
+//! - **Recovery**: Existing swap files can be read back when a file is reopened
+//! - **Ownership**: Swap files of another running Ninja are recognised by the pid in their header
+//! - **Cleanup**: Swap files are removed on a clean save or quit
+//!
+//! ## Swap File Location
+//!
+//! - **Named files**: `.name.ninja-swp` next to the file being edited
+//! - **Read-only directories**: a path-mangled name under the Ninja state directory
+//! - **Untitled buffers**: `untitled-<pid>.ninja-swp` under the Ninja state directory
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use ninja::modules::swap::SwapFile;
+//! use std::path::Path;
+//!
+//! let mut swap = SwapFile::new();
+//! if let Some(contents) = SwapFile::read_existing(Path::new("notes.txt")) {
+//!     println!("Recovered {} bytes", contents.len());
+//! }
+//! swap.remove();
+//! ```
+
+use crate::screens::editor::EditorRows;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Magic value written at the start of every swap file.
+const SWAP_MAGIC: &str = "NINJA-SWAP";
+
+/// Extension used for all swap files.
+const SWAP_EXTENSION: &str = "ninja-swp";
+
+/// Tracks the swap file belonging to the active buffer.
+///
+/// The swap file is written lazily: every idle tick records whether the
+/// buffer changed, and the contents are only flushed to disk once the
+/// buffer has stayed unchanged for the configured idle period.
+///
+/// # Swap File Format
+///
+/// The first line is a header of the form
+/// `NINJA-SWAP<TAB>1<TAB><pid><TAB><original path>`, followed by the
+/// buffer contents exactly as they would be saved.
+///
+/// # Example
+///
+/// ```rust
+/// use ninja::modules::swap::SwapFile;
+/// use std::path::Path;
+///
+/// let path = SwapFile::path_for(Some(Path::new("/tmp/example.rs")));
+/// assert_eq!(path, Some("/tmp/.example.rs.ninja-swp".into()));
+/// ```
+pub struct SwapFile {
+    /// Path of the swap file currently on disk (if any)
+    written_path: Option<PathBuf>,
+    /// Value of the dirty counter when the swap file was last written
+    written_dirty: u64,
+    /// Value of the dirty counter seen on the previous idle tick
+    seen_dirty: u64,
+    /// When the dirty counter last changed
+    changed_at: Instant,
+}
+
+impl SwapFile {
+    /// Creates a new swap file tracker with nothing written yet.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `SwapFile` instance.
+    pub fn new() -> Self {
+        Self {
+            written_path: None,
+            written_dirty: 0,
+            seen_dirty: 0,
+            changed_at: Instant::now(),
+        }
+    }
+
+    /// Returns the preferred swap file path for a file.
+    ///
+    /// Named files get a hidden `.name.ninja-swp` file in the same directory.
+    /// Untitled buffers get a per-process file in the Ninja state directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The file being edited, or `None` for an untitled buffer
+    ///
+    /// # Returns
+    ///
+    /// Returns the swap file path, or `None` if no suitable location exists.
+    pub fn path_for(file: Option<&Path>) -> Option<PathBuf> {
+        match file {
+            Some(file) => {
+                let name = file.file_name()?.to_str()?;
+                let swap_name = format!(".{}.{}", name, SWAP_EXTENSION);
+                Some(match file.parent() {
+                    Some(parent) => parent.join(swap_name),
+                    None => PathBuf::from(swap_name),
+                })
+            }
+            None => Some(Self::state_dir()?.join(format!(
+                "untitled-{}.{}",
+                std::process::id(),
+                SWAP_EXTENSION
+            ))),
+        }
+    }
+
+    /// Returns the fallback swap file path inside the Ninja state directory.
+    ///
+    /// This is used when the directory of the edited file is not writable.
+    /// The absolute path of the file is mangled into a single file name by
+    /// replacing path separators with `%`.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The file being edited
+    ///
+    /// # Returns
+    ///
+    /// Returns the fallback swap file path, or `None` if there is no state directory.
+    pub fn fallback_path_for(file: &Path) -> Option<PathBuf> {
+        let absolute = fs::canonicalize(file)
+            .ok()
+            .or_else(|| std::env::current_dir().ok().map(|dir| dir.join(file)))?;
+        let mangled = absolute
+            .to_string_lossy()
+            .replace(['/', '\\', ':'], "%");
+        Some(Self::state_dir()?.join(format!("{}.{}", mangled, SWAP_EXTENSION)))
+    }
+
+    /// Returns the directory used for swap files that cannot live next to their file.
+    fn state_dir() -> Option<PathBuf> {
+        dirs::state_dir()
+            .or_else(dirs::data_local_dir)
+            .map(|dir| dir.join("ninja").join("swap"))
+    }
+
+    /// Reads the contents of an existing swap file for the given file.
+    ///
+    /// Both the preferred and the fallback location are checked. Files that
+    /// do not carry a valid swap header are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The file whose swap file should be read
+    ///
+    /// # Returns
+    ///
+    /// Returns the recovered buffer contents, or `None` if no swap file exists.
+    pub fn read_existing(file: &Path) -> Option<String> {
+        Self::existing_paths(file)
+            .into_iter()
+            .find_map(|path| Self::read_swap(&path))
+            .map(|(_, body)| body)
+    }
+
+    /// Returns the process id of another running Ninja that owns a swap file for the given file.
+    ///
+    /// The swap file header records the process that wrote it. While that
+    /// process is still alive, its swap file holds a buffer that is being
+    /// edited right now rather than work left behind by a crash.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The file whose swap files should be checked
+    ///
+    /// # Returns
+    ///
+    /// Returns the process id of the running owner, or `None` if every swap
+    /// file was left by a process that has exited (or by this one).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ninja::modules::swap::SwapFile;
+    /// use ninja::screens::editor::EditorRows;
+    ///
+    /// let path = std::env::temp_dir().join("ninja-swap-owner-example.txt");
+    /// let rows = EditorRows { filename: Some(path.clone()), ..Default::default() };
+    /// let mut swap = SwapFile::new();
+    /// swap.write(&rows).unwrap();
+    ///
+    /// // A swap file written by this process does not belong to another session
+    /// assert_eq!(SwapFile::live_owner(&path), None);
+    /// swap.remove();
+    /// ```
+    pub fn live_owner(file: &Path) -> Option<u32> {
+        Self::existing_paths(file)
+            .into_iter()
+            .filter_map(|path| Self::read_swap(&path))
+            .filter_map(|(pid, _)| pid)
+            .find(|&pid| pid != std::process::id() && process_alive(pid))
+    }
+
+    /// Removes any swap files left behind for the given file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The file whose swap files should be discarded
+    pub fn discard_existing(file: &Path) {
+        for path in Self::existing_paths(file) {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    /// Returns the swap file locations that exist on disk for a file.
+    fn existing_paths(file: &Path) -> Vec<PathBuf> {
+        [Self::path_for(Some(file)), Self::fallback_path_for(file)]
+            .into_iter()
+            .flatten()
+            .filter(|path| path.is_file())
+            .collect()
+    }
+
+    /// Reads and validates a single swap file.
+    ///
+    /// Returns the process id recorded in the header (if readable) and the
+    /// buffer contents.
+    fn read_swap(path: &Path) -> Option<(Option<u32>, String)> {
+        let contents = fs::read_to_string(path).ok()?;
+        let (header, body) = contents.split_once('\n')?;
+        let mut fields = header.split('\t');
+        if fields.next() != Some(SWAP_MAGIC) {
+            return None;
+        }
+        let pid = fields.nth(1).and_then(|pid| pid.parse().ok());
+        Some((pid, body.to_string()))
+    }
+
+    /// Processes an idle tick of the editor.
+    ///
+    /// This method tracks changes to the dirty counter and writes the swap
+    /// file once the buffer has been unchanged for at least `idle`. Clean
+    /// buffers never produce a swap file.
+    ///
+    /// # Arguments
+    ///
+    /// * `editor_rows` - The buffer to write
+    /// * `dirty` - The buffer's current modification counter
+    /// * `idle` - How long the buffer must be unchanged before writing
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(true)` if the swap file was written, `Ok(false)` if nothing
+    /// needed to be done, or an `io::Error` if writing failed.
+    pub fn tick(&mut self, editor_rows: &EditorRows, dirty: u64, idle: Duration) -> io::Result<bool> {
+        if dirty != self.seen_dirty {
+            self.seen_dirty = dirty;
+            self.changed_at = Instant::now();
+            return Ok(false);
+        }
+        if dirty == 0 || dirty == self.written_dirty || self.changed_at.elapsed() < idle {
+            return Ok(false);
+        }
+        // Record the attempt first so a failing write is not retried until the next change
+        self.written_dirty = dirty;
+        self.write(editor_rows)?;
+        Ok(true)
+    }
+
+    /// Writes the buffer contents to the swap file immediately.
+    ///
+    /// If the preferred location cannot be written, the fallback location in
+    /// the state directory is used instead. A swap file left at a previous
+    /// location (for example before a "save as") is removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `editor_rows` - The buffer to write
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an `io::Error` if no location could be written.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ninja::modules::swap::SwapFile;
+    /// use ninja::screens::editor::EditorRows;
+    ///
+    /// let path = std::env::temp_dir().join("ninja-swap-write-example.txt");
+    /// let rows = EditorRows { filename: Some(path.clone()), ..Default::default() };
+    /// let mut swap = SwapFile::new();
+    /// swap.write(&rows).unwrap();
+    ///
+    /// // Unsaved contents are only readable by their owner
+    /// #[cfg(unix)]
+    /// {
+    ///     use std::os::unix::fs::PermissionsExt;
+    ///     let swap_path = SwapFile::path_for(Some(&path)).unwrap();
+    ///     let mode = std::fs::metadata(swap_path).unwrap().permissions().mode();
+    ///     assert_eq!(mode & 0o777, 0o600);
+    /// }
+    /// swap.remove();
+    /// ```
+    pub fn write(&mut self, editor_rows: &EditorRows) -> io::Result<()> {
+        let filename = editor_rows.filename.as_deref();
+        let header = format!(
+            "{}\t1\t{}\t{}\n",
+            SWAP_MAGIC,
+            std::process::id(),
+            filename.map(|name| name.to_string_lossy()).unwrap_or_default()
+        );
+        let contents = editor_rows.contents();
+        let candidates = [
+            Self::path_for(filename),
+            filename.and_then(Self::fallback_path_for),
+        ];
+        let mut last_error = io::Error::new(io::ErrorKind::NotFound, "no swap file location available");
+        for path in candidates.into_iter().flatten() {
+            match Self::write_to(&path, &header, &contents) {
+                Ok(()) => {
+                    if let Some(old) = self.written_path.take()
+                        && old != path
+                    {
+                        let _ = fs::remove_file(old);
+                    }
+                    self.written_path = Some(path);
+                    return Ok(());
+                }
+                Err(e) => last_error = e,
+            }
+        }
+        Err(last_error)
+    }
+
+    /// Writes a swap file to a specific path, creating parent directories as needed.
+    fn write_to(path: &Path, header: &str, contents: &str) -> io::Result<()> {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = create_private(path)?;
+        file.write_all(header.as_bytes())?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()
+    }
+
+    /// Removes the swap file written by this tracker, if any.
+    ///
+    /// This is called after a clean save or quit. The change tracking state
+    /// is reset so that the next modification starts a fresh idle period.
+    pub fn remove(&mut self) {
+        if let Some(path) = self.written_path.take() {
+            let _ = fs::remove_file(path);
+        }
+        self.written_dirty = 0;
+        self.seen_dirty = 0;
+    }
+}
+
+impl Default for SwapFile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns whether a process with the given id is running.
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    if pid <= 0 {
+        return false;
+    }
+    // SAFETY: signal 0 performs no action; it only checks that the process exists
+    if unsafe { libc::kill(pid, 0) } == 0 {
+        return true;
+    }
+    // The process exists but belongs to another user
+    io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+/// Returns whether a process with the given id is running.
+///
+/// Processes cannot be checked on this platform, so every owner is assumed
+/// to have exited.
+#[cfg(not(unix))]
+fn process_alive(_pid: u32) -> bool {
+    false
+}
+
+/// Creates or truncates a swap file readable and writable only by its owner.
+///
+/// The swap file holds unsaved buffer contents, so it must not be more
+/// visible than the file being edited.
+#[cfg(unix)]
+fn create_private(path: &Path) -> io::Result<fs::File> {
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    // The mode only applies to new files; tighten one left by an older version
+    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    Ok(file)
+}
+
+/// Creates or truncates a swap file.
+#[cfg(not(unix))]
+fn create_private(path: &Path) -> io::Result<fs::File> {
+    fs::File::create(path)
+}
@@ -0,0 +1,102 @@
+//! # Terminal Redirection Module
+//!
+//! This module lets Ninja run in the middle of a shell pipeline. When standard
+//! input or standard output is a pipe, the editor still needs the terminal for
+//! keyboard input and drawing, so the standard streams are pointed back at the
+//! controlling terminal (`/dev/tty`).
+//!
+//! ## Features
+//!
+//! - **Piped Input**: After stdin has been read, the terminal becomes standard input again
+//! - **Piped Output**: Standard output is moved to the terminal, and the original
+//!   stream is kept so the edited buffer can be written to it on exit
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use ninja::modules::tty;
+//! use std::io::{self, Read, Write};
+//!
+//! fn filter() -> io::Result<()> {
+//!     let mut input = Vec::new();
+//!     io::stdin().read_to_end(&mut input)?;
+//!     tty::reattach_stdin()?;
+//!     let mut output = tty::detach_stdout()?;
+//!     // ... edit `input` on the terminal ...
+//!     output.write_all(&input)
+//! }
+//! ```
+
+use std::fs::File;
+use std::io;
+
+/// Makes the controlling terminal standard input again.
+///
+/// This is used once piped input has been read to the end, so that keyboard
+/// input keeps working. Nothing is changed if standard input is a terminal.
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an `io::Error` if there is no controlling
+/// terminal.
+#[cfg(unix)]
+pub fn reattach_stdin() -> io::Result<()> {
+    use std::io::IsTerminal;
+    if io::stdin().is_terminal() {
+        return Ok(());
+    }
+    redirect_to_tty(libc::STDIN_FILENO)
+}
+
+/// Makes the controlling terminal standard input again.
+///
+/// The console is read directly on this platform, so nothing needs to change.
+#[cfg(not(unix))]
+pub fn reattach_stdin() -> io::Result<()> {
+    Ok(())
+}
+
+/// Moves standard output to the controlling terminal.
+///
+/// # Returns
+///
+/// Returns the original standard output, which the caller can write the
+/// result to once the editor has exited, or an `io::Error` if there is no
+/// controlling terminal.
+#[cfg(unix)]
+pub fn detach_stdout() -> io::Result<File> {
+    use std::io::Write;
+    use std::os::fd::AsFd;
+    io::stdout().flush()?;
+    let original = io::stdout().as_fd().try_clone_to_owned()?;
+    redirect_to_tty(libc::STDOUT_FILENO)?;
+    Ok(File::from(original))
+}
+
+/// Moves standard output to the controlling terminal.
+///
+/// Not supported on this platform; always returns an `Unsupported` error.
+#[cfg(not(unix))]
+pub fn detach_stdout() -> io::Result<File> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "writing to a pipe is not supported on this platform",
+    ))
+}
+
+/// Replaces file descriptor `fd` with the controlling terminal.
+#[cfg(unix)]
+fn redirect_to_tty(fd: std::os::fd::RawFd) -> io::Result<()> {
+    use std::fs::OpenOptions;
+    use std::os::fd::AsRawFd;
+    let tty = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .map_err(|e| io::Error::new(e.kind(), format!("could not open /dev/tty: {}", e)))?;
+    // SAFETY: both descriptors are valid; dup2 atomically replaces `fd`
+    if unsafe { libc::dup2(tty.as_raw_fd(), fd) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
@@ -50,12 +50,22 @@ use crate::transput::transput::Reader;
 use crate::modules::highlighting::{HighlightType, SyntaxHighlight};
 use crate::keybinds::actions::ActionExecutor;
 use crate::keybinds::{KeybindContext, KeybindManager};
+use crate::modules::backup;
+use crate::modules::diff::unified_diff;
+use crate::modules::encoding::FileEncoding;
+use crate::modules::file_format::FileFormat;
+use crate::modules::file_io::{self, DiskChange, DiskState};
+use crate::modules::graphemes;
+use crate::modules::swap::SwapFile;
 use crate::{transput::transput, prompt, TAB_STOP};
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::terminal;
 use transput::Output;
 use std::io::{stdout, ErrorKind, Write};
+use std::collections::{HashSet, VecDeque};
 use std::path::PathBuf;
-use std::{env, fs, io};
+use std::time::{Duration, Instant};
+use std::{cmp, fs, io, mem};
 
 /// Represents a single line of text in the editor.
 /// 
@@ -101,6 +111,8 @@ pub struct Row {
     pub highlight: Vec<HighlightType>,
     /// Whether this line is part of a multi-line comment
     pub is_comment: bool,
+    /// Distance between tab stops when rendering the row
+    pub tab_width: usize,
 }
 
 impl Row {
@@ -117,7 +129,7 @@ impl Row {
     /// 
     /// # Returns
     /// 
-    /// Returns a new `Row` instance.
+    /// Returns a new `Row` instance, with tab stops every `TAB_STOP` columns.
     /// 
     /// # Example
     /// 
@@ -134,6 +146,7 @@ impl Row {
             render,
             highlight: Vec::new(),
             is_comment: false,
+            tab_width: TAB_STOP,
         }
     }
 
@@ -165,12 +178,7 @@ impl Row {
     /// ```
     pub fn insert_char(&mut self, at: usize, ch: char) {
         // Convert character index to byte index for safe insertion
-        let byte_index = self.row_content
-            .char_indices()
-            .nth(at)
-            .map(|(i, _)| i)
-            .unwrap_or_else(|| self.row_content.len());
-        
+        let byte_index = self.byte_index(at);
         self.row_content.insert(byte_index, ch);
         EditorRows::render_row(self)
     }
@@ -223,16 +231,21 @@ impl Row {
     /// Returns the display width in terminal columns:
     /// - **1**: Normal ASCII and most Unicode characters
     /// - **2**: Wide characters (CJK, emojis, etc.)
-    /// - **TAB_STOP**: Tab characters (typically 8)
+    /// - **TAB_STOP**: Tab characters, at the default tab width; within a row a
+    ///   tab reaches the next multiple of the row's `tab_width` instead
     /// 
     /// # Unicode Support
     /// 
-    /// The method supports a comprehensive range of Unicode characters:
+    /// Widths follow the Unicode East Asian Width property (UAX #11):
     /// - **CJK Characters**: Chinese, Japanese, Korean ideographs
     /// - **Emojis**: All emoji and pictographic symbols
-    /// - **Wide Symbols**: Various wide Unicode symbols
+    /// - **Combining Marks**: Zero width, drawn over the previous character
     /// - **Control Characters**: Tab and other control characters
     /// 
+    /// The width is the one the `graphemes` module gives the character as a
+    /// cluster of its own. A single character can't capture emoji sequences
+    /// or flags, so text is measured by grapheme cluster instead.
+    /// 
     /// # Example
     /// 
     /// ```rust
@@ -244,59 +257,7 @@ impl Row {
     /// assert_eq!(Row::char_width('\t'), 8); // Tab (TAB_STOP)
     /// ```
     pub fn char_width(ch: char) -> usize {
-        match ch {
-            '\t' => TAB_STOP,
-            // Check if character is in the "Wide" or "Fullwidth" Unicode categories
-            ch if ch as u32 >= 0x1100 && (
-                (ch as u32 <= 0x115F) || // Hangul Jamo
-                (ch as u32 == 0x2329) || (ch as u32 == 0x232A) || // Miscellaneous Technical
-                (ch as u32 >= 0x2E80 && ch as u32 <= 0x303E) || // CJK Radicals Supplement, etc.
-                (ch as u32 >= 0x3040 && ch as u32 <= 0x309F) || // Hiragana
-                (ch as u32 >= 0x30A0 && ch as u32 <= 0x30FF) || // Katakana
-                (ch as u32 >= 0x3100 && ch as u32 <= 0x312F) || // Bopomofo
-                (ch as u32 >= 0x3130 && ch as u32 <= 0x318F) || // Hangul Compatibility Jamo
-                (ch as u32 >= 0x3190 && ch as u32 <= 0x319F) || // Kanban
-                (ch as u32 >= 0x31A0 && ch as u32 <= 0x31BF) || // Bopomofo Extended
-                (ch as u32 >= 0x31C0 && ch as u32 <= 0x31EF) || // CJK Strokes
-                (ch as u32 >= 0x31F0 && ch as u32 <= 0x31FF) || // Katakana Phonetic Extensions
-                (ch as u32 >= 0x3200 && ch as u32 <= 0x32FF) || // Enclosed CJK Letters and Months
-                (ch as u32 >= 0x3300 && ch as u32 <= 0x33FF) || // CJK Compatibility
-                (ch as u32 >= 0x3400 && ch as u32 <= 0x4DBF) || // CJK Unified Ideographs Extension A
-                (ch as u32 >= 0x4E00 && ch as u32 <= 0x9FFF) || // CJK Unified Ideographs
-                (ch as u32 >= 0xA000 && ch as u32 <= 0xA48F) || // Yi Syllables
-                (ch as u32 >= 0xA490 && ch as u32 <= 0xA4CF) || // Yi Radicals
-                (ch as u32 >= 0xAC00 && ch as u32 <= 0xD7AF) || // Hangul Syllables
-                (ch as u32 >= 0xF900 && ch as u32 <= 0xFAFF) || // CJK Compatibility Ideographs
-                (ch as u32 >= 0xFE10 && ch as u32 <= 0xFE1F) || // Vertical Forms
-                (ch as u32 >= 0xFE30 && ch as u32 <= 0xFE4F) || // CJK Compatibility Forms
-                (ch as u32 >= 0xFF00 && ch as u32 <= 0xFFEF) || // Halfwidth and Fullwidth Forms
-                (ch as u32 >= 0x1B000 && ch as u32 <= 0x1B0FF) || // Kana Supplement
-                (ch as u32 >= 0x1D300 && ch as u32 <= 0x1D35F) || // Tai Xian Jing Symbols
-                (ch as u32 >= 0x1F000 && ch as u32 <= 0x1F02F) || // Mahjong Tiles
-                (ch as u32 >= 0x1F030 && ch as u32 <= 0x1F09F) || // Domino Tiles
-                (ch as u32 >= 0x1F0A0 && ch as u32 <= 0x1F0FF) || // Playing Cards
-                (ch as u32 >= 0x1F100 && ch as u32 <= 0x1F1FF) || // Enclosed Alphanumeric Supplement
-                (ch as u32 >= 0x1F200 && ch as u32 <= 0x1F2FF) || // Enclosed Ideographic Supplement
-                (ch as u32 >= 0x1F300 && ch as u32 <= 0x1F5FF) || // Miscellaneous Symbols and Pictographs
-                (ch as u32 >= 0x1F600 && ch as u32 <= 0x1F64F) || // Emoticons
-                (ch as u32 >= 0x1F650 && ch as u32 <= 0x1F67F) || // Ornamental Dingbats
-                (ch as u32 >= 0x1F680 && ch as u32 <= 0x1F6FF) || // Transport and Map Symbols
-                (ch as u32 >= 0x1F700 && ch as u32 <= 0x1F77F) || // Alchemical Symbols
-                (ch as u32 >= 0x1F780 && ch as u32 <= 0x1F7FF) || // Geometric Shapes Extended
-                (ch as u32 >= 0x1F800 && ch as u32 <= 0x1F8FF) || // Supplemental Arrows-C
-                (ch as u32 >= 0x1F900 && ch as u32 <= 0x1F9FF) || // Supplemental Symbols and Pictographs
-                (ch as u32 >= 0x1FA00 && ch as u32 <= 0x1FA6F) || // Chess Symbols
-                (ch as u32 >= 0x1FA70 && ch as u32 <= 0x1FAFF) || // Symbols and Pictographs Extended-A
-                (ch as u32 >= 0x20000 && ch as u32 <= 0x2A6DF) || // CJK Unified Ideographs Extension B
-                (ch as u32 >= 0x2A700 && ch as u32 <= 0x2B73F) || // CJK Unified Ideographs Extension C
-                (ch as u32 >= 0x2B740 && ch as u32 <= 0x2B81F) || // CJK Unified Ideographs Extension D
-                (ch as u32 >= 0x2B820 && ch as u32 <= 0x2CEAF) || // CJK Unified Ideographs Extension E
-                (ch as u32 >= 0x2CEB0 && ch as u32 <= 0x2EBEF) || // CJK Unified Ideographs Extension F
-                (ch as u32 >= 0x30000 && ch as u32 <= 0x3134F) || // CJK Unified Ideographs Extension G
-                (ch as u32 >= 0x31350 && ch as u32 <= 0x323AF)    // CJK Unified Ideographs Extension H
-            ) => 2,
-            _ => 1,
-        }
+        graphemes::advance(0, ch.encode_utf8(&mut [0; 4]), TAB_STOP)
     }
 
     /// Converts a render position to a content position.
@@ -311,7 +272,10 @@ impl Row {
     /// 
     /// # Returns
     /// 
-    /// Returns the corresponding character index in the original content.
+    /// Returns the corresponding character index in the original content. A
+    /// grapheme cluster that covers the position, such as a tab or a wide
+    /// character, maps to its start; positions past the end of the row map
+    /// to the end.
     /// 
     /// # Example
     /// 
@@ -321,20 +285,17 @@ impl Row {
     /// let row = Row::new("Hello\tWorld".to_string(), "Hello    World".to_string());
     /// assert_eq!(row.get_row_content_x(5), 5); // Before tab
     /// assert_eq!(row.get_row_content_x(8), 6); // After tab expansion
+    /// assert_eq!(row.get_row_content_x(40), 11); // Past the end
     /// ```
     pub fn get_row_content_x(&self, render_x: usize) -> usize {
-        let mut current_render_x = 0;
-        for (cursor_x, ch) in self.row_content.chars().enumerate() {
-            let char_width = Self::char_width(ch);
-            if ch == '\t' {
-                current_render_x += (TAB_STOP - 1) - (current_render_x % TAB_STOP);
-            }
-            current_render_x += char_width;
-            if current_render_x > render_x {
-                return cursor_x;
+        let mut column = 0;
+        for (index, grapheme) in graphemes::clusters(&self.row_content) {
+            column = graphemes::advance(column, grapheme, self.tab_width);
+            if column > render_x {
+                return index;
             }
         }
-        0
+        self.char_count()
     }
 
     /// Returns the number of characters in the row (UTF-8 safe).
@@ -358,6 +319,180 @@ impl Row {
         self.row_content.chars().count()
     }
 
+    /// Converts a character index to a byte offset into `row_content`.
+    /// 
+    /// Positions in the editor, such as the cursor and selections, count
+    /// characters. This converts one for slicing or editing the underlying
+    /// string.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `char_index` - The character index (0-based)
+    /// 
+    /// # Returns
+    /// 
+    /// Returns the byte offset of the character, or the length of the
+    /// content if the index is past the end of the row.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::screens::editor::Row;
+    /// 
+    /// let row = Row::new("café中".to_string(), "café中".to_string());
+    /// assert_eq!(row.byte_index(3), 3);
+    /// assert_eq!(row.byte_index(4), 5); // 'é' takes two bytes
+    /// assert_eq!(row.byte_index(9), 8);
+    /// ```
+    pub fn byte_index(&self, char_index: usize) -> usize {
+        self.row_content
+            .char_indices()
+            .nth(char_index)
+            .map_or(self.row_content.len(), |(i, _)| i)
+    }
+
+    /// Converts an index into the rendered characters to a character index.
+    /// 
+    /// The render expands each tab into several spaces, so every rendered
+    /// space of a tab maps back to the tab itself.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `render_index` - The index of a character in `render`
+    /// 
+    /// # Returns
+    /// 
+    /// Returns the index of the character in `row_content` it was rendered from.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::screens::editor::{EditorRows, Row};
+    /// 
+    /// let mut row = Row::new("a\tb".to_string(), String::new());
+    /// EditorRows::render_row(&mut row);
+    /// assert_eq!(row.char_index_for_render_index(0), 0);
+    /// assert_eq!(row.char_index_for_render_index(4), 1); // inside the tab
+    /// assert_eq!(row.char_index_for_render_index(8), 2);
+    /// ```
+    pub fn char_index_for_render_index(&self, render_index: usize) -> usize {
+        let mut column = 0;
+        let mut rendered = 0;
+        for (index, grapheme) in graphemes::clusters(&self.row_content) {
+            let next = graphemes::advance(column, grapheme, self.tab_width);
+            if grapheme == "\t" {
+                rendered += next - column;
+                if rendered > render_index {
+                    return index;
+                }
+            } else {
+                // Each character of a cluster is rendered as itself
+                for (offset, _) in grapheme.chars().enumerate() {
+                    rendered += 1;
+                    if rendered > render_index {
+                        return index + offset;
+                    }
+                }
+            }
+            column = next;
+        }
+        self.char_count()
+    }
+
+    /// Converts a character index in `row_content` to an index in `render`.
+    /// 
+    /// This is the inverse of [`Row::char_index_for_render_index`]; a tab
+    /// maps to the first of the spaces it is rendered as.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `char_index` - The index of a character in `row_content`
+    /// 
+    /// # Returns
+    /// 
+    /// Returns the index of the first character rendered for it.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::screens::editor::{EditorRows, Row};
+    /// 
+    /// let mut row = Row::new("a\tb".to_string(), String::new());
+    /// EditorRows::render_row(&mut row);
+    /// assert_eq!(row.render_index_for_char_index(1), 1);
+    /// assert_eq!(row.render_index_for_char_index(2), 8);
+    /// ```
+    pub fn render_index_for_char_index(&self, char_index: usize) -> usize {
+        let mut column = 0;
+        let mut rendered = 0;
+        for (index, grapheme) in graphemes::clusters(&self.row_content) {
+            if index >= char_index {
+                break;
+            }
+            let next = graphemes::advance(column, grapheme, self.tab_width);
+            rendered += if grapheme == "\t" { next - column } else { grapheme.chars().count() };
+            column = next;
+        }
+        rendered
+    }
+
+    /// Returns how each character of the row is highlighted.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns one highlight type per character of `row_content`; characters
+    /// the row has not been highlighted for are `HighlightType::Normal`.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::screens::editor::Row;
+    /// 
+    /// let row = Row::new("a\tb".to_string(), String::new());
+    /// assert_eq!(row.char_highlights().len(), 3);
+    /// ```
+    pub fn char_highlights(&self) -> Vec<HighlightType> {
+        let highlight = |index: usize| self.highlight.get(index).copied().unwrap_or(HighlightType::Normal);
+        if !self.row_content.contains('\t') {
+            // Without tabs every character is rendered as itself
+            return (0..self.char_count()).map(highlight).collect();
+        }
+        let mut highlights = Vec::with_capacity(self.row_content.len());
+        let mut column = 0;
+        let mut rendered = 0;
+        for (_, grapheme) in graphemes::clusters(&self.row_content) {
+            let next = graphemes::advance(column, grapheme, self.tab_width);
+            if grapheme == "\t" {
+                highlights.push(highlight(rendered));
+                rendered += next - column;
+            } else {
+                for _ in grapheme.chars() {
+                    highlights.push(highlight(rendered));
+                    rendered += 1;
+                }
+            }
+            column = next;
+        }
+        highlights
+    }
+
+    /// Returns how a character of the row is highlighted.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `char_index` - The index of a character in `row_content`
+    /// 
+    /// # Returns
+    /// 
+    /// Returns the character's highlight type, or `HighlightType::Normal` if
+    /// the row has not been highlighted that far.
+    pub fn highlight_at(&self, char_index: usize) -> HighlightType {
+        self.highlight
+            .get(self.render_index_for_char_index(char_index))
+            .copied()
+            .unwrap_or(HighlightType::Normal)
+    }
+
     /// Returns a substring by character indices (UTF-8 safe).
     /// 
     /// This method extracts a substring from the row using character indices
@@ -421,7 +556,7 @@ impl Row {
 /// use std::path::PathBuf;
 /// 
 /// let mut syntax_highlight = None;
-/// let editor_rows = EditorRows::new(&mut syntax_highlight);
+/// let editor_rows = EditorRows::new();
 /// 
 /// // Load from file
 /// let file_path = PathBuf::from("example.txt");
@@ -435,52 +570,78 @@ pub struct EditorRows {
     pub row_contents: Vec<Row>,
     /// The associated file path (if any)
     pub filename: Option<PathBuf>,
+    /// Encoding, line endings, final newline and BOM of the file
+    pub format: FileFormat,
+    /// State of the file when it was last loaded or saved
+    pub disk_state: Option<DiskState>,
+    /// Distance between tab stops, given to every row
+    pub tab_width: usize,
+}
+
+impl Default for EditorRows {
+    fn default() -> Self {
+        Self {
+            row_contents: Vec::new(),
+            filename: None,
+            format: FileFormat::default(),
+            disk_state: None,
+            tab_width: TAB_STOP,
+        }
+    }
 }
 
 impl EditorRows {
-    /// Creates a new editor rows instance.
+    /// Creates a new, empty editor rows instance for an untitled buffer.
+    /// 
+    /// # Returns
     /// 
-    /// This method initializes a new editor rows instance. If a filename
-    /// is provided as a command line argument, it attempts to load that file.
-    /// Otherwise, it creates an empty editor.
+    /// Returns a new `EditorRows` instance with no rows and no filename.
     /// 
-    /// # Arguments
+    /// # Example
     /// 
-    /// * `syntax_highlight` - Mutable reference to the syntax highlighter
+    /// ```rust
+    /// use ninja::screens::editor::EditorRows;
     /// 
-    /// # Returns
+    /// let editor_rows = EditorRows::new();
+    /// assert_eq!(editor_rows.number_of_rows(), 0);
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an untitled editor rows instance from raw bytes.
     /// 
-    /// Returns a new `EditorRows` instance.
+    /// The encoding and format are detected as for files; this is used for
+    /// text read from standard input.
     /// 
-    /// # Behavior
+    /// # Arguments
+    /// 
+    /// * `bytes` - The raw contents
+    /// * `syntax_highlight` - The active syntax highlighter (if any)
+    /// 
+    /// # Returns
     /// 
-    /// - **File Loading**: Attempts to load file from command line argument
-    /// - **Syntax Detection**: Automatically detects syntax highlighting
-    /// - **Empty Editor**: Creates empty editor if no file specified
+    /// Returns a new `EditorRows` instance without a filename.
     /// 
     /// # Example
     /// 
     /// ```rust
     /// use ninja::screens::editor::EditorRows;
-    /// use ninja::modules::highlighting::SyntaxHighlight;
     /// 
-    /// let mut syntax_highlight = None;
-    /// let editor_rows = EditorRows::new(&mut syntax_highlight);
+    /// let editor_rows = EditorRows::from_bytes(b"one\ntwo\n", &None);
+    /// assert_eq!(editor_rows.number_of_rows(), 2);
+    /// assert!(editor_rows.filename.is_none());
     /// ```
-    pub fn new(syntax_highlight: &mut Option<Box<dyn SyntaxHighlight>>) -> Self {
-        match env::args().nth(1) {
-            None => Self {
-                row_contents: Vec::new(),
-                filename: None,
-            },
-            Some(file) => Self::from_file(file.into(), syntax_highlight),
-        }
+    pub fn from_bytes(bytes: &[u8], syntax_highlight: &Option<Box<dyn SyntaxHighlight>>) -> Self {
+        let mut editor_rows = Self::default();
+        editor_rows.set_bytes(bytes, None, syntax_highlight);
+        editor_rows
     }
 
     /// Creates an editor rows instance from a file.
     /// 
     /// This method loads a file and creates an editor rows instance with
-    /// its contents. It handles UTF-8 encoding and automatically detects
+    /// its contents. It detects the file's encoding and automatically detects
     /// syntax highlighting based on the file extension.
     /// 
     /// # Arguments
@@ -494,8 +655,8 @@ impl EditorRows {
     /// 
     /// # File Handling
     /// 
-    /// - **UTF-8 Reading**: Attempts to read as UTF-8 first
-    /// - **Fallback**: Uses lossy conversion for invalid UTF-8
+    /// - **Encoding Detection**: Honors byte order marks, then UTF-16 heuristics, then UTF-8
+    /// - **Fallback**: Reads other files as Windows-1252
     /// - **Error Recovery**: Returns empty content if file can't be read
     /// - **Syntax Detection**: Automatically sets syntax highlighter
     /// 
@@ -514,34 +675,70 @@ impl EditorRows {
         file: PathBuf,
         syntax_highlight: &mut Option<Box<dyn SyntaxHighlight>>,
     ) -> Self {
-        // Use lossy UTF-8 conversion to handle invalid UTF-8 gracefully
-        let file_contents = match fs::read_to_string(&file) {
-            Ok(contents) => contents,
-            Err(_) => {
-                // If UTF-8 conversion fails, try reading as bytes and converting lossily
-                match fs::read(&file) {
-                    Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
-                    Err(_) => String::new(), // Return empty string if file can't be read
-                }
-            }
-        };
-        
-        let mut row_contents = Vec::new();
         file.extension()
             .and_then(|ext| ext.to_str())
             .map(|ext| Output::select_syntax(ext).map(|syntax| syntax_highlight.insert(syntax)));
-        file_contents.lines().enumerate().for_each(|(i, line)| {
-            let mut row = Row::new(line.into(), String::new());
-            Self::render_row(&mut row);
-            row_contents.push(row);
-            if let Some(it) = syntax_highlight {
-                it.update_syntax(i, &mut row_contents)
-            }
-        });
-        Self {
+        let mut editor_rows = Self {
             filename: Some(file),
-            row_contents,
-        }
+            ..Default::default()
+        };
+        // A file that can't be read opens as an empty buffer
+        let _ = editor_rows.load(None, syntax_highlight);
+        editor_rows
+    }
+
+    /// Reads the file from disk, replacing all rows and the file format.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `encoding` - The encoding to decode the file with, or `None` to detect it
+    /// * `syntax_highlight` - The active syntax highlighter (if any)
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `Ok(())` on success, or an `io::Error` if there is no filename
+    /// or the file could not be read. The buffer is unchanged on error.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::modules::encoding::FileEncoding;
+    /// use ninja::screens::editor::EditorRows;
+    /// use std::path::PathBuf;
+    /// 
+    /// let path = std::env::temp_dir().join("ninja-load-example.txt");
+    /// std::fs::write(&path, b"caf\xE9\n").unwrap();
+    /// let mut editor_rows = EditorRows { filename: Some(path), ..Default::default() };
+    /// editor_rows.load(Some(FileEncoding::Latin1), &None).unwrap();
+    /// assert_eq!(editor_rows.contents(), "café\n");
+    /// ```
+    pub fn load(
+        &mut self,
+        encoding: Option<FileEncoding>,
+        syntax_highlight: &Option<Box<dyn SyntaxHighlight>>,
+    ) -> io::Result<()> {
+        let name = self
+            .filename
+            .as_ref()
+            .ok_or_else(|| io::Error::other("no file name specified"))?;
+        let bytes = fs::read(name)?;
+        self.disk_state = fs::metadata(name)
+            .ok()
+            .map(|metadata| DiskState::new(&bytes, &metadata));
+        self.set_bytes(&bytes, encoding, syntax_highlight);
+        Ok(())
+    }
+
+    /// Decodes raw bytes, replacing all rows and the file format.
+    fn set_bytes(
+        &mut self,
+        bytes: &[u8],
+        encoding: Option<FileEncoding>,
+        syntax_highlight: &Option<Box<dyn SyntaxHighlight>>,
+    ) {
+        let (format, lines) = FileFormat::from_bytes(bytes, encoding);
+        self.format = format;
+        self.set_lines(lines, syntax_highlight);
     }
 
     /// Returns the number of rows in the editor.
@@ -555,7 +752,7 @@ impl EditorRows {
     /// ```rust
     /// use ninja::screens::editor::EditorRows;
     /// 
-    /// let editor_rows = EditorRows { row_contents: vec![], filename: None };
+    /// let editor_rows = EditorRows { row_contents: vec![], filename: None, ..Default::default() };
     /// assert_eq!(editor_rows.number_of_rows(), 0);
     /// ```
     pub fn number_of_rows(&self) -> usize {
@@ -582,7 +779,7 @@ impl EditorRows {
     /// use ninja::screens::editor::{EditorRows, Row};
     /// 
     /// let row = Row::new("Hello".to_string(), "Hello".to_string());
-    /// let editor_rows = EditorRows { row_contents: vec![row], filename: None };
+    /// let editor_rows = EditorRows { row_contents: vec![row], filename: None, ..Default::default() };
     /// assert_eq!(editor_rows.get_row(0), "Hello");
     /// ```
     pub fn get_row(&self, at: usize) -> &str {
@@ -609,7 +806,7 @@ impl EditorRows {
     /// use ninja::screens::editor::{EditorRows, Row};
     /// 
     /// let row = Row::new("Hello\tWorld".to_string(), "Hello    World".to_string());
-    /// let editor_rows = EditorRows { row_contents: vec![row], filename: None };
+    /// let editor_rows = EditorRows { row_contents: vec![row], filename: None, ..Default::default() };
     /// assert_eq!(editor_rows.get_render(0), "Hello    World");
     /// ```
     pub fn get_render(&self, at: usize) -> &String {
@@ -636,7 +833,7 @@ impl EditorRows {
     /// use ninja::screens::editor::{EditorRows, Row};
     /// 
     /// let row = Row::new("Hello".to_string(), "Hello".to_string());
-    /// let editor_rows = EditorRows { row_contents: vec![row], filename: None };
+    /// let editor_rows = EditorRows { row_contents: vec![row], filename: None, ..Default::default() };
     /// let row_ref = editor_rows.get_editor_row(0);
     /// assert_eq!(row_ref.row_content, "Hello");
     /// ```
@@ -664,7 +861,7 @@ impl EditorRows {
     /// use ninja::screens::editor::{EditorRows, Row};
     ///
     /// let mut row = Row::new("Hello".to_string(), "Hello".to_string());
-    /// let mut editor_rows = EditorRows { row_contents: vec![row], filename: None };
+    /// let mut editor_rows = EditorRows { row_contents: vec![row], filename: None, ..Default::default() };
     /// let mut_row_ref = editor_rows.get_editor_row_mut(0);
     /// mut_row_ref.row_content = "Goodbye".to_string();
     /// assert_eq!(editor_rows.get_row(0), "Goodbye");
@@ -685,7 +882,8 @@ impl EditorRows {
     /// # Behavior
     /// 
     /// - **Tab Expansion**: Replaces tabs (`\t`) with spaces until the next
-    ///   multiple of `TAB_STOP` (typically 8).
+    ///   multiple of the row's `tab_width`, counting display columns so
+    ///   wide characters before a tab are accounted for.
     /// - **Capacity**: Allocates enough capacity for the rendered string.
     /// - **UTF-8 Safe**: Handles multi-byte characters correctly.
     /// 
@@ -699,24 +897,22 @@ impl EditorRows {
     /// assert_eq!(row.render, "Hello    World");
     /// ```
     pub fn render_row(row: &mut Row) {
-        let mut index = 0;
+        let mut column = 0;
         let capacity = row
             .row_content
             .chars()
-            .fold(0, |acc, next| acc + if next == '\t' { TAB_STOP } else { 1 });
-        row.render = String::with_capacity(capacity);
-        row.row_content.chars().for_each(|c| {
-            index += 1;
-            if c == '\t' {
-                row.render.push(' ');
-                while index % TAB_STOP != 0 {
-                    row.render.push(' ');
-                    index += 1
-                }
+            .fold(0, |acc, next| acc + if next == '\t' { row.tab_width } else { 1 });
+        let mut render = String::with_capacity(capacity);
+        for (_, grapheme) in graphemes::clusters(&row.row_content) {
+            let next = graphemes::advance(column, grapheme, row.tab_width);
+            if grapheme == "\t" {
+                (column..next).for_each(|_| render.push(' '));
             } else {
-                row.render.push(c);
+                render.push_str(grapheme);
             }
-        });
+            column = next;
+        }
+        row.render = render;
     }
 
     /// Inserts a new row at the specified position.
@@ -740,13 +936,14 @@ impl EditorRows {
     /// ```rust
     /// use ninja::screens::editor::{EditorRows, Row};
     /// 
-    /// let mut editor_rows = EditorRows { row_contents: vec![], filename: None };
+    /// let mut editor_rows = EditorRows { row_contents: vec![], filename: None, ..Default::default() };
     /// editor_rows.insert_row(0, "Hello".to_string());
     /// assert_eq!(editor_rows.number_of_rows(), 1);
     /// assert_eq!(editor_rows.get_row(0), "Hello");
     /// ```
     pub fn insert_row(&mut self, at: usize, contents: String) {
         let mut new_row = Row::new(contents, String::new());
+        new_row.tab_width = self.tab_width;
         EditorRows::render_row(&mut new_row);
         self.row_contents.insert(at, new_row);
     }
@@ -754,8 +951,8 @@ impl EditorRows {
     /// Saves the current content of the editor to the associated file.
     /// 
     /// This method writes the content of all rows to the file specified
-    /// by the `filename` field. It handles UTF-8 encoding and ensures
-    /// the file's length matches the content length.
+    /// by the `filename` field. The write is atomic, so a crash or a full
+    /// disk never leaves a truncated file behind.
     /// 
     /// # Returns
     /// 
@@ -763,10 +960,11 @@ impl EditorRows {
     /// 
     /// # Behavior
     /// 
-    /// - **File Opening**: Opens the file with write and create permissions.
-    /// - **Content Writing**: Writes the content of all rows joined by newlines.
-    /// - **Length Setting**: Sets the file's length to the content length.
-    /// - **Error Handling**: Returns an error if the file cannot be opened or written.
+    /// - **Content Writing**: Writes all rows using the file's line endings, final newline and BOM.
+    /// - **Atomic Replace**: Writes a temporary file, fsyncs it and renames it over the original.
+    /// - **Permissions**: Keeps the original file's mode, owner and symlink target.
+    /// - **Hard Links**: Falls back to writing in place for hard-linked files.
+    /// - **Error Handling**: Returns an error if the file cannot be written.
     /// 
     /// # Example
     /// 
@@ -776,7 +974,7 @@ impl EditorRows {
     /// use std::io::ErrorKind;
     /// use std::path::PathBuf;
     ///
-    /// let mut editor_rows = EditorRows { row_contents: vec![], filename: None };
+    /// let mut editor_rows = EditorRows { row_contents: vec![], filename: None, ..Default::default() };
     /// let result = editor_rows.save();
     /// assert_eq!(result, Err(io::Error::new(ErrorKind::Other, "no file name specified")));
     ///
@@ -789,20 +987,132 @@ impl EditorRows {
         match &self.filename {
             None => Err(io::Error::new(ErrorKind::Other, "no file name specified")),
             Some(name) => {
-                let mut file = fs::OpenOptions::new().write(true).create(true).open(name)?;
-                let contents: String = self
-                    .row_contents
-                    .iter()
-                    .map(|it| it.row_content.as_str())
-                    .collect::<Vec<&str>>()
-                    .join("\n");
-                file.set_len(contents.len() as u64)?;
-                file.write_all(contents.as_bytes())?;
-                Ok(contents.as_bytes().len())
+                let contents = self.to_bytes()?;
+                file_io::write_atomic(name, &contents)?;
+                self.disk_state = fs::metadata(name)
+                    .ok()
+                    .map(|metadata| DiskState::new(&contents, &metadata));
+                Ok(contents.len())
             }
         }
     }
 
+    /// Encodes the buffer exactly as it is written to disk.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns the encoded contents, including the byte order mark, or an
+    /// `InvalidData` error if a character cannot be represented in the
+    /// buffer's encoding.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::screens::editor::EditorRows;
+    /// 
+    /// let editor_rows = EditorRows::from_bytes(b"one\r\ntwo\r\n", &None);
+    /// assert_eq!(editor_rows.to_bytes().unwrap(), b"one\r\ntwo\r\n");
+    /// ```
+    pub fn to_bytes(&self) -> io::Result<Vec<u8>> {
+        self.format
+            .to_bytes(self.row_contents.iter().map(|it| it.row_content.as_str()))
+            .map_err(|e| {
+                io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("{} in {}", e, self.format.encoding.name()),
+                )
+            })
+    }
+
+    /// Returns the full buffer contents as it would be written to disk.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns all rows joined by the file's line ending, followed by a final
+    /// line ending if the file had one. The byte order mark is not included.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::screens::editor::{EditorRows, Row};
+    /// 
+    /// let editor_rows = EditorRows { row_contents: vec![Row::new("Hello".to_string(), "Hello".to_string()), Row::new("World".to_string(), "World".to_string())], filename: None, ..Default::default() };
+    /// assert_eq!(editor_rows.contents(), "Hello\nWorld");
+    /// ```
+    pub fn contents(&self) -> String {
+        self.format
+            .join(self.row_contents.iter().map(|it| it.row_content.as_str()))
+    }
+
+    /// Checks whether the file was changed on disk since it was loaded or saved.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns how the file differs from its recorded state, or
+    /// `DiskChange::Unchanged` for buffers that were never loaded or saved.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::modules::file_io::DiskChange;
+    /// use ninja::screens::editor::EditorRows;
+    /// 
+    /// let editor_rows = EditorRows::default();
+    /// assert_eq!(editor_rows.disk_change(), DiskChange::Unchanged);
+    /// ```
+    pub fn disk_change(&self) -> DiskChange {
+        match (&self.filename, &self.disk_state) {
+            (Some(filename), Some(state)) => state.check(filename),
+            _ => DiskChange::Unchanged,
+        }
+    }
+
+    /// Replaces all rows with the given text.
+    /// 
+    /// The text is split into lines (accepting any line ending), each line
+    /// is rendered and the syntax highlighting is recomputed. The filename
+    /// and file format are left untouched.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `contents` - The new buffer contents
+    /// * `syntax_highlight` - The active syntax highlighter (if any)
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::screens::editor::EditorRows;
+    /// 
+    /// let mut editor_rows = EditorRows { row_contents: vec![], filename: None, ..Default::default() };
+    /// editor_rows.replace_contents("one\ntwo", &None);
+    /// assert_eq!(editor_rows.number_of_rows(), 2);
+    /// ```
+    pub fn replace_contents(
+        &mut self,
+        contents: &str,
+        syntax_highlight: &Option<Box<dyn SyntaxHighlight>>,
+    ) {
+        self.set_lines(FileFormat::split_lines(contents), syntax_highlight);
+    }
+
+    /// Replaces all rows with already split lines and renders them.
+    fn set_lines(
+        &mut self,
+        lines: Vec<String>,
+        syntax_highlight: &Option<Box<dyn SyntaxHighlight>>,
+    ) {
+        self.row_contents.clear();
+        lines.into_iter().enumerate().for_each(|(i, line)| {
+            let mut row = Row::new(line, String::new());
+            row.tab_width = self.tab_width;
+            Self::render_row(&mut row);
+            self.row_contents.push(row);
+            if let Some(it) = syntax_highlight {
+                it.update_syntax(i, &mut self.row_contents)
+            }
+        });
+    }
+
     /// Joins the row at the specified index with the previous row.
     /// 
     /// This method removes the row at `at` and concatenates its content
@@ -824,7 +1134,7 @@ impl EditorRows {
     /// ```rust
     /// use ninja::screens::editor::{EditorRows, Row};
     /// 
-    /// let mut editor_rows = EditorRows { row_contents: vec![Row::new("Hello".to_string(), "Hello".to_string()), Row::new("World".to_string(), "World".to_string())], filename: None };
+    /// let mut editor_rows = EditorRows { row_contents: vec![Row::new("Hello".to_string(), "Hello".to_string()), Row::new("World".to_string(), "World".to_string())], filename: None, ..Default::default() };
     /// editor_rows.join_adjacent_rows(1);
     /// assert_eq!(editor_rows.number_of_rows(), 1);
     /// assert_eq!(editor_rows.get_row(0), "HelloWorld");
@@ -835,6 +1145,112 @@ impl EditorRows {
         previous_row.row_content.push_str(&current_row.row_content);
         Self::render_row(previous_row);
     }
+
+    /// Changes the distance between tab stops and re-renders every row.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `tab_width` - The new tab width, in columns
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::screens::editor::EditorRows;
+    /// 
+    /// let mut editor_rows = EditorRows::from_bytes(b"\tx\n", &None);
+    /// assert_eq!(editor_rows.get_render(0), "        x");
+    /// editor_rows.set_tab_width(4);
+    /// assert_eq!(editor_rows.get_render(0), "    x");
+    /// ```
+    pub fn set_tab_width(&mut self, tab_width: usize) {
+        self.tab_width = tab_width.max(1);
+        for row in &mut self.row_contents {
+            row.tab_width = self.tab_width;
+            Self::render_row(row);
+        }
+    }
+
+    /// Returns the text between two positions.
+    /// 
+    /// Positions are `(row, column)` pairs where the column is a character
+    /// index, the same as the cursor uses. Rows are joined with `'\n'`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `start` - The first position (inclusive)
+    /// * `end` - The last position (exclusive); must not come before `start`
+    /// 
+    /// # Returns
+    /// 
+    /// Returns the text in the range. Columns past the end of a row are clamped.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::screens::editor::EditorRows;
+    /// 
+    /// let editor_rows = EditorRows::from_bytes("héllo\n中文字\n".as_bytes(), &None);
+    /// assert_eq!(editor_rows.text_between((0, 1), (0, 4)), "éll");
+    /// assert_eq!(editor_rows.text_between((0, 4), (1, 2)), "o\n中文");
+    /// ```
+    pub fn text_between(&self, start: (usize, usize), end: (usize, usize)) -> String {
+        let last_row = cmp::min(end.0, self.number_of_rows().saturating_sub(1));
+        let mut text = String::new();
+        for row_index in start.0..=last_row {
+            let row = self.get_editor_row(row_index);
+            let from = if row_index == start.0 { start.1 } else { 0 };
+            let to = if row_index == end.0 { end.1 } else { row.char_count() };
+            text.push_str(&row.row_content[row.byte_index(from)..row.byte_index(cmp::max(from, to))]);
+            if row_index < end.0 {
+                text.push('\n');
+            }
+        }
+        text
+    }
+
+    /// Removes the text between two positions, joining the rows at either end.
+    /// 
+    /// Positions are `(row, column)` pairs of character indices, as in
+    /// `text_between`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `start` - The first position (inclusive)
+    /// * `end` - The last position (exclusive); must not come before `start`
+    /// 
+    /// # Returns
+    /// 
+    /// Returns the removed text.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::screens::editor::EditorRows;
+    /// 
+    /// let mut editor_rows = EditorRows::from_bytes("héllo\nwörld\n".as_bytes(), &None);
+    /// assert_eq!(editor_rows.delete_between((0, 2), (1, 1)), "llo\nw");
+    /// assert_eq!(editor_rows.get_row(0), "héörld");
+    /// assert_eq!(editor_rows.number_of_rows(), 1);
+    /// ```
+    pub fn delete_between(&mut self, start: (usize, usize), end: (usize, usize)) -> String {
+        if start.0 >= self.number_of_rows() {
+            return String::new();
+        }
+        let removed = self.text_between(start, end);
+        let last_row = cmp::min(end.0, self.number_of_rows() - 1);
+        let tail = {
+            let row = self.get_editor_row(last_row);
+            let to = if last_row == end.0 { cmp::min(end.1, row.char_count()) } else { row.char_count() };
+            row.row_content[row.byte_index(to)..].to_string()
+        };
+        self.row_contents.drain(start.0 + 1..=last_row);
+        let row = self.get_editor_row_mut(start.0);
+        let from = row.byte_index(start.1);
+        row.row_content.truncate(from);
+        row.row_content.push_str(&tail);
+        Self::render_row(row);
+        removed
+    }
 }
 
 /// Manages the content buffer for terminal output rendering.
@@ -1070,6 +1486,34 @@ pub struct Editor {
     quit_attempts: u8,
     /// Manages and resolves keybindings.
     keybind_manager: KeybindManager,
+    /// Crash-recovery swap file for the current buffer.
+    swap: SwapFile,
+    /// Dirty counter observed on the last idle tick.
+    last_dirty: u64,
+    /// When the buffer last changed, as observed by idle ticks.
+    last_change: Instant,
+    /// Dirty counter at the last failed auto-save (0 if none).
+    auto_save_failed_at: u64,
+    /// Files already backed up during this session.
+    backed_up: HashSet<PathBuf>,
+    /// Open buffers other than the current one, in switching order.
+    background: VecDeque<BackgroundBuffer>,
+    /// Position of the current buffer among all open buffers.
+    buffer_index: usize,
+    /// Whether saves are collected for standard output (`--pipe`).
+    pipe: bool,
+    /// Contents of the last save in pipe mode.
+    pipe_output: Option<Vec<u8>>,
+    /// Whether the less-like viewer keys are active (`ninja view`).
+    view: bool,
+}
+
+/// An open buffer that is not currently shown.
+struct BackgroundBuffer {
+    /// The buffer and its view state.
+    output: Output,
+    /// Crash-recovery swap file for the buffer.
+    swap: SwapFile,
 }
 
 impl Editor {
@@ -1096,15 +1540,904 @@ impl Editor {
     /// let mut editor = Editor::new(config);
     /// ```
     pub fn new(config: NinjaConfig) -> Self {
+        let output = Output::new(config.clone());
+        Self::with_buffers(config, vec![output])
+    }
+
+    /// Creates a new editor instance with several open buffers.
+    /// 
+    /// The first buffer is shown; the others can be switched to with the
+    /// `next_buffer` and `previous_buffer` actions. Quitting closes the
+    /// current buffer, and the editor exits once no buffers remain.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `config` - The editor's configuration settings.
+    /// * `buffers` - The buffers to open, in order. An empty list opens an untitled buffer.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns a new `Editor` instance.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::screens::editor::{Editor, EditorRows};
+    /// use ninja::transput::transput::Output;
+    /// use ninja::config::NinjaConfig;
+    /// 
+    /// let config = NinjaConfig::default();
+    /// let buffers = vec![
+    ///     Output::with_rows(config.clone(), EditorRows::from_bytes(b"first\n", &None), None),
+    ///     Output::with_rows(config.clone(), EditorRows::from_bytes(b"second\n", &None), None),
+    /// ];
+    /// let editor = Editor::with_buffers(config.clone(), buffers);
+    /// assert_eq!(editor.buffer_count(), 2);
+    /// assert_eq!(editor.output.editor_rows.get_row(0), "first");
+    /// 
+    /// // Without buffers, an untitled one is opened
+    /// let editor = Editor::with_buffers(config, Vec::new());
+    /// assert_eq!(editor.buffer_count(), 1);
+    /// assert!(editor.output.editor_rows.filename.is_none());
+    /// ```
+    pub fn with_buffers(config: NinjaConfig, buffers: Vec<Output>) -> Self {
+        let mut background: VecDeque<BackgroundBuffer> = buffers
+            .into_iter()
+            .map(|output| BackgroundBuffer {
+                output,
+                swap: SwapFile::new(),
+            })
+            .collect();
+        let current = background.pop_front().unwrap_or_else(|| BackgroundBuffer {
+            output: Output::new(config.clone()),
+            swap: SwapFile::new(),
+        });
         Self {
             reader: Reader,
-            output: Output::new(config.clone()),
+            output: current.output,
             config,
             quit_attempts: 0,
             keybind_manager: KeybindManager::new(),
+            swap: current.swap,
+            last_dirty: 0,
+            last_change: Instant::now(),
+            auto_save_failed_at: 0,
+            backed_up: HashSet::new(),
+            background,
+            buffer_index: 0,
+            pipe: false,
+            pipe_output: None,
+            view: false,
+        }
+    }
+
+    /// Turns on view mode, used by `ninja view`.
+    /// 
+    /// Every buffer becomes read-only, and keybinds in the `View` context
+    /// (Space, `b`, `/`, `q`, ...) take precedence over the editor keybinds.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::screens::editor::Editor;
+    /// use ninja::config::NinjaConfig;
+    /// 
+    /// let mut editor = Editor::new(NinjaConfig::default());
+    /// editor.enable_view_mode();
+    /// assert!(editor.output.read_only);
+    /// ```
+    pub fn enable_view_mode(&mut self) {
+        self.view = true;
+        self.output.read_only = true;
+        for buffer in &mut self.background {
+            buffer.output.read_only = true;
+        }
+        self.output.status_message.set_message(
+            "VIEW: Space/b = Page down/up | / = Search | q = Quit | Alt-R = Allow editing".into(),
+        );
+    }
+
+    /// Turns on pipe mode, used by `--pipe`.
+    /// 
+    /// Saving an untitled buffer no longer asks for a filename; instead the
+    /// saved contents are kept and can be taken with `take_pipe_output` once
+    /// the editor has exited. Buffers with a filename are saved as usual and
+    /// their saved contents are kept as well.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::screens::editor::Editor;
+    /// use ninja::config::NinjaConfig;
+    /// 
+    /// fn filter(config: NinjaConfig) -> Option<Vec<u8>> {
+    ///     let mut editor = Editor::new(config);
+    ///     editor.enable_pipe();
+    ///     while editor.run().ok()? {}
+    ///     editor.take_pipe_output()
+    /// }
+    /// ```
+    pub fn enable_pipe(&mut self) {
+        self.pipe = true;
+        self.output.status_message.set_message(
+            "PIPE: Ctrl-S = Keep buffer for standard output | Ctrl-Q = Quit and write it".into(),
+        );
+    }
+
+    /// Takes the contents saved in pipe mode.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns the encoded buffer as of the last save, or `None` if pipe mode
+    /// is off or the buffer was never saved.
+    pub fn take_pipe_output(&mut self) -> Option<Vec<u8>> {
+        self.pipe_output.take()
+    }
+
+    /// Returns the number of open buffers, including the current one.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::screens::editor::Editor;
+    /// use ninja::transput::transput::Output;
+    /// use ninja::config::NinjaConfig;
+    /// 
+    /// let config = NinjaConfig::default();
+    /// let buffers = (0..3).map(|_| Output::new(config.clone())).collect();
+    /// let editor = Editor::with_buffers(config, buffers);
+    /// assert_eq!(editor.buffer_count(), 3);
+    /// ```
+    pub fn buffer_count(&self) -> usize {
+        self.background.len() + 1
+    }
+
+    /// Makes the next (or previous) buffer the current one.
+    fn rotate_buffers(&mut self, forward: bool) {
+        let next = if forward {
+            self.background.pop_front()
+        } else {
+            self.background.pop_back()
+        };
+        let Some(next) = next else {
+            return;
+        };
+        let previous = BackgroundBuffer {
+            output: mem::replace(&mut self.output, next.output),
+            swap: mem::replace(&mut self.swap, next.swap),
+        };
+        let count = self.buffer_count();
+        if forward {
+            self.background.push_back(previous);
+            self.buffer_index = (self.buffer_index + 1) % count;
+        } else {
+            self.background.push_front(previous);
+            self.buffer_index = (self.buffer_index + count - 1) % count;
+        }
+        self.reset_buffer_state();
+    }
+
+    /// Resets the idle bookkeeping after the current buffer was replaced.
+    fn reset_buffer_state(&mut self) {
+        self.quit_attempts = 0;
+        self.last_dirty = self.output.dirty;
+        self.last_change = Instant::now();
+        self.auto_save_failed_at = 0;
+    }
+
+    /// Switches to the next (or previous) buffer and reports which one is shown.
+    fn switch_buffer(&mut self, forward: bool) {
+        if self.background.is_empty() {
+            self.output
+                .status_message
+                .set_message("No other buffers open".into());
+            return;
+        }
+        self.on_buffer_switch();
+        self.rotate_buffers(forward);
+        self.announce_buffer("Buffer");
+    }
+
+    /// Closes the current buffer and shows the next one.
+    /// 
+    /// The swap file of a clean buffer is removed. A buffer closed with unsaved
+    /// changes keeps its swap file, brought up to date first, so the changes
+    /// can still be recovered.
+    /// 
+    /// Returns `false` if it was the last open buffer.
+    fn close_buffer(&mut self) -> bool {
+        if self.output.dirty == 0 {
+            self.swap.remove();
+        } else if self.config.behavior.swap_files {
+            let _ = self.swap.write(&self.output.editor_rows);
+        }
+        let Some(next) = self.background.pop_front() else {
+            return false;
+        };
+        self.output = next.output;
+        self.swap = next.swap;
+        if self.buffer_index >= self.buffer_count() {
+            self.buffer_index = 0;
+        }
+        self.reset_buffer_state();
+        self.announce_buffer("Buffer closed; now showing");
+        true
+    }
+
+    /// Shows the position and name of the current buffer in the message bar.
+    fn announce_buffer(&mut self, prefix: &str) {
+        let name = self
+            .output
+            .editor_rows
+            .filename
+            .as_ref()
+            .map(|filename| filename.display().to_string())
+            .unwrap_or_else(|| "[No Name]".into());
+        self.output.status_message.set_message(format!(
+            "{} {}/{}: {}",
+            prefix,
+            self.buffer_index + 1,
+            self.buffer_count(),
+            name
+        ));
+    }
+
+    /// Offers to recover unsaved changes from a swap file left by a previous session.
+    /// 
+    /// If a swap file exists for the opened file and its contents differ from
+    /// the file on disk, the user can recover the swapped contents, view a diff
+    /// against the file on disk, or discard the swap file. Swap files whose
+    /// contents match the file on disk are removed silently.
+    /// 
+    /// A swap file owned by another Ninja that is still running belongs to a
+    /// buffer being edited right now, so it is left alone and the buffer is
+    /// opened read-only instead.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `Ok(())` once the user has made a choice, or a `crossterm::Error`.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::modules::swap::SwapFile;
+    /// use ninja::screens::editor::{Editor, EditorRows};
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let path = std::env::temp_dir().join("ninja-swap-example.txt");
+    /// std::fs::write(&path, "saved\n").unwrap();
+    /// let mut rows = EditorRows { filename: Some(path.clone()), ..Default::default() };
+    /// rows.load(None, &None).unwrap();
+    /// SwapFile::new().write(&rows).unwrap();
+    /// let config = NinjaConfig::default();
+    /// let output = Output::with_rows(config.clone(), rows, None);
+    /// let mut editor = Editor::with_buffers(config, vec![output]);
+    /// 
+    /// // The swap file holds nothing the file on disk lacks, so it goes away without asking
+    /// editor.check_swap_file().unwrap();
+    /// assert_eq!(SwapFile::read_existing(&path), None);
+    /// 
+    /// // A swap file written by a running process is kept, and the buffer is read-only
+    /// #[cfg(unix)]
+    /// {
+    ///     let mut other = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+    ///     let swap_path = SwapFile::path_for(Some(&path)).unwrap();
+    ///     let header = format!("NINJA-SWAP\t1\t{}\t{}\n", other.id(), path.display());
+    ///     std::fs::write(&swap_path, header + "unsaved\n").unwrap();
+    ///     editor.check_swap_file().unwrap();
+    ///     assert!(editor.output.read_only);
+    ///     assert_eq!(SwapFile::read_existing(&path).as_deref(), Some("unsaved\n"));
+    ///     other.kill().unwrap();
+    ///     other.wait().unwrap();
+    ///     std::fs::remove_file(swap_path).unwrap();
+    /// }
+    /// ```
+    pub fn check_swap_file(&mut self) -> crossterm::Result<()> {
+        if !self.config.behavior.swap_files {
+            return Ok(());
+        }
+        let Some(filename) = self.output.editor_rows.filename.clone() else {
+            return Ok(());
+        };
+        let Some(recovered) = SwapFile::read_existing(&filename) else {
+            return Ok(());
+        };
+        if let Some(pid) = SwapFile::live_owner(&filename) {
+            self.output.read_only = true;
+            self.output.status_message.set_message(format!(
+                "{} is being edited by another Ninja (pid {}); opened read-only (Alt-R = Allow editing)",
+                filename.display(),
+                pid
+            ));
+            return Ok(());
+        }
+        let on_disk = self.output.editor_rows.contents();
+        if recovered == on_disk {
+            SwapFile::discard_existing(&filename);
+            return Ok(());
+        }
+
+        let message = format!(
+            "Swap file found for {}: (r)ecover, (d)iff, discard (x)?",
+            filename.display()
+        );
+        loop {
+            match self.output.prompt_choice(&message, &['r', 'd', 'x'])? {
+                Some('r') => {
+                    self.output
+                        .editor_rows
+                        .replace_contents(&recovered, &self.output.syntax_highlight);
+                    self.output.dirty += 1;
+                    self.output
+                        .status_message
+                        .set_message("Recovered unsaved changes from swap file".into());
+                    return Ok(());
+                }
+                Some('d') => {
+                    let diff = unified_diff(&on_disk, &recovered, 3);
+                    self.output
+                        .show_lines("Diff: - on disk / + swap file", diff)?;
+                }
+                Some('x') => {
+                    SwapFile::discard_existing(&filename);
+                    self.output
+                        .status_message
+                        .set_message("Swap file discarded".into());
+                    return Ok(());
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Offers swap file recovery for every open buffer.
+    /// 
+    /// Each buffer is shown in turn while its swap file is checked, and the
+    /// first buffer is current again afterwards.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `Ok(())` once every buffer has been checked, or a `crossterm::Error`.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::modules::swap::SwapFile;
+    /// use ninja::screens::editor::{Editor, EditorRows};
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let config = NinjaConfig::default();
+    /// let mut buffers = Vec::new();
+    /// let mut paths = Vec::new();
+    /// for name in ["ninja-swaps-example-a.txt", "ninja-swaps-example-b.txt"] {
+    ///     let path = std::env::temp_dir().join(name);
+    ///     std::fs::write(&path, "saved\n").unwrap();
+    ///     let mut rows = EditorRows { filename: Some(path.clone()), ..Default::default() };
+    ///     rows.load(None, &None).unwrap();
+    ///     SwapFile::new().write(&rows).unwrap();
+    ///     buffers.push(Output::with_rows(config.clone(), rows, None));
+    ///     paths.push(path);
+    /// }
+    /// let mut editor = Editor::with_buffers(config, buffers);
+    /// 
+    /// // Both swap files match their files, so both are removed without asking
+    /// editor.check_swap_files().unwrap();
+    /// assert!(paths.iter().all(|path| SwapFile::read_existing(path).is_none()));
+    /// assert_eq!(editor.output.editor_rows.filename.as_ref(), Some(&paths[0]));
+    /// ```
+    pub fn check_swap_files(&mut self) -> crossterm::Result<()> {
+        for _ in 0..self.buffer_count() {
+            self.check_swap_file()?;
+            self.rotate_buffers(true);
+        }
+        Ok(())
+    }
+
+    /// Saves the buffer to its file and marks it clean.
+    /// 
+    /// If backups are enabled, the file on disk is backed up first the first
+    /// time it is saved in this session. The swap file is removed once the
+    /// buffer has been written. In pipe mode the saved contents are also kept
+    /// for standard output, and untitled buffers are only kept.
+    fn save_buffer(&mut self) -> io::Result<usize> {
+        self.backup_before_save()?;
+        let len = if self.pipe {
+            let contents = self.output.editor_rows.to_bytes()?;
+            if self.output.editor_rows.filename.is_some() {
+                self.output.editor_rows.save()?;
+            }
+            let len = contents.len();
+            self.pipe_output = Some(contents);
+            len
+        } else {
+            self.output.editor_rows.save()?
+        };
+        self.output.dirty = 0;
+        self.swap.remove();
+        Ok(len)
+    }
+
+    /// Backs up the file on disk if backups are enabled and it has not been
+    /// backed up during this session yet.
+    fn backup_before_save(&mut self) -> io::Result<()> {
+        if self.config.behavior.backup_files
+            && let Some(filename) = &self.output.editor_rows.filename
+            && !self.backed_up.contains(filename)
+        {
+            backup::create(filename, &self.config.behavior).map_err(|e| {
+                io::Error::new(e.kind(), format!("could not create backup: {}", e))
+            })?;
+            self.backed_up.insert(filename.clone());
+        }
+        Ok(())
+    }
+
+    /// Saves the buffer through the configured elevated save command.
+    /// 
+    /// Raw mode is left while the helper runs so that it can ask for a
+    /// password on the terminal. The helper overwrites the file in place, so
+    /// its permissions and owner are kept.
+    fn save_elevated(&mut self) -> io::Result<usize> {
+        let filename = self
+            .output
+            .editor_rows
+            .filename
+            .clone()
+            .ok_or_else(|| io::Error::other("no file name specified"))?;
+        let contents = self.output.editor_rows.to_bytes()?;
+        self.backup_before_save()?;
+
+        let command = self.config.behavior.elevated_save_command.clone();
+        Output::clear_screen()?;
+        terminal::disable_raw_mode()?;
+        println!("Saving {} with: {}", filename.display(), command);
+        let written = file_io::write_elevated(&filename, &contents, &command);
+        terminal::enable_raw_mode()?;
+        written?;
+
+        self.output.editor_rows.disk_state = fs::metadata(&filename)
+            .ok()
+            .map(|metadata| DiskState::new(&contents, &metadata));
+        let len = contents.len();
+        if self.pipe {
+            self.pipe_output = Some(contents);
+        }
+        self.output.dirty = 0;
+        self.swap.remove();
+        Ok(len)
+    }
+
+    /// Returns the program of the elevated save command, if one is configured.
+    fn elevated_save_program(&self) -> Option<String> {
+        self.config
+            .behavior
+            .elevated_save_command
+            .split_whitespace()
+            .next()
+            .map(String::from)
+    }
+
+    /// Saves the buffer if auto-save is enabled and there is something to save.
+    /// 
+    /// Untitled buffers, read-only files and files changed by another program
+    /// are skipped. Write errors are reported in the message bar instead of
+    /// interrupting the user, and the same unsaved state is not retried until
+    /// the buffer changes again.
+    /// 
+    /// Returns `true` if the screen needs a refresh.
+    fn auto_save(&mut self) -> bool {
+        if !self.config.behavior.auto_save
+            || self.output.dirty == 0
+            || self.output.dirty == self.auto_save_failed_at
+            || self.output.editor_rows.filename.is_none()
+            || self.output.read_only
+        {
+            return false;
+        }
+        // Leave changes made by another program for the user to resolve
+        if self.config.behavior.detect_external_changes
+            && matches!(self.output.editor_rows.disk_change(), DiskChange::Modified(_))
+        {
+            return false;
+        }
+        let result = match &self.output.editor_rows.filename {
+            Some(filename) if file_io::is_read_only(filename) => Err(io::Error::new(
+                ErrorKind::PermissionDenied,
+                "file is read-only",
+            )),
+            _ => self.save_buffer(),
+        };
+        if let Err(e) = result {
+            self.auto_save_failed_at = self.output.dirty;
+            self.output
+                .status_message
+                .set_message(format!("Auto-save failed: {}", e));
+        }
+        true
+    }
+
+    /// Auto-saves the current buffer before another buffer becomes active.
+    /// 
+    /// This does nothing unless both `auto_save` and
+    /// `auto_save_on_buffer_switch` are enabled in the configuration.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::screens::editor::{Editor, EditorRows};
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let path = std::env::temp_dir().join("ninja-switch-example.txt");
+    /// std::fs::write(&path, "one\n").unwrap();
+    /// let mut rows = EditorRows { filename: Some(path.clone()), ..Default::default() };
+    /// rows.load(None, &None).unwrap();
+    /// let mut config = NinjaConfig::default();
+    /// config.behavior.auto_save = true;
+    /// let output = Output::with_rows(config.clone(), rows, None);
+    /// let mut editor = Editor::with_buffers(config, vec![output]);
+    /// 
+    /// editor.output.insert_char('1');
+    /// editor.on_buffer_switch();
+    /// assert_eq!(editor.output.dirty, 0);
+    /// assert_eq!(std::fs::read_to_string(&path).unwrap(), "1one\n");
+    /// ```
+    pub fn on_buffer_switch(&mut self) {
+        if self.config.behavior.auto_save_on_buffer_switch {
+            self.auto_save();
+        }
+    }
+
+    /// Waits for the next key event, handling other events and idle work meanwhile.
+    fn read_key_or_idle(&mut self) -> crossterm::Result<KeyEvent> {
+        loop {
+            let refresh = match self.reader.poll_event(Duration::from_millis(500))? {
+                Some(Event::Key(key_event)) => return Ok(key_event),
+                Some(Event::FocusLost) if self.config.behavior.auto_save_on_focus_loss => {
+                    self.auto_save()
+                }
+                Some(Event::FocusGained) => self.check_disk_changes()?,
+                Some(_) => false,
+                None => self.on_idle() | self.check_disk_changes()?,
+            };
+            if refresh {
+                self.output.refresh_screen()?;
+            }
+        }
+    }
+
+    /// Performs background work while the editor is idle.
+    /// 
+    /// Returns `true` if anything visible changed and the screen needs a refresh.
+    fn on_idle(&mut self) -> bool {
+        if self.output.dirty != self.last_dirty {
+            self.last_dirty = self.output.dirty;
+            self.last_change = Instant::now();
+        }
+        let delay = self.config.behavior.auto_save_delay_secs;
+        if delay > 0 && self.last_change.elapsed() >= Duration::from_secs(delay) && self.auto_save() {
+            return true;
+        }
+
+        if !self.config.behavior.swap_files {
+            return false;
+        }
+        let idle = Duration::from_secs(self.config.behavior.swap_idle_secs);
+        match self.swap.tick(&self.output.editor_rows, self.output.dirty, idle) {
+            Ok(_) => false,
+            Err(e) => {
+                self.output
+                    .status_message
+                    .set_message(format!("Could not write swap file: {}", e));
+                true
+            }
+        }
+    }
+
+    /// Reacts to the open file being changed by another program.
+    /// 
+    /// Unmodified buffers are reloaded automatically when `auto_reload` is
+    /// enabled. Otherwise the user can reload the file, keep the buffer, or
+    /// view a diff first. A deleted file is reported once; saving recreates it.
+    /// 
+    /// Returns `true` if the screen needs a refresh.
+    fn check_disk_changes(&mut self) -> crossterm::Result<bool> {
+        if !self.config.behavior.detect_external_changes {
+            return Ok(false);
+        }
+        let Some(filename) = self.output.editor_rows.filename.clone() else {
+            return Ok(false);
+        };
+        match self.output.editor_rows.disk_change() {
+            DiskChange::Unchanged => Ok(false),
+            DiskChange::Touched(state) => {
+                self.output.editor_rows.disk_state = Some(state);
+                Ok(false)
+            }
+            DiskChange::Deleted => {
+                self.output.editor_rows.disk_state = None;
+                self.output.status_message.set_message(format!(
+                    "{} was deleted on disk; saving will recreate it",
+                    filename.display()
+                ));
+                Ok(true)
+            }
+            DiskChange::Modified(state) => {
+                if self.output.dirty == 0 && self.config.behavior.auto_reload {
+                    self.reload_from_disk();
+                    return Ok(true);
+                }
+                let message = format!(
+                    "{} changed on disk: (r)eload, (k)eep mine, (d)iff?",
+                    filename.display()
+                );
+                loop {
+                    match self.output.prompt_choice(&message, &['r', 'k', 'd'])? {
+                        Some('r') => {
+                            self.reload_from_disk();
+                            break;
+                        }
+                        Some('d') => {
+                            let diff = self.disk_diff();
+                            self.output
+                                .show_lines("Diff: - on disk / + buffer", diff)?;
+                        }
+                        _ => {
+                            // Remember the new state so the same change is not reported again
+                            self.output.editor_rows.disk_state = Some(state);
+                            if self.output.dirty == 0 {
+                                self.output.dirty += 1;
+                            }
+                            self.output
+                                .status_message
+                                .set_message("Kept buffer; saving will overwrite the changes on disk".into());
+                            break;
+                        }
+                    }
+                }
+                Ok(true)
+            }
+        }
+    }
+
+    /// Reloads the buffer from disk and reports the outcome.
+    fn reload_from_disk(&mut self) {
+        let message = match self.output.reload_from_disk() {
+            Ok(()) => "File changed on disk: reloaded (Ctrl-Z to undo)".to_string(),
+            Err(e) => format!("Could not reload file: {}", e),
+        };
+        self.output.status_message.set_message(message);
+    }
+
+    /// Returns a unified diff from the file on disk, decoded like the buffer,
+    /// to the buffer.
+    fn disk_diff(&self) -> Vec<String> {
+        let encoding = self.output.editor_rows.format.encoding;
+        let on_disk = self
+            .output
+            .editor_rows
+            .filename
+            .as_ref()
+            .and_then(|filename| fs::read(filename).ok())
+            .map(|bytes| FileFormat::from_bytes(&bytes, Some(encoding)).1.join("\n"))
+            .unwrap_or_default();
+        let buffer = self
+            .output
+            .editor_rows
+            .row_contents
+            .iter()
+            .map(|row| row.row_content.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+        unified_diff(&on_disk, &buffer, 3)
+    }
+
+    /// Asks before a save overwrites changes another program made to the file.
+    /// 
+    /// Returns `Ok(true)` if the save should go ahead.
+    fn confirm_overwrite_external_change(&mut self) -> crossterm::Result<bool> {
+        if !self.config.behavior.detect_external_changes {
+            return Ok(true);
+        }
+        let Some(filename) = self.output.editor_rows.filename.clone() else {
+            return Ok(true);
+        };
+        match self.output.editor_rows.disk_change() {
+            DiskChange::Modified(_) => {}
+            DiskChange::Touched(state) => {
+                self.output.editor_rows.disk_state = Some(state);
+                return Ok(true);
+            }
+            DiskChange::Unchanged | DiskChange::Deleted => return Ok(true),
+        }
+        let message = format!(
+            "{} changed on disk since it was opened: (o)verwrite, (d)iff, cancel (c)?",
+            filename.display()
+        );
+        loop {
+            match self.output.prompt_choice(&message, &['o', 'd', 'c'])? {
+                Some('o') => return Ok(true),
+                Some('d') => {
+                    let diff = self.disk_diff();
+                    self.output
+                        .show_lines("Diff: - on disk / + buffer", diff)?;
+                }
+                _ => return Ok(false),
+            }
         }
     }
 
+    /// Saves the buffer, asking for a filename first if it has none.
+    /// 
+    /// The user is asked to confirm before a read-only file is overwritten,
+    /// and before overwriting changes another program made to the file.
+    /// Files the user may not write can be saved through the configured
+    /// elevated save command (`sudo tee` by default).
+    /// The outcome is reported in the status bar.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `Ok(true)` if the file was written, `Ok(false)` if the save was
+    /// aborted or failed, or a `crossterm::Error` if prompting failed.
+    fn save(&mut self) -> crossterm::Result<bool> {
+        if !self.output.ensure_writable() {
+            return Ok(false);
+        }
+        if self.pipe && self.output.editor_rows.filename.is_none() {
+            let (message, saved) = match self.save_buffer() {
+                Ok(len) => (format!("{} bytes will be written to standard output on quit", len), true),
+                Err(e) => (format!("Save failed: {}", e), false),
+            };
+            self.output.status_message.set_message(message);
+            return Ok(saved);
+        }
+        if matches!(self.output.editor_rows.filename, None) {
+            let prompt = prompt!(&mut self.output, "Save as : {} (ESC to cancel)")
+                .map(|it| it.into());
+            if prompt.is_none() {
+                self.output
+                    .status_message
+                    .set_message("Save Aborted".into());
+                return Ok(false);
+            }
+            prompt
+                .as_ref()
+                .and_then(|path: &PathBuf| path.extension())
+                .and_then(|ext| ext.to_str())
+                .map(|ext| {
+                    Output::select_syntax(ext).map(|syntax| {
+                        let highlight = self.output.syntax_highlight.insert(syntax);
+                        for i in 0..self.output.editor_rows.number_of_rows() {
+                            highlight
+                                .update_syntax(i, &mut self.output.editor_rows.row_contents)
+                        }
+                    })
+                });
+            self.output.editor_rows.filename = prompt;
+        }
+        let mut elevated = false;
+        if let Some(filename) = &self.output.editor_rows.filename
+            && file_io::is_read_only(filename)
+        {
+            let choice = match self.elevated_save_program() {
+                Some(program) => {
+                    let message = format!(
+                        "{} is read-only: (o)verwrite, (e)levated save with {}, (c)ancel?",
+                        filename.display(),
+                        program
+                    );
+                    self.output.prompt_choice(&message, &['o', 'e', 'c'])?
+                }
+                None => {
+                    let message = format!(
+                        "{} is read-only. Overwrite anyway? (y/n)",
+                        filename.display()
+                    );
+                    self.output
+                        .prompt_choice(&message, &['y', 'n'])?
+                        .filter(|&choice| choice == 'y')
+                        .map(|_| 'o')
+                }
+            };
+            match choice {
+                Some('o') => {}
+                Some('e') => elevated = true,
+                _ => {
+                    self.output
+                        .status_message
+                        .set_message("Save Aborted".into());
+                    return Ok(false);
+                }
+            }
+        }
+        if !self.confirm_overwrite_external_change()? {
+            self.output
+                .status_message
+                .set_message("Save Aborted".into());
+            return Ok(false);
+        }
+        let mut result = if elevated {
+            self.save_elevated()
+        } else {
+            self.save_buffer()
+        };
+        if let Err(e) = &result
+            && e.kind() == ErrorKind::PermissionDenied
+            && !elevated
+            && let Some(program) = self.elevated_save_program()
+        {
+            let message = format!(
+                "Save failed: {}: (e)levated save with {}, (c)ancel?",
+                e, program
+            );
+            if self.output.prompt_choice(&message, &['e', 'c'])? == Some('e') {
+                elevated = true;
+                result = self.save_elevated();
+            }
+        }
+        match result {
+            Ok(len) => {
+                let how = if elevated { " with elevated privileges" } else { "" };
+                self.output
+                    .status_message
+                    .set_message(format!("{} bytes written to disk{}", len, how));
+                Ok(true)
+            }
+            Err(e) => {
+                self.output
+                    .status_message
+                    .set_message(format!("Save failed: {}", e));
+                Ok(false)
+            }
+        }
+    }
+
+    /// Lets the user pick an encoding and saves the buffer with it.
+    /// 
+    /// The buffer is checked before anything is written, so a character the
+    /// chosen encoding cannot represent aborts the save with its position.
+    /// UTF-16 files are always saved with a byte order mark. If the save
+    /// fails or is aborted, the previous encoding is kept.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `Ok(())` once the save has finished or been aborted, or a
+    /// `crossterm::Error` if prompting failed.
+    fn save_with_encoding(&mut self) -> crossterm::Result<()> {
+        let format = self.output.editor_rows.format;
+        let labels = FileEncoding::ALL
+            .iter()
+            .map(|encoding| encoding.name().to_string())
+            .collect();
+        let title = format!("Save with encoding (currently {})", format.encoding.name());
+        let Some(index) = self.output.pick_line(&title, labels)? else {
+            self.output.status_message.set_message("Save Aborted".into());
+            return Ok(());
+        };
+        let encoding = FileEncoding::ALL[index];
+        if let Err(e) = encoding.encode(&self.output.editor_rows.contents()) {
+            self.output
+                .status_message
+                .set_message(format!("Cannot save as {}: {}", encoding.name(), e));
+            return Ok(());
+        }
+
+        let rows_format = &mut self.output.editor_rows.format;
+        rows_format.encoding = encoding;
+        rows_format.bom = match encoding {
+            FileEncoding::Utf16Le | FileEncoding::Utf16Be => true,
+            FileEncoding::Utf8 => format.bom && format.encoding == FileEncoding::Utf8,
+            _ => false,
+        };
+        rows_format.decode_errors = false;
+        if !self.save()? {
+            self.output.editor_rows.format = format;
+        }
+        Ok(())
+    }
+
     /// Processes a single keypress event.
     /// 
     /// This method reads a key event from the reader, finds the corresponding
@@ -1143,13 +2476,17 @@ impl Editor {
     /// assert_eq!(editor.output.editor_rows.row_contents[0].row_content, "a");
     /// ```
     pub fn process_keypress(&mut self) -> crossterm::Result<bool> {
-        let key_event = self.reader.read_key()?;
+        let key_event = self.read_key_or_idle()?;
         
         // Define the contexts to check in order of priority
-        let contexts = [KeybindContext::Global, KeybindContext::Editor];
+        let contexts: &[KeybindContext] = if self.view {
+            &[KeybindContext::View, KeybindContext::Global, KeybindContext::Editor]
+        } else {
+            &[KeybindContext::Global, KeybindContext::Editor]
+        };
         
         // Try to find a keybind for this event
-        if let Some(keybind) = self.keybind_manager.find_keybind_in_contexts(&key_event, &contexts) {
+        if let Some(keybind) = self.keybind_manager.find_keybind_in_contexts(&key_event, contexts) {
             // Get the action for this keybind
             if let Some(action) = self.keybind_manager.get_action(&keybind.action) {
                 // Handle special cases that need custom logic
@@ -1164,40 +2501,22 @@ impl Editor {
                             ));
                             return Ok(true);
                         }
-                        Ok(false)
+                        Ok(self.close_buffer())
+                    }
+                    crate::keybinds::actions::Action::NextBuffer => {
+                        self.switch_buffer(true);
+                        Ok(true)
+                    }
+                    crate::keybinds::actions::Action::PreviousBuffer => {
+                        self.switch_buffer(false);
+                        Ok(true)
                     }
                     crate::keybinds::actions::Action::Save => {
-                        // Handle save with custom logic
-                        if matches!(self.output.editor_rows.filename, None) {
-                            let prompt = prompt!(&mut self.output, "Save as : {} (ESC to cancel)")
-                                .map(|it| it.into());
-                            if prompt.is_none() {
-                                self.output
-                                    .status_message
-                                    .set_message("Save Aborted".into());
-                                return Ok(true);
-                            }
-                            prompt
-                                .as_ref()
-                                .and_then(|path: &PathBuf| path.extension())
-                                .and_then(|ext| ext.to_str())
-                                .map(|ext| {
-                                    Output::select_syntax(ext).map(|syntax| {
-                                        let highlight = self.output.syntax_highlight.insert(syntax);
-                                        for i in 0..self.output.editor_rows.number_of_rows() {
-                                            highlight
-                                                .update_syntax(i, &mut self.output.editor_rows.row_contents)
-                                        }
-                                    })
-                                });
-                            self.output.editor_rows.filename = prompt;
-                        }
-                        self.output.editor_rows.save().map(|len| {
-                            self.output
-                                .status_message
-                                .set_message(format!("{} bytes written to disk", len));
-                            self.output.dirty = 0
-                        })?;
+                        self.save()?;
+                        Ok(true)
+                    }
+                    crate::keybinds::actions::Action::SaveWithEncoding => {
+                        self.save_with_encoding()?;
                         Ok(true)
                     }
                     crate::keybinds::actions::Action::MoveCursor(direction) => {
@@ -1213,20 +2532,28 @@ impl Editor {
                         }
                         Ok(true)
                     }
-                    crate::keybinds::actions::Action::InsertChar(ch) => {
-                        // Handle character insertion with selection clearing
-                        if self.output.is_selecting() {
-                            self.output.clear_selection();
+                    crate::keybinds::actions::Action::Motion(motion) => {
+                        // Shift extends the selection, as for single-step movement
+                        if key_event.modifiers.contains(KeyModifiers::SHIFT) {
+                            if !self.output.is_selecting() {
+                                self.output.start_selection();
+                            }
+                            self.output.apply_motion(*motion);
+                            self.output.update_selection();
+                        } else {
+                            self.output.apply_motion(*motion);
                         }
-                        if *ch == ' ' && key_event.code == KeyCode::Tab {
-                            // Handle tab insertion with soft tabs
-                            let tab_size = if self.config.editor.soft_tabs {
-                                self.config.editor.tab_size
-                            } else {
-                                1
-                            };
-                            for _ in 0..tab_size {
-                                self.output.insert_char(' ');
+                        Ok(true)
+                    }
+                    crate::keybinds::actions::Action::InsertChar(ch) => {
+                        // Typing replaces any selection
+                        if *ch == ' ' && key_event.code == KeyCode::Tab && self.output.has_any_selection() {
+                            // Tab with a selection indents the selected lines instead
+                            self.output.indent_lines();
+                        } else if *ch == ' ' && key_event.code == KeyCode::Tab {
+                            // Insert one level of the buffer's indentation
+                            for ch in self.output.indent_unit().chars() {
+                                self.output.insert_char(ch);
                             }
                         } else {
                             self.output.insert_char(*ch);
@@ -1234,15 +2561,16 @@ impl Editor {
                         Ok(true)
                     }
                     crate::keybinds::actions::Action::InsertNewline => {
-                        if self.output.is_selecting() {
-                            self.output.clear_selection();
-                        }
                         self.output.insert_newline();
                         Ok(true)
                     }
                     crate::keybinds::actions::Action::DeleteChar => {
-                        // Handle delete with cursor movement for Delete key
-                        if key_event.code == KeyCode::Delete {
+                        // Handle delete with cursor movement for Delete key; a selection is
+                        // removed the same way by either key
+                        if key_event.code == KeyCode::Delete
+                            && !self.output.has_any_selection()
+                            && self.output.ensure_writable()
+                        {
                             self.output.move_cursor(KeyCode::Right);
                         }
                         self.output.delete_char();
@@ -1267,10 +2595,8 @@ impl Editor {
             KeyEvent {
                 code: KeyCode::Char(ch),
                 modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+                ..
             } => {
-                if self.output.is_selecting() {
-                    self.output.clear_selection();
-                }
                 self.output.insert_char(ch);
             }
             _ => {
@@ -0,0 +1,707 @@
+//! # Hex Editor Screen Module
+//!
+//! This module provides the hex editor screen of the Ninja editor. Binary
+//! files are opened here instead of in the text editor, so that their bytes
+//! are shown and saved exactly as they are on disk.
+//!
+//! ## Features
+//!
+//! - **Hex View**: Offset, hex byte and ASCII columns, adapting to the terminal width
+//! - **Nibble Cursor**: The cursor moves by half-byte in the hex column
+//! - **Overwrite Editing**: Type hex digits, or characters in the ASCII column
+//! - **Pattern Search**: Find byte sequences such as `de ad be ef`
+//! - **Go To Offset**: Jump to a hexadecimal or decimal offset
+//! - **Byte-Exact Save**: The buffer is written back without any decoding
+//!
+//! ## Keys
+//!
+//! - **Arrows / Page Up / Page Down / Home / End**: Move the cursor
+//! - **Tab**: Switch between the hex and ASCII columns
+//! - **Ctrl-F**: Search for a hex pattern (an empty pattern repeats the last search)
+//! - **Ctrl-G**: Go to an offset
+//! - **Ctrl-S**: Save
+//! - **Ctrl-Q**: Quit
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use ninja::screens::hex::{is_binary, HexEditor};
+//!
+//! assert!(is_binary(b"\x7fELF\x02\x01\x01\x00"));
+//! assert!(!is_binary(b"plain text"));
+//! assert_eq!(HexEditor::parse_pattern("de ad BEEF"), Some(vec![0xDE, 0xAD, 0xBE, 0xEF]));
+//! ```
+
+use crate::config::NinjaConfig;
+use crate::modules::backup;
+use crate::modules::encoding::FileEncoding;
+use crate::modules::file_io;
+use crate::modules::message_bar::MessageBar;
+use crate::modules::status::StatusMessage;
+use crate::screens::editor::EditorContents;
+use crate::transput::transput::Reader;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::{cursor, queue, style, terminal};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::{cmp, fs};
+
+/// Number of leading bytes inspected when deciding whether a file is binary.
+const BINARY_SNIFF_LEN: usize = 8192;
+
+/// Width of the offset column, including the gap after it.
+const OFFSET_WIDTH: usize = 10;
+
+/// Returns `true` if the contents look like binary data rather than text.
+///
+/// A file is considered binary if its first 8 KiB contain a NUL byte that
+/// is not explained by the file being UTF-16 text. Without a byte order
+/// mark, UTF-16 is only assumed if the bytes decode to text free of
+/// control characters.
+///
+/// # Arguments
+///
+/// * `bytes` - The raw file contents
+///
+/// # Returns
+///
+/// Returns `true` for binary contents, `false` for text.
+///
+/// # Example
+///
+/// ```rust
+/// use ninja::screens::hex::is_binary;
+///
+/// assert!(is_binary(&[0x00, 0x01, 0x02, 0xFF]));
+/// assert!(!is_binary(b"\xFF\xFEh\0i\0"));
+/// assert!(!is_binary(b"h\0i\0\n\0"));
+/// ```
+pub fn is_binary(bytes: &[u8]) -> bool {
+    let head = &bytes[..cmp::min(bytes.len(), BINARY_SNIFF_LEN)];
+    if !head.contains(&0) {
+        return false;
+    }
+    match FileEncoding::detect(head) {
+        (FileEncoding::Utf16Le | FileEncoding::Utf16Be, true) => false,
+        (encoding @ (FileEncoding::Utf16Le | FileEncoding::Utf16Be), false) => encoding
+            .decode(head)
+            .0
+            .chars()
+            .any(|ch| ch.is_control() && !matches!(ch, '\t' | '\n' | '\r' | '\x0c')),
+        _ => true,
+    }
+}
+
+/// The column of the hex view that receives typed input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pane {
+    /// Hex digits overwrite the nibble under the cursor
+    Hex,
+    /// Printable characters overwrite the byte under the cursor
+    Ascii,
+}
+
+/// The hex editor screen for binary files.
+///
+/// The whole file is held in memory as raw bytes. Edits overwrite bytes in
+/// place; the file length never changes.
+///
+/// # Example
+///
+/// ```rust
+/// use ninja::config::NinjaConfig;
+/// use ninja::screens::hex::HexEditor;
+///
+/// let path = std::env::temp_dir().join("ninja-hex-example.bin");
+/// std::fs::write(&path, [0x7f, b'E', b'L', b'F', 0x00, 0x01]).unwrap();
+/// assert!(HexEditor::open(path.clone(), NinjaConfig::default()).is_ok());
+///
+/// // The file is read up front, so a missing file is reported right away
+/// std::fs::remove_file(&path).unwrap();
+/// assert!(HexEditor::open(path, NinjaConfig::default()).is_err());
+/// ```
+pub struct HexEditor {
+    /// Reader for keyboard input
+    reader: Reader,
+    /// Editor configuration
+    config: NinjaConfig,
+    /// The file being edited
+    path: PathBuf,
+    /// The file contents
+    data: Vec<u8>,
+    /// Offset of the byte under the cursor
+    cursor: usize,
+    /// Whether the cursor is on the low nibble of its byte
+    low_nibble: bool,
+    /// Column receiving typed input
+    pane: Pane,
+    /// Index of the first visible row
+    row_offset: usize,
+    /// Terminal size (columns, rows available for the hex view)
+    win_size: (usize, usize),
+    /// Whether the buffer differs from the file on disk
+    dirty: bool,
+    /// Offsets of the bytes changed since the last save
+    modified: Vec<bool>,
+    /// Number of quit attempts made with unsaved changes
+    quit_attempts: u8,
+    /// The most recent search pattern
+    last_search: Option<Vec<u8>>,
+    /// Whether a backup has been taken during this session
+    backed_up: bool,
+    /// Message bar contents
+    status_message: StatusMessage,
+    /// Output buffer for a screen refresh
+    editor_contents: EditorContents,
+}
+
+impl HexEditor {
+    /// Opens a file in the hex editor.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file to edit
+    /// * `config` - The editor's configuration settings
+    ///
+    /// # Returns
+    ///
+    /// Returns the new `HexEditor`, or an `io::Error` if the file could not be read.
+    pub fn open(path: PathBuf, config: NinjaConfig) -> io::Result<Self> {
+        let data = fs::read(&path)?;
+        let win_size = terminal::size()
+            .map(|(x, y)| (x as usize, (y as usize).saturating_sub(2)))
+            .unwrap_or((80, 22));
+        Ok(Self {
+            reader: Reader,
+            config,
+            path,
+            modified: vec![false; data.len()],
+            data,
+            cursor: 0,
+            low_nibble: false,
+            pane: Pane::Hex,
+            row_offset: 0,
+            win_size,
+            dirty: false,
+            quit_attempts: 0,
+            last_search: None,
+            backed_up: false,
+            status_message: StatusMessage::new(
+                "HELP: Ctrl-S = Save | Ctrl-Q = Quit | Ctrl-F = Find hex | Ctrl-G = Go to | Tab = Hex/ASCII".into(),
+            ),
+            editor_contents: EditorContents::new(),
+        })
+    }
+
+    /// Parses a hex byte pattern.
+    ///
+    /// Whitespace between bytes and `0x` prefixes are ignored, so `"de ad"`,
+    /// `"DEAD"` and `"0xde 0xad"` all describe the same two bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The pattern typed by the user
+    ///
+    /// # Returns
+    ///
+    /// Returns the bytes, or `None` if the pattern is empty or not valid hex.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ninja::screens::hex::HexEditor;
+    ///
+    /// assert_eq!(HexEditor::parse_pattern("0x7f 45 4c46"), Some(vec![0x7F, 0x45, 0x4C, 0x46]));
+    /// assert_eq!(HexEditor::parse_pattern("abc"), None);
+    /// ```
+    pub fn parse_pattern(input: &str) -> Option<Vec<u8>> {
+        let digits: String = input
+            .split_whitespace()
+            .map(|token| {
+                token
+                    .strip_prefix("0x")
+                    .or_else(|| token.strip_prefix("0X"))
+                    .unwrap_or(token)
+            })
+            .collect();
+        if digits.is_empty() || !digits.len().is_multiple_of(2) {
+            return None;
+        }
+        (0..digits.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(digits.get(i..i + 2)?, 16).ok())
+            .collect()
+    }
+
+    /// Refreshes the screen and processes one keypress.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(true)` to keep running, `Ok(false)` once the user quits,
+    /// or a `crossterm::Error` if terminal I/O fails.
+    pub fn run(&mut self) -> crossterm::Result<bool> {
+        self.refresh_screen()?;
+        self.process_keypress()
+    }
+
+    /// Number of bytes shown on each row for the current terminal width.
+    fn bytes_per_row(&self) -> usize {
+        [16, 8, 4]
+            .into_iter()
+            .find(|&n| Self::row_width(n) <= self.win_size.0)
+            .unwrap_or(4)
+    }
+
+    /// Total width of a row showing `n` bytes.
+    fn row_width(n: usize) -> usize {
+        // Offset, "xx " per byte, a gap after each group of 8, then the ASCII column
+        OFFSET_WIDTH + n * 3 + n.div_ceil(8) + n
+    }
+
+    /// Screen column of the hex digits of the `i`th byte in a row.
+    fn hex_column(i: usize) -> usize {
+        OFFSET_WIDTH + i * 3 + i / 8
+    }
+
+    /// Screen column of the first character of the ASCII column.
+    fn ascii_column(n: usize) -> usize {
+        OFFSET_WIDTH + n * 3 + n.div_ceil(8)
+    }
+
+    /// Renders the visible rows, the status bar and the message bar.
+    fn refresh_screen(&mut self) -> crossterm::Result<()> {
+        if let Ok((columns, rows)) = terminal::size() {
+            self.win_size = (columns as usize, (rows as usize).saturating_sub(2));
+        }
+        let per_row = self.bytes_per_row();
+        let cursor_row = self.cursor / per_row;
+        if cursor_row < self.row_offset {
+            self.row_offset = cursor_row;
+        }
+        if cursor_row >= self.row_offset + self.win_size.1 {
+            self.row_offset = cursor_row + 1 - self.win_size.1;
+        }
+
+        queue!(self.editor_contents, cursor::Hide, cursor::MoveTo(0, 0))?;
+        for screen_row in 0..self.win_size.1 {
+            let start = (self.row_offset + screen_row) * per_row;
+            if start < self.data.len() || (start == 0 && self.data.is_empty()) {
+                self.draw_row(start, per_row);
+            } else {
+                self.editor_contents.push('~');
+            }
+            queue!(self.editor_contents, terminal::Clear(terminal::ClearType::UntilNewLine))?;
+            self.editor_contents.push_str("\r\n");
+        }
+        self.draw_status_bar();
+        MessageBar::draw_message_bar(
+            &mut self.editor_contents,
+            self.win_size,
+            &mut self.status_message,
+        );
+
+        let in_row = self.cursor % per_row;
+        let cursor_x = match self.pane {
+            Pane::Hex => Self::hex_column(in_row) + self.low_nibble as usize,
+            Pane::Ascii => Self::ascii_column(per_row) + in_row,
+        };
+        let cursor_y = cursor_row - self.row_offset;
+        queue!(
+            self.editor_contents,
+            cursor::MoveTo(cursor_x as u16, cursor_y as u16),
+            cursor::Show
+        )?;
+        self.editor_contents.flush()
+    }
+
+    /// Renders the row of bytes starting at `start`.
+    fn draw_row(&mut self, start: usize, per_row: usize) {
+        let end = cmp::min(start + per_row, self.data.len());
+        let mut line = format!("{:08x}  ", start);
+        let mut ascii = String::new();
+        for i in 0..per_row {
+            if i > 0 && i % 8 == 0 {
+                line.push(' ');
+            }
+            let offset = start + i;
+            if offset >= end {
+                line.push_str("   ");
+                continue;
+            }
+            let byte = self.data[offset];
+            let ch = if byte.is_ascii_graphic() || byte == b' ' {
+                byte as char
+            } else {
+                '.'
+            };
+            let (on, off) = self.byte_style(offset);
+            line.push_str(&format!("{}{:02x}{} ", on, byte, off));
+            ascii.push_str(&format!("{}{}{}", on, ch, off));
+        }
+        line.push(' ');
+        line.push_str(&ascii);
+        self.editor_contents.push_str(&line);
+    }
+
+    /// Returns the escape sequences that start and end the styling of a byte.
+    ///
+    /// The byte under the cursor is shown in reverse video in both columns,
+    /// and bytes changed since the last save are shown in bold.
+    fn byte_style(&self, offset: usize) -> (String, String) {
+        let mut on = String::new();
+        if offset == self.cursor {
+            on.push_str(&style::Attribute::Reverse.to_string());
+        }
+        if self.modified[offset] {
+            on.push_str(&style::Attribute::Bold.to_string());
+        }
+        if on.is_empty() {
+            (on, String::new())
+        } else {
+            (on, style::Attribute::Reset.to_string())
+        }
+    }
+
+    /// Renders the status bar with the file name, offset and size.
+    fn draw_status_bar(&mut self) {
+        self.editor_contents
+            .push_str(&style::Attribute::Reverse.to_string());
+        let info = format!(
+            "{} {}",
+            self.path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("[No Name]"),
+            if self.dirty { "(modified)" } else { "" }
+        );
+        let position = format!(
+            "HEX | {} | 0x{:08x} ({}) / {} bytes",
+            match self.pane {
+                Pane::Hex => "hex",
+                Pane::Ascii => "ascii",
+            },
+            self.cursor,
+            self.cursor,
+            self.data.len()
+        );
+        let width = self.win_size.0;
+        let info: String = info.chars().take(width).collect();
+        let info_len = info.chars().count();
+        self.editor_contents.push_str(&info);
+        for i in info_len..width {
+            if width - i == position.len() {
+                self.editor_contents.push_str(&position);
+                break;
+            }
+            self.editor_contents.push(' ');
+        }
+        self.editor_contents
+            .push_str(&style::Attribute::Reset.to_string());
+        self.editor_contents.push_str("\r\n");
+    }
+
+    /// Reads and handles one keypress.
+    fn process_keypress(&mut self) -> crossterm::Result<bool> {
+        let key_event = self.reader.read_key()?;
+        let per_row = self.bytes_per_row();
+        let page = per_row * self.win_size.1.max(1);
+        let last = self.data.len().saturating_sub(1);
+
+        match key_event {
+            KeyEvent {
+                code: KeyCode::Char('q'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                if self.dirty && self.quit_attempts < self.config.behavior.quit_times {
+                    self.quit_attempts += 1;
+                    let remaining = self.config.behavior.quit_times - self.quit_attempts;
+                    self.status_message.set_message(format!(
+                        "WARNING!!! File has unsaved changes. Press Ctrl-Q {} more times to quit.",
+                        remaining
+                    ));
+                    return Ok(true);
+                }
+                return Ok(false);
+            }
+            KeyEvent {
+                code: KeyCode::Char('s'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => self.save()?,
+            KeyEvent {
+                code: KeyCode::Char('f'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => self.find()?,
+            KeyEvent {
+                code: KeyCode::Char('g'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => self.go_to()?,
+            KeyEvent {
+                code: KeyCode::Tab, ..
+            } => {
+                self.pane = match self.pane {
+                    Pane::Hex => Pane::Ascii,
+                    Pane::Ascii => Pane::Hex,
+                };
+                self.low_nibble = false;
+            }
+            KeyEvent {
+                code: KeyCode::Left,
+                ..
+            } => {
+                if self.pane == Pane::Hex && self.low_nibble {
+                    self.low_nibble = false;
+                } else if self.cursor > 0 {
+                    self.cursor -= 1;
+                    self.low_nibble = self.pane == Pane::Hex;
+                }
+            }
+            KeyEvent {
+                code: KeyCode::Right,
+                ..
+            } => self.advance(),
+            KeyEvent {
+                code: KeyCode::Up, ..
+            } => self.cursor = self.cursor.saturating_sub(per_row),
+            KeyEvent {
+                code: KeyCode::Down,
+                ..
+            } if self.cursor + per_row <= last => self.cursor += per_row,
+            KeyEvent {
+                code: KeyCode::PageUp,
+                ..
+            } => self.cursor = self.cursor.saturating_sub(page),
+            KeyEvent {
+                code: KeyCode::PageDown,
+                ..
+            } => self.cursor = cmp::min(self.cursor + page, last),
+            KeyEvent {
+                code: KeyCode::Home,
+                modifiers,
+                ..
+            } => {
+                self.cursor = if modifiers.contains(KeyModifiers::CONTROL) {
+                    0
+                } else {
+                    self.cursor - self.cursor % per_row
+                };
+                self.low_nibble = false;
+            }
+            KeyEvent {
+                code: KeyCode::End,
+                modifiers,
+                ..
+            } => {
+                self.cursor = if modifiers.contains(KeyModifiers::CONTROL) {
+                    last
+                } else {
+                    cmp::min(self.cursor - self.cursor % per_row + per_row - 1, last)
+                };
+                self.low_nibble = false;
+            }
+            KeyEvent {
+                code: KeyCode::Char(ch),
+                modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+                ..
+            } => self.overwrite(ch),
+            _ => {}
+        }
+        self.quit_attempts = 0;
+        Ok(true)
+    }
+
+    /// Moves the cursor forward by one nibble (hex column) or byte (ASCII column).
+    fn advance(&mut self) {
+        if self.pane == Pane::Hex && !self.low_nibble {
+            if !self.data.is_empty() {
+                self.low_nibble = true;
+            }
+        } else if self.cursor + 1 < self.data.len() {
+            self.cursor += 1;
+            self.low_nibble = false;
+        }
+    }
+
+    /// Overwrites the nibble or byte under the cursor with a typed character.
+    fn overwrite(&mut self, ch: char) {
+        if self.data.is_empty() {
+            self.status_message
+                .set_message("The file is empty: there are no bytes to overwrite".into());
+            return;
+        }
+        let old = self.data[self.cursor];
+        let new = match self.pane {
+            Pane::Hex => {
+                let Some(digit) = ch.to_digit(16) else {
+                    return;
+                };
+                if self.low_nibble {
+                    (old & 0xF0) | digit as u8
+                } else {
+                    (old & 0x0F) | ((digit as u8) << 4)
+                }
+            }
+            Pane::Ascii if ch.is_ascii_graphic() || ch == ' ' => ch as u8,
+            Pane::Ascii => return,
+        };
+        if new != old {
+            self.data[self.cursor] = new;
+            self.modified[self.cursor] = true;
+            self.dirty = true;
+        }
+        self.advance();
+    }
+
+    /// Reads a line of input in the message bar.
+    ///
+    /// Returns `None` if the user pressed Escape.
+    fn prompt(&mut self, message: &str) -> crossterm::Result<Option<String>> {
+        let mut input = String::new();
+        let answer = loop {
+            self.status_message
+                .set_message(format!("{}{} (ESC to cancel)", message, input));
+            self.refresh_screen()?;
+            match self.reader.read_key()? {
+                KeyEvent {
+                    code: KeyCode::Esc, ..
+                } => break None,
+                KeyEvent {
+                    code: KeyCode::Enter,
+                    ..
+                } => break Some(input),
+                KeyEvent {
+                    code: KeyCode::Backspace,
+                    ..
+                } => {
+                    input.pop();
+                }
+                KeyEvent {
+                    code: KeyCode::Char(ch),
+                    modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+                    ..
+                } => input.push(ch),
+                _ => {}
+            }
+        };
+        self.status_message.set_message(String::new());
+        Ok(answer)
+    }
+
+    /// Searches forward from the cursor for a hex byte pattern, wrapping
+    /// around at the end of the file.
+    fn find(&mut self) -> crossterm::Result<()> {
+        let Some(input) = self.prompt("Search hex: ")? else {
+            return Ok(());
+        };
+        let pattern = if input.trim().is_empty() {
+            self.last_search.clone()
+        } else {
+            Self::parse_pattern(&input)
+        };
+        let Some(pattern) = pattern else {
+            self.status_message
+                .set_message(format!("Invalid hex pattern: {}", input.trim()));
+            return Ok(());
+        };
+
+        let from = cmp::min(self.cursor + 1, self.data.len());
+        let matches_at = |offset: &usize| self.data[*offset..].starts_with(&pattern);
+        let found = (from..self.data.len())
+            .find(matches_at)
+            .or_else(|| (0..from).find(matches_at));
+        match found {
+            Some(offset) => {
+                self.cursor = offset;
+                self.low_nibble = false;
+                let wrapped = if offset < from { " (wrapped)" } else { "" };
+                self.status_message
+                    .set_message(format!("Found at 0x{:08x}{}", offset, wrapped));
+            }
+            None => self.status_message.set_message("Pattern not found".into()),
+        }
+        self.last_search = Some(pattern);
+        Ok(())
+    }
+
+    /// Moves the cursor to an offset typed by the user.
+    ///
+    /// Offsets starting with `0x` are hexadecimal, all others decimal.
+    fn go_to(&mut self) -> crossterm::Result<()> {
+        let Some(input) = self.prompt("Go to offset: ")? else {
+            return Ok(());
+        };
+        let input = input.trim();
+        let offset = match input.strip_prefix("0x").or_else(|| input.strip_prefix("0X")) {
+            Some(hex) => usize::from_str_radix(hex, 16),
+            None => input.parse(),
+        };
+        match offset {
+            Ok(offset) if offset < self.data.len() => {
+                self.cursor = offset;
+                self.low_nibble = false;
+            }
+            Ok(_) => self.status_message.set_message(format!(
+                "Offset {} is past the end of the file ({} bytes)",
+                input,
+                self.data.len()
+            )),
+            Err(_) => self
+                .status_message
+                .set_message(format!("Invalid offset: {}", input)),
+        }
+        Ok(())
+    }
+
+    /// Asks a yes/no question in the message bar.
+    fn confirm(&mut self, message: &str) -> crossterm::Result<bool> {
+        let answer = loop {
+            self.status_message.set_message(message.to_string());
+            self.refresh_screen()?;
+            match self.reader.read_key()?.code {
+                KeyCode::Char('y') => break true,
+                KeyCode::Char('n') | KeyCode::Esc => break false,
+                _ => {}
+            }
+        };
+        self.status_message.set_message(String::new());
+        Ok(answer)
+    }
+
+    /// Writes the bytes back to disk unchanged.
+    ///
+    /// Read-only files are only overwritten after confirmation, and a backup
+    /// is taken on the first save when backups are enabled.
+    fn save(&mut self) -> crossterm::Result<()> {
+        if file_io::is_read_only(&self.path) {
+            let message = format!("{} is read-only. Overwrite anyway? (y/n)", self.path.display());
+            if !self.confirm(&message)? {
+                self.status_message.set_message("Save Aborted".into());
+                return Ok(());
+            }
+        }
+        let result = (|| {
+            if self.config.behavior.backup_files && !self.backed_up {
+                backup::create(&self.path, &self.config.behavior).map_err(|e| {
+                    io::Error::new(e.kind(), format!("could not create backup: {}", e))
+                })?;
+                self.backed_up = true;
+            }
+            file_io::write_atomic(&self.path, &self.data)
+        })();
+        match result {
+            Ok(()) => {
+                self.dirty = false;
+                self.modified.iter_mut().for_each(|changed| *changed = false);
+                self.status_message
+                    .set_message(format!("{} bytes written to disk", self.data.len()));
+            }
+            Err(e) => self
+                .status_message
+                .set_message(format!("Save failed: {}", e)),
+        }
+        Ok(())
+    }
+}
@@ -7,6 +7,7 @@
 //! ## Screen Types
 //! 
 //! - **`editor`**: The main text editing screen with full functionality
+//! - **`hex`**: Hex editor screen for binary files
 //! - **`clipboard`**: Clipboard management and history view
 //! - **`debug`**: Debug information and diagnostics display
 //! - **`screens`**: Screen manager that coordinates between different screens
@@ -22,4 +23,5 @@
 pub mod screens;
 pub mod clipboard;
 pub mod debug;
-pub mod editor;
\ No newline at end of file
+pub mod editor;
+pub mod hex;
\ No newline at end of file
@@ -9,6 +9,7 @@
 //! - **`ScreenManager`**: Central manager for all screens
 //! - **`ActiveScreen`**: Enumeration of available screen types
 //! - **`EditorScreen`**: Wrapper for the main editor screen
+//! - **`HexScreen`**: Wrapper for the hex editor screen
 //! - **`DebugScreen`**: Wrapper for the debug screen
 //! - **`ClipboardScreen`**: Wrapper for the clipboard screen
 //! 
@@ -24,6 +25,7 @@
 //! 
 //! The screen system supports several types of screens:
 //! - **Editor**: Main text editing interface
+//! - **Hex**: Byte-level editing of binary files
 //! - **Debug**: Diagnostic and debugging information
 //! - **Clipboard**: Clipboard history management
 //! 
@@ -44,7 +46,13 @@
 //! ```
 
 use crate::config::NinjaConfig;
-use crate::screens::{clipboard, debug, editor};
+use crate::cli::{CliOptions, FileArg, FileSource};
+use crate::screens::editor::EditorRows;
+use crate::screens::{clipboard, debug, editor, hex};
+use crate::transput::transput::Output;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
 
 /// Manages the different screens in the Ninja editor.
 /// 
@@ -120,6 +128,34 @@ pub struct EditorScreen {
     pub screen: editor::Editor,
 }
 
+/// Wrapper for the hex editor screen.
+/// 
+/// This struct provides a wrapper around the hex editor, which is used in
+/// place of the main editor when the opened file contains binary data.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use ninja::cli::{CliOptions, FileArg, FileSource};
+/// use ninja::config::NinjaConfig;
+/// use ninja::screens::screens::{ActiveScreen, ScreenManager};
+///
+/// let path = std::env::temp_dir().join("ninja-hex-screen-example.bin");
+/// std::fs::write(&path, [0x00, 0xff, 0x10, 0x00]).unwrap();
+/// let options = CliOptions {
+///     files: vec![FileArg { source: FileSource::Path(path), line: None, column: None }],
+///     ..Default::default()
+/// };
+///
+/// let mut screen_manager = ScreenManager::new();
+/// screen_manager.open_files(NinjaConfig::default(), &options, None);
+/// assert!(matches!(screen_manager.active_screen(), Some(ActiveScreen::Hex(_))));
+/// ```
+pub struct HexScreen {
+    /// The underlying hex editor instance
+    pub screen: hex::HexEditor,
+}
+
 /// Wrapper for the debug screen.
 /// 
 /// This struct provides a wrapper around the debug screen functionality,
@@ -277,8 +313,8 @@ impl ScreenManager {
 
     /// Shows the editor screen with the given configuration.
     /// 
-    /// This method creates a new editor screen with the specified configuration
-    /// and sets it as the active screen.
+    /// This method creates a new editor screen with an untitled buffer and
+    /// sets it as the active screen.
     /// 
     /// # Arguments
     /// 
@@ -300,6 +336,152 @@ impl ScreenManager {
         let editor = editor::Editor::new(config);
         self.set_active_screen(ActiveScreen::Editor(EditorScreen { screen: editor }));
     }
+
+    /// Opens the buffers requested on the command line.
+    /// 
+    /// Each file opens in its own editor buffer, with the cursor at the
+    /// requested position. A single file containing binary data opens in the
+    /// hex editor screen instead, unless the editor runs in pipe or view mode.
+    /// Any other binary file opens read-only, since saving its decoded text
+    /// would not reproduce the original bytes.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `config` - The configuration to use for the editor
+    /// * `options` - The parsed command-line options
+    /// * `stdin` - The contents of standard input, if a buffer reads it
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::cli::{CliOptions, FileArg, FileSource};
+    /// use ninja::screens::screens::{ActiveScreen, ScreenManager};
+    /// use ninja::config::NinjaConfig;
+    /// 
+    /// let path = std::env::temp_dir().join("ninja-open-files-example.txt");
+    /// std::fs::write(&path, "one\ntwo\n").unwrap();
+    /// let options = CliOptions {
+    ///     files: vec![
+    ///         FileArg { source: FileSource::Path(path), line: Some(2), column: Some(3) },
+    ///         FileArg { source: FileSource::Stdin, line: None, column: None },
+    ///     ],
+    ///     ..Default::default()
+    /// };
+    /// 
+    /// let mut screen_manager = ScreenManager::new();
+    /// screen_manager.open_files(NinjaConfig::default(), &options, Some(b"piped\n".to_vec()));
+    /// let Some(ActiveScreen::Editor(editor)) = screen_manager.active_screen() else {
+    ///     panic!("text files open in the editor");
+    /// };
+    /// assert_eq!(editor.screen.buffer_count(), 2);
+    /// let cursor = &editor.screen.output.cursor_controller;
+    /// assert_eq!((cursor.cursor_y, cursor.cursor_x), (1, 2));
+    /// ```
+    /// 
+    /// A binary file opened next to a text file cannot be changed:
+    /// 
+    /// ```rust
+    /// use ninja::cli::{CliOptions, FileArg, FileSource};
+    /// use ninja::screens::screens::{ActiveScreen, ScreenManager};
+    /// use ninja::config::NinjaConfig;
+    /// 
+    /// let binary = std::env::temp_dir().join("ninja-open-files-example.bin");
+    /// let bytes = [0x89, b'P', b'N', b'G', 0x00, 0xff, 0xfe, b'\n'];
+    /// std::fs::write(&binary, bytes).unwrap();
+    /// let text = std::env::temp_dir().join("ninja-open-files-example-2.txt");
+    /// std::fs::write(&text, "text\n").unwrap();
+    /// let options = CliOptions {
+    ///     files: vec![
+    ///         FileArg { source: FileSource::Path(binary.clone()), line: None, column: None },
+    ///         FileArg { source: FileSource::Path(text), line: None, column: None },
+    ///     ],
+    ///     ..Default::default()
+    /// };
+    /// 
+    /// let mut screen_manager = ScreenManager::new();
+    /// screen_manager.open_files(NinjaConfig::default(), &options, None);
+    /// let Some(ActiveScreen::Editor(editor)) = screen_manager.active_screen_mut() else {
+    ///     panic!("several files open in the editor");
+    /// };
+    /// let output = &mut editor.screen.output;
+    /// assert!(output.read_only);
+    /// output.insert_char('x');
+    /// assert_eq!(output.dirty, 0);
+    /// assert_eq!(std::fs::read(&binary).unwrap(), bytes);
+    /// ```
+    pub fn open_files(&mut self, config: NinjaConfig, options: &CliOptions, stdin: Option<Vec<u8>>) {
+        if let [FileArg { source: FileSource::Path(path), .. }] = options.files.as_slice()
+            && !options.pipe
+            && !options.view
+            && Self::is_binary_file(path)
+            && let Ok(hex_editor) = hex::HexEditor::open(path.clone(), config.clone())
+        {
+            self.set_active_screen(ActiveScreen::Hex(HexScreen { screen: hex_editor }));
+            return;
+        }
+
+        let mut stdin = stdin;
+        let buffers = options
+            .files
+            .iter()
+            .map(|file| {
+                let mut output = match &file.source {
+                    FileSource::Path(path) => {
+                        let mut output = Output::from_file(config.clone(), path.clone());
+                        // Decoding binary data as text is lossy, so saving it back would corrupt it
+                        if Self::is_binary_file(path) {
+                            output.read_only = true;
+                            output.status_message.set_message(format!(
+                                "WARNING: {} looks binary; opened read-only (open it on its own for the hex editor)",
+                                path.display()
+                            ));
+                        }
+                        output
+                    }
+                    FileSource::Stdin => {
+                        let bytes = stdin.take().unwrap_or_default();
+                        Output::with_rows(config.clone(), EditorRows::from_bytes(&bytes, &None), None)
+                    }
+                };
+                output.read_only |= options.read_only;
+                if let Some(line) = file.line {
+                    let column = file.column.unwrap_or(1);
+                    output.set_cursor_position(line - 1, column.saturating_sub(1));
+                }
+                output
+            })
+            .collect();
+        let mut editor = editor::Editor::with_buffers(config, buffers);
+        if options.pipe {
+            editor.enable_pipe();
+        }
+        if options.view {
+            editor.enable_view_mode();
+        }
+        self.set_active_screen(ActiveScreen::Editor(EditorScreen { screen: editor }));
+    }
+
+    /// Takes the buffer contents saved in pipe mode.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns the contents of the last save, or `None` if the active screen
+    /// is not an editor in pipe mode or nothing was saved.
+    pub fn take_pipe_output(&mut self) -> Option<Vec<u8>> {
+        match self.active_screen.as_mut()? {
+            ActiveScreen::Editor(editor) => editor.screen.take_pipe_output(),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the start of the file looks like binary data.
+    fn is_binary_file(path: &Path) -> bool {
+        let mut head = Vec::new();
+        File::open(path)
+            .and_then(|file| file.take(8192).read_to_end(&mut head))
+            .is_ok()
+            && hex::is_binary(&head)
+    }
     
     /// Runs the currently active screen.
     /// 
@@ -309,6 +491,7 @@ impl ScreenManager {
     /// # Behavior
     /// 
     /// - **Editor Screen**: Runs the main editor loop
+    /// - **Hex Screen**: Runs the hex editor loop
     /// - **Debug Screen**: Runs the debug screen logic
     /// - **Clipboard Screen**: Runs the clipboard screen logic
     /// - **No Active Screen**: Prints an error message
@@ -328,6 +511,7 @@ impl ScreenManager {
     pub fn run_active(&mut self) {
         match self.active_screen_mut() {
             Some(ActiveScreen::Editor(editor)) => editor.run(),
+            Some(ActiveScreen::Hex(hex)) => hex.run(),
             Some(ActiveScreen::Debug(debug)) => debug.run(),
             Some(ActiveScreen::Clipboard(clipboard)) => clipboard.run(),
             None => {
@@ -347,6 +531,7 @@ impl EditorScreen {
     /// 
     /// # Behavior
     /// 
+    /// - **Swap Recovery**: Offers to recover swap files left by a previous session
     /// - **Main Loop**: Continuously runs the editor until quit
     /// - **Error Handling**: Expects the editor to run successfully
     /// - **Exit Condition**: Stops when the editor returns false (quit)
@@ -370,10 +555,26 @@ impl EditorScreen {
     /// // editor_screen.run();
     /// ```
     pub fn run(&mut self) {
+        self.screen
+            .check_swap_files()
+            .expect("Could not check for swap files");
         while self.screen.run().expect("Could not run Ninja Editor") {}
     }
 }
 
+impl HexScreen {
+    /// Runs the hex editor screen.
+    /// 
+    /// This method runs the hex editor loop until the user quits.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if the hex editor fails to run (e.g., terminal errors).
+    pub fn run(&mut self) {
+        while self.screen.run().expect("Could not run Ninja hex editor") {}
+    }
+}
+
 impl DebugScreen {
     /// Runs the debug screen.
     /// 
@@ -440,6 +641,7 @@ impl ClipboardScreen {
 /// # Variants
 /// 
 /// - **`Editor`**: The main text editing screen
+/// - **`Hex`**: The hex editor screen for binary files
 /// - **`Debug`**: The debug and diagnostic screen
 /// - **`Clipboard`**: The clipboard management screen
 /// 
@@ -460,6 +662,7 @@ impl ClipboardScreen {
 /// 
 /// match active_screen {
 ///     ActiveScreen::Editor(_) => println!("Editor screen"),
+///     ActiveScreen::Hex(_) => println!("Hex screen"),
 ///     ActiveScreen::Debug(_) => println!("Debug screen"),
 ///     ActiveScreen::Clipboard(_) => println!("Clipboard screen"),
 /// }
@@ -467,6 +670,8 @@ impl ClipboardScreen {
 pub enum ActiveScreen {
     /// The main text editing screen
     Editor(EditorScreen),
+    /// The hex editor screen for binary files
+    Hex(HexScreen),
     /// The debug and diagnostic screen
     Debug(DebugScreen),
     /// The clipboard management screen
@@ -13,6 +13,8 @@
 //! 
 //! - Character insertion and deletion with undo/redo
 //! - Multi-line text selection and manipulation
+//! - Multiple cursors that edit and move together
+//! - Rectangular block selection in display columns
 //! - Clipboard integration with copy/cut/paste
 //! - Search functionality with highlighting
 //! - Syntax highlighting for multiple programming languages
@@ -31,13 +33,21 @@
 //! - Search state and highlighting
 //! - Configuration integration
 
+use crate::modules::backup;
+use crate::modules::file_io;
 use crate::modules::clipboard::CLIPBOARD;
+use crate::modules::encoding::FileEncoding;
+use crate::modules::file_format::LineEnding;
 use crate::config::NinjaConfig;
-use crate::modules::cursor::CursorController;
+use crate::modules::cursor::{CursorController, CursorMotion, GotoTarget};
+use crate::modules::graphemes;
+use crate::modules::indentation;
+use crate::modules::jump_list::JumpList;
+use crate::modules::multi_cursor::{self, Caret};
 use crate::screens::editor::{EditorContents, EditorRows, Row};
 use crossterm::event::KeyModifiers;
 use crate::modules::highlighting::{
-    CHighlight, CSSHighlight, GoHighlight, HTMLHighlight, HighlightType, JavaHighlight,
+    CHighlight, CSSHighlight, GoHighlight, HTMLHighlight, HighlightType, IndentRules, JavaHighlight,
     JavaScriptHighlight, PythonHighlight, RustHighlight, SyntaxHighlight, TypeScriptHighlight, TOMLHighlight,
 };
 use crate::modules::search::{SearchDirection, SearchIndex};
@@ -45,13 +55,14 @@ use crate::modules::status::StatusMessage;
 use crate::modules::statusbar::StatusBar;
 use crate::modules::message_bar::MessageBar;
 use crate::{prompt, VERSION};
-use crossterm::event::{Event, KeyCode, KeyEvent};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
 use crossterm::style::Color;
 use crossterm::terminal::ClearType;
 use crossterm::{cursor, event, execute, queue, style, terminal};
 use std::io::{stdout, Write};
+use std::path::PathBuf;
 use std::time::Duration;
-use std::{cmp, io};
+use std::{cmp, fs, io};
 
 /// Handles keyboard input and event processing for the editor.
 /// 
@@ -112,6 +123,8 @@ pub struct Output {
     pub editor_rows: EditorRows,
     pub status_message: StatusMessage,
     pub dirty: u64,
+    /// Whether saving this buffer is refused
+    pub read_only: bool,
     search_index: SearchIndex,
     pub syntax_highlight: Option<Box<dyn SyntaxHighlight>>,
     pub config: NinjaConfig,
@@ -120,9 +133,29 @@ pub struct Output {
     selection_start: Option<(usize, usize)>, // (row, col)
     selection_end: Option<(usize, usize)>,   // (row, col)
     is_selecting: bool,
+    // Additional cursors besides `cursor_controller`
+    carets: Vec<Caret>,
+    // Fixed corner of a block selection as (row, display column)
+    block_anchor: Option<(usize, usize)>,
     // Undo stack
-    undo_stack: Vec<(Vec<Row>, CursorController, u64)>,
+    undo_stack: Vec<UndoState>,
     pending_edit: bool,
+    // Positions before large cursor jumps
+    jump_list: JumpList,
+    // Closing brackets and quotes inserted by pairing, as (row, col)
+    auto_closed: Vec<(usize, usize)>,
+}
+
+/// A snapshot of the buffer and its cursors, restored by undo.
+struct UndoState {
+    rows: Vec<Row>,
+    cursor: CursorController,
+    dirty: u64,
+    carets: Vec<Caret>,
+    selection_start: Option<(usize, usize)>,
+    selection_end: Option<(usize, usize)>,
+    is_selecting: bool,
+    block_anchor: Option<(usize, usize)>,
 }
 
 impl Output {
@@ -220,19 +253,121 @@ impl Output {
     /// let output = Output::new(config);
     /// ```
     pub fn new(config: NinjaConfig) -> Self {
+        Self::with_rows(config, EditorRows::new(), None)
+    }
+
+    /// Creates a new Output instance editing the file at `path`.
+    /// 
+    /// The syntax highlighter is chosen from the file extension. A file that
+    /// does not exist yet opens as an empty buffer that is created on save,
+    /// and a file without write permission opens read-only.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `config` - The editor configuration
+    /// * `path` - The file to open
+    /// 
+    /// # Returns
+    /// 
+    /// Returns a new `Output` instance for the file.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::transput::transput::Output;
+    /// use ninja::config::NinjaConfig;
+    /// 
+    /// let path = std::env::temp_dir().join("ninja-from-file-example.rs");
+    /// std::fs::write(&path, "fn main() {}\n").unwrap();
+    /// let output = Output::from_file(NinjaConfig::default(), path.clone());
+    /// assert_eq!(output.editor_rows.get_row(0), "fn main() {}");
+    /// assert!(!output.read_only);
+    /// 
+    /// // A file that does not exist yet opens as an empty buffer
+    /// std::fs::remove_file(&path).unwrap();
+    /// let output = Output::from_file(NinjaConfig::default(), path);
+    /// assert_eq!(output.editor_rows.number_of_rows(), 0);
+    /// ```
+    pub fn from_file(config: NinjaConfig, path: PathBuf) -> Self {
+        let read_only = file_io::is_read_only(&path);
+        let mut syntax_highlight = None;
+        let editor_rows = EditorRows::from_file(path, &mut syntax_highlight);
+        let mut output = Self::with_rows(config, editor_rows, syntax_highlight);
+        if read_only {
+            output.read_only = true;
+            output.status_message.set_message(
+                "File is not writable; opened read-only (Alt-R = Allow editing)".into(),
+            );
+        }
+        output
+    }
+
+    /// Creates a new Output instance around already loaded editor rows.
+    /// 
+    /// The buffer's indentation settings start from the `editor` section of
+    /// the configuration, with the language's overrides applied. If
+    /// `detect_indentation` is on, indentation found in the contents takes
+    /// precedence. The tab size also sets how wide tabs are drawn.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `config` - The editor configuration
+    /// * `editor_rows` - The buffer contents
+    /// * `syntax_highlight` - The syntax highlighter for the contents (if any)
+    /// 
+    /// # Returns
+    /// 
+    /// Returns a new `Output` instance for the given contents.
+    pub fn with_rows(
+        mut config: NinjaConfig,
+        mut editor_rows: EditorRows,
+        syntax_highlight: Option<Box<dyn SyntaxHighlight>>,
+    ) -> Self {
+        // Indentation comes from the language's settings, then from the file itself
+        let extension = editor_rows
+            .filename
+            .as_ref()
+            .and_then(|path| path.extension())
+            .and_then(|ext| ext.to_str());
+        config.editor = config.editor_for(syntax_highlight.as_ref().map(|it| it.file_type()), extension);
+        if config.editor.detect_indentation
+            && let Some(detected) = indentation::detect(
+                editor_rows.row_contents.iter().map(|row| row.row_content.as_str()),
+            )
+        {
+            config.editor.soft_tabs = detected.soft_tabs;
+            if let Some(width) = detected.width {
+                config.editor.tab_size = width;
+            }
+        }
+        if editor_rows.tab_width != config.editor.tab_size {
+            editor_rows.set_tab_width(config.editor.tab_size);
+            // Highlighting follows the rendered text, which the tab width changes
+            if let Some(it) = syntax_highlight.as_ref() {
+                (0..editor_rows.number_of_rows())
+                    .for_each(|row| it.update_syntax(row, &mut editor_rows.row_contents));
+            }
+        }
+
         let win_size = terminal::size()
             .map(|(x, y)| (x as usize, y as usize - 2))
             .unwrap();
-        let mut syntax_highlight = None;
+        let message = if editor_rows.format.decode_errors {
+            format!(
+                "WARNING: File is not valid {}; invalid bytes were replaced. Alt-E = Reopen with encoding",
+                editor_rows.format.encoding.name()
+            )
+        } else {
+            "HELP: Ctrl-S = Save | Ctrl-Q = Quit | Ctrl-F = Find | Ctrl-C = Copy | Ctrl-V = Paste".into()
+        };
         Self {
             win_size,
             editor_contents: EditorContents::new(),
             cursor_controller: CursorController::new(win_size),
-            editor_rows: EditorRows::new(&mut syntax_highlight),
-            status_message: StatusMessage::new(
-                "HELP: Ctrl-S = Save | Ctrl-Q = Quit | Ctrl-F = Find | Ctrl-C = Copy | Ctrl-V = Paste".into(),
-            ),
+            editor_rows,
+            status_message: StatusMessage::new(message),
             dirty: 0,
+            read_only: false,
             search_index: SearchIndex::new(),
             syntax_highlight,
             config,
@@ -240,11 +375,174 @@ impl Output {
             selection_start: None,
             selection_end: None,
             is_selecting: false,
+            carets: Vec::new(),
+            block_anchor: None,
             undo_stack: Vec::new(),
             pending_edit: false,
+            jump_list: JumpList::new(),
+            auto_closed: Vec::new(),
+        }
+    }
+
+    /// Moves the cursor to the given position, clamped to the buffer.
+    /// 
+    /// The viewport is scrolled so that the cursor is centered vertically.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `row` - Zero-based row
+    /// * `column` - Zero-based character column
+    pub fn set_cursor_position(&mut self, row: usize, column: usize) {
+        self.cursor_controller.jump_to(row, column, &self.editor_rows);
+    }
+
+    /// Returns the cursor position as a `(row, column)` pair.
+    fn cursor_position(&self) -> (usize, usize) {
+        (self.cursor_controller.cursor_y, self.cursor_controller.cursor_x)
+    }
+
+    /// Prompts for a position and moves the cursor there.
+    /// 
+    /// The prompt accepts a line (`42`), a line and column (`42:7`), a
+    /// relative offset (`+10`, `-10`) or a percentage of the file (`50%`),
+    /// as parsed by `GotoTarget::parse`, and jumps there with [`Output::goto`].
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `Ok(())` on success, or an `io::Error` if input or rendering fails.
+    pub fn goto_prompt(&mut self) -> io::Result<()> {
+        let Some(input) = prompt!(self, "Go to: {} (line, line:col, +N / -N, N%)") else {
+            return Ok(());
+        };
+        match GotoTarget::parse(&input) {
+            Some(target) => self.goto(target),
+            None => self
+                .status_message
+                .set_message(format!("Invalid position: {}", input)),
+        }
+        Ok(())
+    }
+
+    /// Moves the cursor to a go-to target.
+    /// 
+    /// The position before the jump is recorded in the jump list. Targets
+    /// without a column keep the cursor's column where the line allows.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `target` - Where to go
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::modules::cursor::GotoTarget;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let rows = EditorRows::from_bytes(b"one\ntwo\nthree\n", &None);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, None);
+    /// output.goto(GotoTarget::Line(3, Some(4)));
+    /// assert_eq!((output.cursor_controller.cursor_y, output.cursor_controller.cursor_x), (2, 3));
+    /// output.goto(GotoTarget::Relative(-1));
+    /// assert_eq!((output.cursor_controller.cursor_y, output.cursor_controller.cursor_x), (1, 3));
+    /// ```
+    pub fn goto(&mut self, target: GotoTarget) {
+        let current = self.cursor_position();
+        let (row, column) = target.resolve(current.0, self.editor_rows.number_of_rows());
+        self.jump_list.record(current);
+        self.set_cursor_position(row, column.unwrap_or(current.1));
+    }
+
+    /// Moves the cursor back to the position before the last jump.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::modules::cursor::GotoTarget;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let rows = EditorRows::from_bytes(b"one\ntwo\nthree\n", &None);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, None);
+    /// output.set_cursor_position(0, 2);
+    /// output.goto(GotoTarget::Line(3, None));
+    /// output.jump_back();
+    /// assert_eq!((output.cursor_controller.cursor_y, output.cursor_controller.cursor_x), (0, 2));
+    /// // There is nothing earlier to go back to
+    /// output.jump_back();
+    /// assert_eq!((output.cursor_controller.cursor_y, output.cursor_controller.cursor_x), (0, 2));
+    /// ```
+    pub fn jump_back(&mut self) {
+        match self.jump_list.back(self.cursor_position()) {
+            Some((row, column)) => self.set_cursor_position(row, column),
+            None => self.status_message.set_message("No earlier jump".into()),
+        }
+    }
+
+    /// Moves the cursor forward again after `jump_back`.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::modules::cursor::GotoTarget;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let rows = EditorRows::from_bytes(b"one\ntwo\nthree\n", &None);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, None);
+    /// output.goto(GotoTarget::Line(3, Some(2)));
+    /// output.jump_back();
+    /// output.jump_forward();
+    /// assert_eq!((output.cursor_controller.cursor_y, output.cursor_controller.cursor_x), (2, 1));
+    /// ```
+    pub fn jump_forward(&mut self) {
+        match self.jump_list.forward() {
+            Some((row, column)) => self.set_cursor_position(row, column),
+            None => self.status_message.set_message("No later jump".into()),
         }
     }
 
+    /// Turns read-only mode on or off for the buffer.
+    /// 
+    /// While read-only, every change to the buffer is refused with a message
+    /// and saving is disabled.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// fn toggle(output: &mut Output) {
+    ///     output.toggle_read_only();
+    /// }
+    /// ```
+    pub fn toggle_read_only(&mut self) {
+        self.read_only = !self.read_only;
+        let message = if self.read_only {
+            "Buffer is now read-only"
+        } else {
+            "Buffer is now editable"
+        };
+        self.status_message.set_message(message.into());
+    }
+
+    /// Checks that the buffer may be changed.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `true` if the buffer is editable. Otherwise a message is shown
+    /// and `false` is returned.
+    pub fn ensure_writable(&mut self) -> bool {
+        if self.read_only {
+            self.status_message
+                .set_message("Buffer is read-only (Alt-R = Allow editing)".into());
+        }
+        !self.read_only
+    }
+
     /// Clears the terminal screen and moves the cursor to the top-left corner.
     /// 
     /// This method provides a clean slate for rendering by clearing all
@@ -382,7 +680,7 @@ impl Output {
                         output.cursor_controller.cursor_y = row_index;
                         output.search_index.y_index = row_index;
                         output.search_index.x_index = index;
-                        output.cursor_controller.cursor_x = row.get_row_content_x(index);
+                        output.cursor_controller.cursor_x = row.char_index_for_render_index(index);
                         output.cursor_controller.row_offset = output.editor_rows.number_of_rows();
                         break;
                     }
@@ -423,161 +721,686 @@ impl Output {
         .is_none()
         {
             self.cursor_controller = cursor_controller
+        } else if self.cursor_position() != (cursor_controller.cursor_y, cursor_controller.cursor_x) {
+            self.jump_list
+                .record((cursor_controller.cursor_y, cursor_controller.cursor_x));
         }
         Ok(())
     }
 
-    // Selection and clipboard methods
-    pub fn start_selection(&mut self) {
-        self.is_selecting = true;
-        self.selection_start = Some((
-            self.cursor_controller.cursor_y,
-            self.cursor_controller.cursor_x,
-        ));
-        self.selection_end = Some((
-            self.cursor_controller.cursor_y,
-            self.cursor_controller.cursor_x,
-        ));
+    /// Asks the user to pick one of several single-key choices.
+    /// 
+    /// The message is shown in the message bar and the method waits until
+    /// one of the given characters is typed. Escape cancels the question.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `message` - The question to display, including the available choices
+    /// * `choices` - The characters accepted as answers
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `Ok(Some(char))` with the chosen character, `Ok(None)` if the
+    /// user pressed Escape, or a `crossterm::Error` if input fails.
+    /// 
+    /// # Example
+    /// 
+    /// The question waits for a key press on the terminal, so this example is not run.
+    /// 
+    /// ```rust,no_run
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let rows = EditorRows::from_bytes(b"text\n", &None);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, None);
+    /// let answer = output.prompt_choice("Overwrite? (y/n)", &['y', 'n']).unwrap();
+    /// assert!(matches!(answer, Some('y' | 'n') | None));
+    /// ```
+    pub fn prompt_choice(&mut self, message: &str, choices: &[char]) -> crossterm::Result<Option<char>> {
+        let answer = loop {
+            self.status_message.set_message(message.to_string());
+            self.refresh_screen()?;
+            match Reader.read_key()? {
+                KeyEvent { code: KeyCode::Esc, .. } => break None,
+                KeyEvent { code: KeyCode::Char(ch), .. } if choices.contains(&ch) => break Some(ch),
+                _ => {}
+            }
+        };
+        self.status_message.set_message(String::new());
+        Ok(answer)
     }
 
-    pub fn update_selection(&mut self) {
-        if self.is_selecting {
-            self.selection_end = Some((
-                self.cursor_controller.cursor_y,
-                self.cursor_controller.cursor_x,
-            ));
-        }
+    /// Temporarily shows a list of lines in place of the buffer.
+    /// 
+    /// This is used for read-only views such as diffs. The buffer, cursor,
+    /// selection and syntax highlighting are restored once the user closes
+    /// the view with `q`, Escape or Enter.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `title` - Message bar text describing the view
+    /// * `lines` - The lines to display
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `Ok(())` once the view is closed, or a `crossterm::Error` if
+    /// rendering or input fails.
+    /// 
+    /// # Example
+    /// 
+    /// The lines stay up until a key is pressed on the terminal, so this
+    /// example is not run.
+    /// 
+    /// ```rust,no_run
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let rows = EditorRows::from_bytes(b"text\n", &None);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, None);
+    /// output.show_lines("Diff", vec!["-old".to_string(), "+new".to_string()]).unwrap();
+    /// // The buffer is shown again once the lines are closed
+    /// assert_eq!(output.editor_rows.get_row(0), "text");
+    /// ```
+    pub fn show_lines(&mut self, title: &str, lines: Vec<String>) -> crossterm::Result<()> {
+        self.view_lines(
+            &format!("{} (Arrows / PgUp / PgDn to scroll, q / ESC to close)", title),
+            lines,
+            false,
+        )
+        .map(|_| ())
     }
 
-    pub fn clear_selection(&mut self) {
-        self.is_selecting = false;
-        self.selection_start = None;
-        self.selection_end = None;
+    /// Temporarily shows a list of lines and lets the user pick one.
+    /// 
+    /// The cursor starts on the first line; Enter picks the line under the
+    /// cursor and `q` or Escape cancels. The buffer is restored afterwards.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `title` - Message bar text describing the choice
+    /// * `lines` - The lines to choose from
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `Ok(Some(index))` with the picked line, `Ok(None)` if the user
+    /// cancelled, or a `crossterm::Error` if rendering or input fails.
+    /// 
+    /// # Example
+    /// 
+    /// Picking waits for key presses on the terminal, so this example is not run.
+    /// 
+    /// ```rust,no_run
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let rows = EditorRows::from_bytes(b"text\n", &None);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, None);
+    /// let colors = vec!["red".to_string(), "green".to_string()];
+    /// if let Some(index) = output.pick_line("Color", colors).unwrap() {
+    ///     assert!(index < 2);
+    /// }
+    /// // The buffer is shown again once the list is closed
+    /// assert_eq!(output.editor_rows.get_row(0), "text");
+    /// ```
+    pub fn pick_line(&mut self, title: &str, lines: Vec<String>) -> crossterm::Result<Option<usize>> {
+        self.view_lines(
+            &format!("{} (Arrows to move, Enter to select, q / ESC to cancel)", title),
+            lines,
+            true,
+        )
     }
 
-    pub fn has_selection(&self) -> bool {
-        if let (Some(start), Some(end)) = (self.selection_start, self.selection_end) {
-            start != end
-        } else {
-            false
+    /// Shared implementation of [`Output::show_lines`] and [`Output::pick_line`].
+    fn view_lines(&mut self, message: &str, lines: Vec<String>, pick: bool) -> crossterm::Result<Option<usize>> {
+        let rows = std::mem::take(&mut self.editor_rows.row_contents);
+        let cursor_controller = self.cursor_controller;
+        let syntax_highlight = self.syntax_highlight.take();
+        let selection = (self.selection_start, self.selection_end, self.is_selecting);
+        let carets = std::mem::take(&mut self.carets);
+        self.clear_selection();
+        for (i, line) in lines.into_iter().enumerate() {
+            self.editor_rows.insert_row(i, line);
         }
-    }
-
-    pub fn is_selecting(&self) -> bool {
-        self.is_selecting
-    }
+        self.cursor_controller.cursor_x = 0;
+        self.cursor_controller.cursor_y = 0;
+        self.cursor_controller.row_offset = 0;
+        self.cursor_controller.column_offset = 0;
 
-    pub fn get_selection_bounds(&self) -> Option<((usize, usize), (usize, usize))> {
-        if let (Some(start), Some(end)) = (self.selection_start, self.selection_end) {
-            // Ensure start is before end
-            if (start.0 < end.0) || (start.0 == end.0 && start.1 <= end.1) {
-                Some((start, end))
-            } else {
-                Some((end, start))
+        let result = loop {
+            self.status_message.set_message(message.to_string());
+            if let Err(e) = self.refresh_screen() {
+                break Err(e);
             }
-        } else {
-            None
-        }
-    }
-
-    pub fn copy_selection(&mut self) {
-        if let Some(((start_row, start_col), (end_row, end_col))) = self.get_selection_bounds() {
-            let mut selected_text = String::new();
-
-            if start_row == end_row {
-                // Single line selection
-                let row = self.editor_rows.get_editor_row(start_row);
-                let content = &row.row_content[start_col..end_col];
-                selected_text.push_str(content);
-            } else {
-                // Multi-line selection
-                for row_idx in start_row..=end_row {
-                    let row = self.editor_rows.get_editor_row(row_idx);
-                    if row_idx == start_row {
-                        // First line: from start_col to end
-                        selected_text.push_str(&row.row_content[start_col..]);
-                    } else if row_idx == end_row {
-                        // Last line: from beginning to end_col
-                        selected_text.push_str(&row.row_content[..end_col]);
-                    } else {
-                        // Middle lines: entire line
-                        selected_text.push_str(&row.row_content);
-                    }
-
-                    if row_idx < end_row {
-                        selected_text.push('\n');
+            match Reader.read_key() {
+                Ok(KeyEvent {
+                    code: KeyCode::Char('q') | KeyCode::Esc,
+                    ..
+                }) => break Ok(None),
+                Ok(KeyEvent {
+                    code: KeyCode::Enter,
+                    ..
+                }) => {
+                    let picked = self.cursor_controller.cursor_y;
+                    if !pick {
+                        break Ok(None);
+                    } else if picked < self.editor_rows.number_of_rows() {
+                        break Ok(Some(picked));
                     }
                 }
+                Ok(KeyEvent {
+                    code: direction @ (KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right | KeyCode::Home | KeyCode::End),
+                    ..
+                }) => self.move_cursor(direction),
+                Ok(KeyEvent {
+                    code: direction @ (KeyCode::PageUp | KeyCode::PageDown),
+                    ..
+                }) => {
+                    let direction = if direction == KeyCode::PageUp {
+                        KeyCode::Up
+                    } else {
+                        KeyCode::Down
+                    };
+                    (0..self.win_size.1).for_each(|_| self.move_cursor(direction));
+                }
+                Ok(_) => {}
+                Err(e) => break Err(e),
             }
+        };
 
-            CLIPBOARD.lock().unwrap().add(selected_text);
+        self.editor_rows.row_contents = rows;
+        self.cursor_controller = cursor_controller;
+        self.syntax_highlight = syntax_highlight;
+        (self.selection_start, self.selection_end, self.is_selecting) = selection;
+        self.carets = carets;
+        self.status_message.set_message(String::new());
+        result
+    }
+
+    /// Lets the user restore the buffer from one of the current file's backups.
+    /// 
+    /// All backups of the current file are listed, newest first. The chosen
+    /// backup replaces the buffer contents as a single undoable change; the
+    /// file on disk is left untouched until the buffer is saved.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `Ok(())` once the user has chosen or cancelled, or an `io::Error`.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let dir = std::env::temp_dir().join("ninja-restore-example");
+    /// std::fs::create_dir_all(&dir).unwrap();
+    /// let path = dir.join("notes.txt");
+    /// std::fs::write(&path, "draft\n").unwrap();
+    /// let mut rows = EditorRows { filename: Some(path.clone()), ..Default::default() };
+    /// rows.load(None, &None).unwrap();
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, None);
+    /// 
+    /// // Nothing has been backed up yet, so the buffer is left alone
+    /// output.restore_backup().unwrap();
+    /// assert_eq!(output.editor_rows.get_row(0), "draft");
+    /// assert_eq!(
+    ///     output.status_message.message().cloned(),
+    ///     Some(format!("No backups found for {}", path.display()))
+    /// );
+    /// ```
+    pub fn restore_backup(&mut self) -> io::Result<()> {
+        if !self.ensure_writable() {
+            return Ok(());
+        }
+        let Some(filename) = self.editor_rows.filename.clone() else {
+            self.status_message
+                .set_message("No file name: nothing to restore".into());
+            return Ok(());
+        };
+        let backups = backup::list(&filename, &self.config.behavior);
+        if backups.is_empty() {
             self.status_message
-                .set_message(format!("Copied {} characters", CLIPBOARD.lock().unwrap().get_top().unwrap().len()));
+                .set_message(format!("No backups found for {}", filename.display()));
+            return Ok(());
         }
+        let labels = backups.iter().map(|entry| entry.label()).collect();
+        let Some(index) = self.pick_line("Restore backup", labels)? else {
+            self.status_message.set_message("Restore aborted".into());
+            return Ok(());
+        };
+        let path = &backups[index].path;
+        let (contents, _) = self.editor_rows.format.encoding.decode(&fs::read(path)?);
+        self.push_undo();
+        self.clear_selection();
+        self.editor_rows
+            .replace_contents(&contents, &self.syntax_highlight);
+        self.cursor_controller.cursor_y = cmp::min(
+            self.cursor_controller.cursor_y,
+            self.editor_rows.number_of_rows(),
+        );
+        self.cursor_controller.cursor_x = 0;
+        self.dirty += 1;
+        self.pending_edit = false;
+        self.status_message
+            .set_message(format!("Restored backup {}", path.display()));
+        Ok(())
     }
 
-    pub fn cut_selection(&mut self) {
-        if let Some(((start_row, start_col), (end_row, end_col))) = self.get_selection_bounds() {
-            self.push_undo();
-            self.copy_selection();
-
-            // Remove the selected text
-            if start_row == end_row {
-                // Single line selection
-                self.remove_chars_from_row(start_row, start_col, end_col);
-            } else {
-                // Multi-line selection
-                // Handle the end row (remove from beginning to end_col)
-                self.remove_chars_from_row(end_row, 0, end_col);
-
-                // Handle the start row (remove from start_col to end)
-                self.remove_chars_from_row(start_row, start_col, self.editor_rows.get_editor_row(start_row).char_count());
+    /// Converts the buffer to a different line ending style.
+    /// 
+    /// The conversion takes effect when the file is next saved. It also
+    /// resolves mixed line endings, since every line is written with the
+    /// chosen style.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `line_ending` - The new line ending style
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::modules::file_format::LineEnding;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let rows = EditorRows::from_bytes(b"one\ntwo\r\n", &None);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, None);
+    /// output.set_line_ending(LineEnding::CrLf);
+    /// assert_eq!(output.editor_rows.format.line_ending, LineEnding::CrLf);
+    /// assert!(!output.editor_rows.format.mixed_line_endings);
+    /// assert_eq!(output.editor_rows.to_bytes().unwrap(), b"one\r\ntwo\r\n");
+    /// ```
+    pub fn set_line_ending(&mut self, line_ending: LineEnding) {
+        if !self.ensure_writable() {
+            return;
+        }
+        let format = &mut self.editor_rows.format;
+        if format.line_ending == line_ending && !format.mixed_line_endings {
+            self.status_message
+                .set_message(format!("Line endings are already {}", line_ending.name()));
+            return;
+        }
+        format.line_ending = line_ending;
+        format.mixed_line_endings = false;
+        self.dirty += 1;
+        self.status_message
+            .set_message(format!("Line endings converted to {}", line_ending.name()));
+    }
 
-                // Remove all rows in between
-                let rows_to_remove = end_row - start_row - 1;
-                for _ in 0..rows_to_remove {
-                    self.editor_rows.row_contents.remove(start_row + 1);
-                }
+    /// Lets the user pick a line ending style to convert the buffer to.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `Ok(())` once the user has chosen or cancelled, or an `io::Error`.
+    pub fn choose_line_ending(&mut self) -> io::Result<()> {
+        if !self.ensure_writable() {
+            return Ok(());
+        }
+        let labels = LineEnding::ALL
+            .iter()
+            .map(|line_ending| line_ending.description().to_string())
+            .collect();
+        let title = format!("Line endings (currently {})", self.editor_rows.format.label());
+        if let Some(index) = self.pick_line(&title, labels)? {
+            self.set_line_ending(LineEnding::ALL[index]);
+        }
+        Ok(())
+    }
 
-                // Join the start and end rows if they're now adjacent
-                if start_row + 1 < self.editor_rows.number_of_rows() {
-                    let end_row_content = self
-                        .editor_rows
-                        .get_editor_row(start_row + 1)
-                        .row_content
-                        .clone();
-                    let start_row_ref = self.editor_rows.get_editor_row_mut(start_row);
-                    start_row_ref.row_content.push_str(&end_row_content);
-                    EditorRows::render_row(start_row_ref);
-
-                    // Remove the end row since we joined it
-                    self.editor_rows.row_contents.remove(start_row + 1);
-                }
+    /// Re-reads the current file from disk using an encoding chosen by the user.
+    /// 
+    /// This is used when the detected encoding was wrong. Unsaved changes
+    /// are discarded after confirmation, and the undo history is cleared
+    /// since it refers to the previously decoded text.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `Ok(())` once the user has chosen or cancelled, or an `io::Error`.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// // A buffer that was never saved has nothing to re-read
+    /// let rows = EditorRows::from_bytes(b"caf\xC3\xA9\n", &None);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, None);
+    /// output.reopen_with_encoding().unwrap();
+    /// assert_eq!(output.editor_rows.get_row(0), "café");
+    /// assert_eq!(
+    ///     output.status_message.message().map(String::as_str),
+    ///     Some("No file name: nothing to reopen")
+    /// );
+    /// ```
+    pub fn reopen_with_encoding(&mut self) -> io::Result<()> {
+        if self.editor_rows.filename.is_none() {
+            self.status_message
+                .set_message("No file name: nothing to reopen".into());
+            return Ok(());
+        }
+        let labels = FileEncoding::ALL
+            .iter()
+            .map(|encoding| encoding.name().to_string())
+            .collect();
+        let title = format!(
+            "Reopen with encoding (currently {})",
+            self.editor_rows.format.encoding.name()
+        );
+        let Some(index) = self.pick_line(&title, labels)? else {
+            self.status_message.set_message("Reopen aborted".into());
+            return Ok(());
+        };
+        if self.dirty > 0
+            && self.prompt_choice("Discard unsaved changes and reopen? (y/n)", &['y', 'n'])?
+                != Some('y')
+        {
+            self.status_message.set_message("Reopen aborted".into());
+            return Ok(());
+        }
+
+        let encoding = FileEncoding::ALL[index];
+        self.editor_rows.load(Some(encoding), &self.syntax_highlight)?;
+        self.clear_selection();
+        self.undo_stack.clear();
+        self.pending_edit = false;
+        self.dirty = 0;
+        self.cursor_controller.cursor_y = cmp::min(
+            self.cursor_controller.cursor_y,
+            self.editor_rows.number_of_rows(),
+        );
+        self.cursor_controller.cursor_x = 0;
+        let message = if self.editor_rows.format.decode_errors {
+            format!("Reopened as {} (some bytes are not valid {})", encoding.name(), encoding.name())
+        } else {
+            format!("Reopened as {}", encoding.name())
+        };
+        self.status_message.set_message(message);
+        Ok(())
+    }
+
+    /// Replaces the buffer with the current contents of the file on disk.
+    /// 
+    /// This is used when another program changed the file. The previous
+    /// buffer contents are kept on the undo stack, so the reload can be undone.
+    /// The file is decoded with the buffer's current encoding.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `Ok(())` on success, or an `io::Error` if the file could not be read.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::modules::encoding::FileEncoding;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let path = std::env::temp_dir().join("ninja-reload-example.txt");
+    /// std::fs::write(&path, b"caf\xE9\n").unwrap();
+    /// let mut rows = EditorRows { filename: Some(path.clone()), ..Default::default() };
+    /// rows.load(Some(FileEncoding::Latin1), &None).unwrap();
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, None);
+    /// 
+    /// // Detection would read 0x80 as the Windows-1252 euro sign
+    /// std::fs::write(&path, b"\x80\n").unwrap();
+    /// output.reload_from_disk().unwrap();
+    /// assert_eq!(output.editor_rows.format.encoding, FileEncoding::Latin1);
+    /// assert_eq!(output.editor_rows.get_row(0), "\u{80}");
+    /// ```
+    pub fn reload_from_disk(&mut self) -> io::Result<()> {
+        self.push_undo();
+        let encoding = Some(self.editor_rows.format.encoding);
+        if let Err(e) = self.editor_rows.load(encoding, &self.syntax_highlight) {
+            self.undo_stack.pop();
+            return Err(e);
+        }
+        self.clear_selection();
+        self.pending_edit = false;
+        self.dirty = 0;
+        self.cursor_controller.cursor_y = cmp::min(
+            self.cursor_controller.cursor_y,
+            self.editor_rows.number_of_rows(),
+        );
+        let row_len = self
+            .editor_rows
+            .row_contents
+            .get(self.cursor_controller.cursor_y)
+            .map_or(0, |row| row.row_content.chars().count());
+        self.cursor_controller.cursor_x = cmp::min(self.cursor_controller.cursor_x, row_len);
+        Ok(())
+    }
+
+    // Selection and clipboard methods
+    pub fn start_selection(&mut self) {
+        self.block_anchor = None;
+        for caret in &mut self.carets {
+            caret.anchor = Some(caret.position());
+        }
+        self.is_selecting = true;
+        self.selection_start = Some((
+            self.cursor_controller.cursor_y,
+            self.cursor_controller.cursor_x,
+        ));
+        self.selection_end = Some((
+            self.cursor_controller.cursor_y,
+            self.cursor_controller.cursor_x,
+        ));
+    }
+
+    pub fn update_selection(&mut self) {
+        if self.is_selecting {
+            self.selection_end = Some((
+                self.cursor_controller.cursor_y,
+                self.cursor_controller.cursor_x,
+            ));
+        }
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.block_anchor = None;
+        for caret in &mut self.carets {
+            caret.anchor = None;
+        }
+        self.is_selecting = false;
+        self.selection_start = None;
+        self.selection_end = None;
+    }
+
+    pub fn has_selection(&self) -> bool {
+        if let (Some(start), Some(end)) = (self.selection_start, self.selection_end) {
+            start != end
+        } else {
+            false
+        }
+    }
+
+    pub fn is_selecting(&self) -> bool {
+        self.is_selecting
+    }
+
+    pub fn get_selection_bounds(&self) -> Option<((usize, usize), (usize, usize))> {
+        if let (Some(start), Some(end)) = (self.selection_start, self.selection_end) {
+            // Ensure start is before end
+            if (start.0 < end.0) || (start.0 == end.0 && start.1 <= end.1) {
+                Some((start, end))
+            } else {
+                Some((end, start))
             }
+        } else {
+            None
+        }
+    }
 
+    pub fn copy_selection(&mut self) {
+        if let Some(lines) = self.block_lines() {
+            let (width, height) = self
+                .block_bounds()
+                .map_or((0, 0), |(top, bottom, left, right)| (right - left, bottom - top + 1));
+            CLIPBOARD.lock().unwrap().add_rectangle(lines.join("\n"));
+            self.status_message
+                .set_message(format!("Copied {}x{} block", width, height));
+            return;
+        }
+        if let Some((start, end)) = self.get_selection_bounds() {
+            let selected_text = self.editor_rows.text_between(start, end);
+            let count = selected_text.chars().count();
+            CLIPBOARD.lock().unwrap().add(selected_text);
+            self.status_message
+                .set_message(format!("Copied {} characters", count));
+        }
+    }
+
+    pub fn cut_selection(&mut self) {
+        if !self.ensure_writable() {
+            return;
+        }
+        if let Some((top, bottom, left, right)) = self.block_bounds() {
+            self.push_undo();
+            self.copy_selection();
+            let bottom = cmp::min(bottom, self.editor_rows.number_of_rows().saturating_sub(1));
+            for row in top..=bottom {
+                let range = CursorController::char_range_for_columns(self.editor_rows.get_editor_row(row), left, right);
+                self.editor_rows.delete_between((row, range.start), (row, range.end));
+                self.update_syntax_highlighting(row);
+            }
             self.clear_selection();
-            self.cursor_controller.cursor_y = start_row;
-            self.cursor_controller.cursor_x = start_col;
+            self.cursor_controller.cursor_y = top;
+            self.cursor_controller.cursor_x = CursorController::cursor_x_for_render_x(self.editor_rows.get_editor_row(top), left);
             self.dirty += 1;
             self.pending_edit = false;
+            return;
+        }
+        if self.get_selection_bounds().is_some() {
+            self.push_undo();
+            self.copy_selection();
+            self.delete_selection();
+            self.pending_edit = false;
+        }
+    }
+
+    /// Removes the selection of every cursor, leaving each cursor where its
+    /// selection started.
+    /// 
+    /// Typing, deleting and pasting call this first so that they replace the
+    /// selected text. It does not record an undo state; callers push one so
+    /// the removal and the new text undo together.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `true` if any text was removed.
+    fn delete_selections(&mut self) -> bool {
+        let mut selections = self.cursor_selections();
+        selections.retain(|(start, end)| start != end);
+        if selections.is_empty() {
+            self.clear_selection();
+            return false;
+        }
+        // Moving each cursor to the end of its selection lets the removal be
+        // tracked like a backspace from there to the start
+        if let Some((_, end)) = self.get_selection_bounds() {
+            (self.cursor_controller.cursor_y, self.cursor_controller.cursor_x) = end;
+        }
+        for caret in &mut self.carets {
+            if let Some((_, end)) = caret.selection_bounds() {
+                caret.set_position(end);
+            }
+        }
+        self.clear_selection();
+        self.for_each_cursor(|output, _| {
+            let position = (output.cursor_controller.cursor_y, output.cursor_controller.cursor_x);
+            if let Some(&(start, end)) = selections.iter().find(|(_, end)| *end == position) {
+                output.editor_rows.delete_between(start, end);
+                output.update_syntax_highlighting(start.0);
+                (output.cursor_controller.cursor_y, output.cursor_controller.cursor_x) = start;
+                output.cursor_controller.reset_preferred_column();
+            }
+        });
+        self.dirty += 1;
+        true
+    }
+
+    /// Removes the selected text and places the cursor where it started.
+    /// 
+    /// This is the shared deletion step for cutting and for typing over a
+    /// selection. It does not record an undo state or touch the clipboard.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `true` if there was a selection to remove.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let rows = EditorRows::from_bytes("naïve café\n".as_bytes(), &None);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, None);
+    /// output.set_cursor_position(0, 2);
+    /// output.start_selection();
+    /// output.set_cursor_position(0, 7);
+    /// output.update_selection();
+    /// assert!(output.delete_selection());
+    /// assert_eq!(output.editor_rows.get_row(0), "naafé");
+    /// assert_eq!(output.cursor_controller.cursor_x, 2);
+    /// // Nothing is selected any more
+    /// assert!(!output.delete_selection());
+    /// ```
+    pub fn delete_selection(&mut self) -> bool {
+        let Some((start, end)) = self.get_selection_bounds() else {
+            return false;
+        };
+        self.clear_selection();
+        if start == end {
+            return false;
         }
+        self.editor_rows.delete_between(start, end);
+        let row = cmp::min(start.0, self.editor_rows.number_of_rows().saturating_sub(1));
+        self.update_syntax_highlighting(row);
+        (self.cursor_controller.cursor_y, self.cursor_controller.cursor_x) = start;
+        self.cursor_controller.reset_preferred_column();
+        self.dirty += 1;
+        true
     }
 
     pub fn paste_clipboard(&mut self) {
+        if !self.ensure_writable() {
+            return;
+        }
         if !CLIPBOARD.lock().unwrap().is_empty() {
-            self.push_undo();
             let clipboard_content = CLIPBOARD.lock().unwrap().get_top().unwrap().clone();
-            let mut chars = clipboard_content.chars().peekable();
+            if CLIPBOARD.lock().unwrap().is_rectangle(&clipboard_content) {
+                self.paste_rectangle(&clipboard_content);
+                return;
+            }
+            self.block_to_cursors();
+            self.push_undo();
+            self.delete_selections();
+            // With one clipboard line per cursor, each cursor gets its own line
+            let lines: Vec<&str> = clipboard_content.lines().collect();
+            let distribute = self.carets.len() + 1 == lines.len() && lines.len() > 1;
 
-            while let Some(ch) = chars.next() {
-                if ch == '\n' {
-                    self.insert_newline_without_undo();
-                } else {
-                    self.insert_char_without_undo(ch);
+            self.for_each_cursor(|output, index| {
+                let text = if distribute { lines[index] } else { clipboard_content.as_str() };
+                for ch in text.chars() {
+                    if ch == '\n' {
+                        output.insert_newline_without_undo();
+                    } else {
+                        output.insert_char_without_undo(ch);
+                    }
                 }
-            }
+            });
             self.status_message
-                .set_message(format!("Pasted {} characters", clipboard_content.len()));
+                .set_message(format!("Pasted {} characters", clipboard_content.chars().count()));
             self.pending_edit = false;
         }
     }
@@ -598,6 +1421,8 @@ impl Output {
                 .insert_row(self.editor_rows.number_of_rows(), String::new());
             self.dirty += 1;
         }
+        let (y, x) = (self.cursor_controller.cursor_y, self.cursor_controller.cursor_x);
+        self.shift_auto_closed(y, x, 1);
         self.editor_rows
             .get_editor_row_mut(self.cursor_controller.cursor_y)
             .insert_char(self.cursor_controller.cursor_x, ch);
@@ -606,7 +1431,12 @@ impl Output {
         self.dirty += 1;
     }
 
-    fn insert_newline_core(&mut self) {
+    /// Splits the row at the cursor and moves the cursor to the new row.
+    /// 
+    /// With auto-indent, the new row copies the indentation of the one it was
+    /// split from. `smart` also applies the language's indent rules, which
+    /// pasted text does without as it brings its own indentation.
+    fn insert_newline_core(&mut self, smart: bool) {
         if self.cursor_at_start() {
             // If cursor is at the beginning, check previous line for indentation
             let indent_level =
@@ -620,10 +1450,10 @@ impl Output {
                 };
 
             // Create new row with same indentation
-            let indent_spaces = " ".repeat(indent_level);
+            let indentation = self.indentation_for(indent_level);
+            self.cursor_controller.cursor_x = indentation.chars().count();
             self.editor_rows
-                .insert_row(self.cursor_controller.cursor_y, indent_spaces);
-            self.cursor_controller.cursor_x = indent_level;
+                .insert_row(self.cursor_controller.cursor_y, indentation);
         } else {
             // Get the current row content and calculate indentation before any mutable operations
             let current_row = self
@@ -634,6 +1464,11 @@ impl Output {
             } else {
                 0
             };
+            let (prefix, closing_line) = if smart && self.config.editor.auto_indent {
+                self.language_indentation(self.cursor_controller.cursor_y, indent_level)
+            } else {
+                (self.indentation_for(indent_level), None)
+            };
 
             let current_row = self
                 .editor_rows
@@ -650,15 +1485,24 @@ impl Output {
             EditorRows::render_row(current_row);
 
             // Create new line with proper indentation
-            let indent_spaces = " ".repeat(indent_level);
-            let mut new_line_content = indent_spaces;
-            new_line_content.push_str(&new_row_content);
+            let mut new_line_content = prefix;
+            let indent_length = new_line_content.chars().count();
+            match closing_line {
+                // A closing bracket right after the cursor moves to a line of its own
+                Some(mut closing_line) if new_row_content.trim_start().starts_with(self.indent_rules().dedent_on) => {
+                    closing_line.push_str(new_row_content.trim_start());
+                    self.editor_rows
+                        .insert_row(self.cursor_controller.cursor_y + 1, closing_line);
+                    self.update_syntax_highlighting(self.cursor_controller.cursor_y + 1);
+                }
+                _ => new_line_content.push_str(&new_row_content),
+            }
 
             self.editor_rows
                 .insert_row(self.cursor_controller.cursor_y + 1, new_line_content);
             self.update_syntax_highlighting(self.cursor_controller.cursor_y);
             self.update_syntax_highlighting(self.cursor_controller.cursor_y + 1);
-            self.cursor_controller.cursor_x = indent_level;
+            self.cursor_controller.cursor_x = indent_length;
         }
         self.cursor_controller.cursor_y += 1;
         self.dirty += 1;
@@ -670,27 +1514,697 @@ impl Output {
     }
 
     fn insert_newline_without_undo(&mut self) {
-        self.insert_newline_core();
+        self.insert_newline_core(false);
+    }
+
+    /// Checks whether a character is inside any selection.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `row` - The row index
+    /// * `col` - The character index within the row, as used by the cursor
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `true` if the character is selected by the primary cursor, an
+    /// extra cursor or the block selection.
+    pub fn is_position_selected(&self, row: usize, col: usize) -> bool {
+        let in_bounds = |((start_row, start_col), (end_row, end_col)): ((usize, usize), (usize, usize))| {
+            if row == start_row && row == end_row {
+                // Single line selection
+                col >= start_col && col < end_col
+            } else if row == start_row {
+                // First line of multi-line selection
+                col >= start_col
+            } else if row == end_row {
+                // Last line of multi-line selection
+                col < end_col
+            } else {
+                // Middle line of multi-line selection
+                row > start_row && row < end_row
+            }
+        };
+        self.get_selection_bounds().is_some_and(in_bounds)
+            || self.is_in_block(row, col)
+            || self
+                .carets
+                .iter()
+                .filter_map(Caret::selection_bounds)
+                .any(in_bounds)
+    }
+
+    /// Checks whether a character falls inside the block selection.
+    fn is_in_block(&self, row: usize, col: usize) -> bool {
+        let Some((top, bottom, left, right)) = self.block_bounds() else {
+            return false;
+        };
+        if row < top || row > bottom || row >= self.editor_rows.number_of_rows() {
+            return false;
+        }
+        let range = CursorController::char_range_for_columns(self.editor_rows.get_editor_row(row), left, right);
+        range.contains(&col)
+    }
+
+    /// Checks whether the primary cursor or any extra cursor has a selection.
+    pub fn has_any_selection(&self) -> bool {
+        self.has_selection()
+            || self.has_block_selection()
+            || self.carets.iter().any(|caret| caret.selection_bounds().is_some())
+    }
+
+    /// Starts or extends a block selection by moving the cursor.
+    /// 
+    /// A block selection is a rectangle between the cursor and the point
+    /// where the block was started, measured in display columns so that tabs
+    /// and wide characters line up as they appear on screen. Starting a block
+    /// ends any other selection and extra cursors.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `direction` - The arrow key to move the cursor with
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use crossterm::event::KeyCode;
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let rows = EditorRows::from_bytes(b"abcd\nefgh\n", &None);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, None);
+    /// output.set_cursor_position(0, 1);
+    /// output.extend_block_selection(KeyCode::Down);
+    /// output.extend_block_selection(KeyCode::Right);
+    /// assert!(output.has_block_selection());
+    /// // Typing replaces the block on every row
+    /// output.insert_char('x');
+    /// assert_eq!(output.editor_rows.get_row(0), "axcd");
+    /// assert_eq!(output.editor_rows.get_row(1), "exgh");
+    /// ```
+    pub fn extend_block_selection(&mut self, direction: KeyCode) {
+        if self.block_anchor.is_none() {
+            let column = self.cursor_controller.remember_column(&self.editor_rows);
+            let row = self.cursor_controller.cursor_y;
+            self.clear_selection();
+            self.carets.clear();
+            self.block_anchor = Some((row, column));
+        }
+        let column = self.cursor_controller.preferred_display_column(&self.editor_rows);
+        match direction {
+            // Sideways steps stay on the row and may go past its end
+            KeyCode::Left | KeyCode::Right => {
+                let mut probe = self.cursor_controller;
+                let width = match self.editor_rows.row_contents.get(probe.cursor_y) {
+                    Some(row) => CursorController::row_width(row),
+                    None => 0,
+                };
+                let column = match direction {
+                    KeyCode::Right if column < width => {
+                        // Step over the whole character, which may be a tab or wide
+                        let row = self.editor_rows.get_editor_row(probe.cursor_y);
+                        probe.cursor_x = CursorController::cursor_x_for_render_x(row, column) + 1;
+                        probe.display_column(&self.editor_rows)
+                    }
+                    KeyCode::Right => column + 1,
+                    _ if column > 0 && column <= width => {
+                        let row = self.editor_rows.get_editor_row(probe.cursor_y);
+                        probe.cursor_x = CursorController::cursor_x_for_render_x(row, column - 1);
+                        probe.display_column(&self.editor_rows)
+                    }
+                    _ => column.saturating_sub(1),
+                };
+                self.cursor_controller.place_at_column(column, &self.editor_rows);
+            }
+            _ => self
+                .cursor_controller
+                .move_cursor(direction, &self.editor_rows),
+        }
+        self.pending_edit = false;
+    }
+
+    /// Checks whether a block selection is active.
+    pub fn has_block_selection(&self) -> bool {
+        self.block_bounds().is_some()
+    }
+
+    /// Returns the block selection as `(top, bottom, left, right)`.
+    /// 
+    /// Rows are inclusive; `left` is the first selected display column and
+    /// `right` the display column just past the block.
+    fn block_bounds(&self) -> Option<(usize, usize, usize, usize)> {
+        let (anchor_row, anchor_column) = self.block_anchor?;
+        let row = self.cursor_controller.cursor_y;
+        let column = self.cursor_controller.preferred_display_column(&self.editor_rows);
+        if (row, column) == (anchor_row, anchor_column) {
+            return None;
+        }
+        Some((
+            cmp::min(row, anchor_row),
+            cmp::max(row, anchor_row),
+            cmp::min(column, anchor_column),
+            cmp::max(column, anchor_column),
+        ))
+    }
+
+    /// Returns the text inside the block selection, one string per row.
+    fn block_lines(&self) -> Option<Vec<String>> {
+        let (top, bottom, left, right) = self.block_bounds()?;
+        Some(
+            (top..=bottom)
+                .map(|row| match self.editor_rows.row_contents.get(row) {
+                    Some(it) => {
+                        let range = CursorController::char_range_for_columns(it, left, right);
+                        it.substring_by_chars(range.start, range.end)
+                    }
+                    None => String::new(),
+                })
+                .collect(),
+        )
+    }
+
+    /// Turns a block selection into one cursor per row.
+    /// 
+    /// Each row's cursor selects the part of the row inside the block, so
+    /// typing replaces the block's contents on every line. A block without
+    /// width gives plain cursors at the cursor's display column.
+    fn block_to_cursors(&mut self) {
+        let Some((top, bottom, left, right)) = self.block_bounds() else {
+            self.block_anchor = None;
+            return;
+        };
+        let column = self.cursor_controller.preferred_display_column(&self.editor_rows);
+        let primary_row = self.cursor_controller.cursor_y;
+        self.clear_selection();
+        self.carets.clear();
+        let bottom = cmp::min(bottom, self.editor_rows.number_of_rows().saturating_sub(1));
+        for row in top..=bottom {
+            let mut cursor = self.cursor_controller;
+            cursor.cursor_y = row;
+            cursor.place_at_column(column, &self.editor_rows);
+            let range = CursorController::char_range_for_columns(self.editor_rows.get_editor_row(row), left, right);
+            let anchor = if range.is_empty() {
+                None
+            } else if column == left {
+                cursor.cursor_x = range.start;
+                Some((row, range.end))
+            } else {
+                cursor.cursor_x = range.end;
+                Some((row, range.start))
+            };
+            if row == primary_row {
+                self.cursor_controller = cursor;
+                self.selection_start = anchor;
+                self.selection_end = anchor.map(|_| (row, cursor.cursor_x));
+            } else {
+                self.carets.push(Caret::new(cursor, anchor));
+            }
+        }
+    }
+
+    /// Pastes the lines of a copied block column-wise, starting at the cursor.
+    /// 
+    /// Each line goes into the next row at the cursor's display column. Short
+    /// rows are padded with spaces and rows are added past the end of the buffer.
+    /// Selected text, including a block selection, is replaced and the lines
+    /// go in where the first selection was.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `text` - The lines of the block, separated by newlines
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use crossterm::event::KeyCode;
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let rows = EditorRows::from_bytes(b"abcd\nefgh\n", &None);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, None);
+    /// output.set_cursor_position(0, 1);
+    /// output.extend_block_selection(KeyCode::Right);
+    /// output.extend_block_selection(KeyCode::Down);
+    /// output.paste_rectangle("XY\nZW");
+    /// assert_eq!(output.editor_rows.get_row(0), "aXYcd");
+    /// assert_eq!(output.editor_rows.get_row(1), "eZWgh");
+    /// assert_eq!(output.cursor_count(), 1);
+    /// ```
+    pub fn paste_rectangle(&mut self, text: &str) {
+        self.block_to_cursors();
+        self.push_undo();
+        self.delete_selections();
+        // One block is pasted, from the first of the cursors
+        let first = std::iter::once(self.cursor_controller)
+            .chain(self.carets.iter().map(|caret| caret.cursor))
+            .min_by_key(|cursor| (cursor.cursor_y, cursor.cursor_x));
+        if let Some(first) = first {
+            self.cursor_controller = first;
+        }
+        self.carets.clear();
+        let column = self.cursor_controller.display_column(&self.editor_rows);
+        let top = self.cursor_controller.cursor_y;
+        let lines: Vec<&str> = text.split('\n').collect();
+        for (offset, line) in lines.iter().enumerate() {
+            let row_index = top + offset;
+            if row_index >= self.editor_rows.number_of_rows() {
+                self.editor_rows
+                    .insert_row(self.editor_rows.number_of_rows(), String::new());
+            }
+            let row = self.editor_rows.get_editor_row_mut(row_index);
+            let width = CursorController::row_width(row);
+            if width < column {
+                row.row_content.push_str(&" ".repeat(column - width));
+            }
+            let index = CursorController::cursor_x_for_render_x(row, column);
+            let mut chars: Vec<char> = row.row_content.chars().collect();
+            chars.splice(index..index, line.chars());
+            row.row_content = chars.into_iter().collect();
+            EditorRows::render_row(row);
+            self.update_syntax_highlighting(row_index);
+            if offset == 0 {
+                self.cursor_controller.cursor_x = index + line.chars().count();
+            }
+        }
+        self.dirty += 1;
+        self.pending_edit = false;
+        self.status_message
+            .set_message(format!("Pasted {}-line block", lines.len()));
+    }
+
+    /// Returns the number of cursors, including the primary cursor.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let rows = EditorRows::from_bytes(b"one\ntwo\n", &None);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, None);
+    /// assert_eq!(output.cursor_count(), 1);
+    /// output.add_cursor_vertical(false);
+    /// assert_eq!(output.cursor_count(), 2);
+    /// ```
+    pub fn cursor_count(&self) -> usize {
+        self.carets.len() + 1
+    }
+
+    /// Runs an edit once for every cursor, as a single change to the buffer.
+    /// 
+    /// The edit is applied at each cursor in turn, starting from the one
+    /// furthest down the buffer. After each edit the other cursors are moved
+    /// to account for the text that was inserted or deleted. The edit is
+    /// given the cursor's index in document order.
+    fn for_each_cursor(&mut self, mut edit: impl FnMut(&mut Output, usize)) {
+        if self.carets.is_empty() {
+            edit(self, 0);
+            return;
+        }
+        let mut cursors = std::mem::take(&mut self.carets);
+        cursors.insert(0, Caret::new(self.cursor_controller, None));
+        let mut order: Vec<usize> = (0..cursors.len()).collect();
+        order.sort_by_key(|&index| cursors[index].position());
+
+        for (document_index, &index) in order.iter().enumerate().rev() {
+            let from = cursors[index].position();
+            let rows = self.editor_rows.number_of_rows();
+            let length = self.row_length(from.0);
+            self.cursor_controller = cursors[index].cursor;
+            edit(self, document_index);
+            let to = (self.cursor_controller.cursor_y, self.cursor_controller.cursor_x);
+            cursors[index].cursor = self.cursor_controller;
+            // An edit within one row can change more text than the cursor moved
+            // over, such as a closer typed with its opener, so the row's change
+            // in length is what moves the cursors after it
+            let within_row = from.0 == to.0 && self.editor_rows.number_of_rows() == rows;
+            let delta = self.row_length(from.0) as isize - length as isize;
+            let shift = |position| {
+                if within_row {
+                    multi_cursor::shift_in_row(position, from.0, from.1.min(to.1), delta)
+                } else {
+                    multi_cursor::shift_position(position, from, to)
+                }
+            };
+            for (other_index, other) in cursors.iter_mut().enumerate() {
+                if other_index != index {
+                    other.set_position(shift(other.position()));
+                }
+                other.anchor = other.anchor.map(shift);
+            }
+        }
+
+        self.cursor_controller = cursors.remove(0).cursor;
+        self.carets = cursors;
+        self.merge_carets();
+    }
+
+    /// Returns the number of characters on a row, or 0 past the last row.
+    fn row_length(&self, row: usize) -> usize {
+        self.editor_rows.row_contents.get(row).map_or(0, |row| row.char_count())
+    }
+
+    /// Removes extra cursors that share a position with another cursor.
+    fn merge_carets(&mut self) {
+        let mut seen = vec![(self.cursor_controller.cursor_y, self.cursor_controller.cursor_x)];
+        self.carets.retain(|caret| {
+            let position = caret.position();
+            if seen.contains(&position) {
+                false
+            } else {
+                seen.push(position);
+                true
+            }
+        });
+    }
+
+    /// Adds a cursor on the line above the topmost cursor, or below the bottommost one.
+    /// 
+    /// The new cursor keeps the display column of the cursor it was added from.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `up` - `true` to add the cursor above, `false` to add it below
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let rows = EditorRows::from_bytes(b"one\ntwo\nthree\n", &None);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, None);
+    /// output.set_cursor_position(0, 1);
+    /// output.add_cursor_vertical(false);
+    /// output.add_cursor_vertical(false);
+    /// // There is no line below the last one
+    /// output.add_cursor_vertical(false);
+    /// assert_eq!(output.cursor_count(), 3);
+    /// output.insert_char('-');
+    /// assert_eq!(output.editor_rows.get_row(0), "o-ne");
+    /// assert_eq!(output.editor_rows.get_row(1), "t-wo");
+    /// assert_eq!(output.editor_rows.get_row(2), "t-hree");
+    /// ```
+    pub fn add_cursor_vertical(&mut self, up: bool) {
+        let cursors = std::iter::once(self.cursor_controller).chain(self.carets.iter().map(|caret| caret.cursor));
+        let extreme = if up {
+            cursors.min_by_key(|cursor| cursor.cursor_y)
+        } else {
+            cursors.max_by_key(|cursor| cursor.cursor_y)
+        };
+        let Some(mut cursor) = extreme else {
+            return;
+        };
+        let row = cursor.cursor_y;
+        cursor.move_cursor(if up { KeyCode::Up } else { KeyCode::Down }, &self.editor_rows);
+        if cursor.cursor_y == row || cursor.cursor_y >= self.editor_rows.number_of_rows() {
+            self.status_message
+                .set_message(format!("No line {} to add a cursor on", if up { "above" } else { "below" }));
+            return;
+        }
+        self.carets.push(Caret::new(cursor, None));
+        self.merge_carets();
+        self.status_message
+            .set_message(format!("{} cursors (ESC = Single cursor)", self.cursor_count()));
+    }
+
+    /// Returns the text to look for when adding cursors at occurrences.
+    /// 
+    /// This is the primary selection if it lies on one line. Without a
+    /// selection the word under the cursor is selected and used instead.
+    fn occurrence_needle(&mut self) -> Option<Vec<char>> {
+        if let Some(((start_row, start_col), (end_row, end_col))) = self.get_selection_bounds()
+            && start_row == end_row
+            && start_col < end_col
+        {
+            let chars: Vec<char> = self.editor_rows.get_editor_row(start_row).row_content.chars().collect();
+            return Some(chars[start_col..cmp::min(end_col, chars.len())].to_vec());
+        }
+        let row = self.cursor_controller.cursor_y;
+        if row >= self.editor_rows.number_of_rows() {
+            return None;
+        }
+        let chars: Vec<char> = self.editor_rows.get_editor_row(row).row_content.chars().collect();
+        let is_word = |c: &char| c.is_alphanumeric() || *c == '_';
+        let x = cmp::min(self.cursor_controller.cursor_x, chars.len());
+        let start = x - chars[..x].iter().rev().take_while(|c| is_word(c)).count();
+        let end = x + chars[x..].iter().take_while(|c| is_word(c)).count();
+        if start == end {
+            return None;
+        }
+        self.selection_start = Some((row, start));
+        self.selection_end = Some((row, end));
+        self.is_selecting = true;
+        self.cursor_controller.cursor_x = end;
+        Some(chars[start..end].to_vec())
+    }
+
+    /// Finds every occurrence of `needle` in the buffer, as `(row, column)` of its first character.
+    fn find_occurrences(&self, needle: &[char]) -> Vec<(usize, usize)> {
+        let mut occurrences = Vec::new();
+        for (row_index, row) in self.editor_rows.row_contents.iter().enumerate() {
+            let chars: Vec<char> = row.row_content.chars().collect();
+            let mut column = 0;
+            while column + needle.len() <= chars.len() {
+                if chars[column..column + needle.len()] == *needle {
+                    occurrences.push((row_index, column));
+                    column += needle.len();
+                } else {
+                    column += 1;
+                }
+            }
+        }
+        occurrences
+    }
+
+    /// Returns the selection of every cursor, as `(start, end)` pairs.
+    fn cursor_selections(&self) -> Vec<((usize, usize), (usize, usize))> {
+        self.get_selection_bounds()
+            .into_iter()
+            .chain(self.carets.iter().filter_map(Caret::selection_bounds))
+            .collect()
+    }
+
+    /// Adds a cursor that selects an occurrence of `needle` starting at `start`.
+    fn add_occurrence_caret(&mut self, start: (usize, usize), length: usize) {
+        let mut cursor = self.cursor_controller;
+        cursor.reset_preferred_column();
+        cursor.cursor_y = start.0;
+        cursor.cursor_x = start.1 + length;
+        self.carets.push(Caret::new(cursor, Some(start)));
+    }
+
+    /// Adds a cursor at the next occurrence of the selected text.
+    /// 
+    /// Without a selection, the word under the cursor is selected first.
+    /// The search continues after the most recently added cursor and wraps
+    /// around the end of the buffer.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let rows = EditorRows::from_bytes(b"foo bar foo\nfoo\n", &None);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, None);
+    /// // The first call selects the word under the cursor
+    /// output.add_next_occurrence();
+    /// assert_eq!(output.get_selection_bounds(), Some(((0, 0), (0, 3))));
+    /// assert_eq!(output.cursor_count(), 1);
+    /// output.add_next_occurrence();
+    /// assert_eq!(output.cursor_count(), 2);
+    /// output.insert_char('x');
+    /// assert_eq!(output.editor_rows.get_row(0), "x bar x");
+    /// assert_eq!(output.editor_rows.get_row(1), "foo");
+    /// ```
+    pub fn add_next_occurrence(&mut self) {
+        let had_selection = self.has_selection();
+        let Some(needle) = self.occurrence_needle() else {
+            self.status_message
+                .set_message("Select text on one line to add cursors at its occurrences".into());
+            return;
+        };
+        if !had_selection {
+            return;
+        }
+        let selections = self.cursor_selections();
+        let last = self
+            .carets
+            .last()
+            .map(Caret::position)
+            .unwrap_or((self.cursor_controller.cursor_y, self.cursor_controller.cursor_x));
+        let occurrences = self.find_occurrences(&needle);
+        let next = occurrences
+            .iter()
+            .filter(|&&start| start >= last)
+            .chain(occurrences.iter())
+            .find(|&&start| !selections.iter().any(|&(selected, _)| selected == start));
+        match next {
+            Some(&start) => {
+                self.add_occurrence_caret(start, needle.len());
+                self.status_message
+                    .set_message(format!("{} cursors (ESC = Single cursor)", self.cursor_count()));
+            }
+            None => self
+                .status_message
+                .set_message("No more occurrences".into()),
+        }
+    }
+
+    /// Adds a cursor at every occurrence of the selected text.
+    /// 
+    /// Without a selection, the word under the cursor is used.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let rows = EditorRows::from_bytes(b"foo bar foo\nfoo\n", &None);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, None);
+    /// output.set_cursor_position(0, 9);
+    /// output.select_all_occurrences();
+    /// assert_eq!(output.cursor_count(), 3);
+    /// output.insert_char('x');
+    /// assert_eq!(output.editor_rows.get_row(0), "x bar x");
+    /// assert_eq!(output.editor_rows.get_row(1), "x");
+    /// ```
+    pub fn select_all_occurrences(&mut self) {
+        let Some(needle) = self.occurrence_needle() else {
+            self.status_message
+                .set_message("Select text on one line to add cursors at its occurrences".into());
+            return;
+        };
+        let primary = self.get_selection_bounds().map(|(start, _)| start);
+        self.carets.clear();
+        for start in self.find_occurrences(&needle) {
+            if Some(start) != primary {
+                self.add_occurrence_caret(start, needle.len());
+            }
+        }
+        self.status_message
+            .set_message(format!("{} occurrences selected (ESC = Single cursor)", self.cursor_count()));
+    }
+
+    /// Splits a selection spanning several lines into one cursor per line.
+    /// 
+    /// Each cursor selects its line's part of the original selection and
+    /// sits at the end of it.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let rows = EditorRows::from_bytes(b"a\nbb\nccc\n", &None);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, None);
+    /// output.start_selection();
+    /// output.set_cursor_position(2, 2);
+    /// output.update_selection();
+    /// output.split_selection_into_lines();
+    /// assert_eq!(output.cursor_count(), 3);
+    /// // Typing replaces each line's part of the selection
+    /// output.insert_char(';');
+    /// assert_eq!(output.editor_rows.get_row(0), ";");
+    /// assert_eq!(output.editor_rows.get_row(1), ";");
+    /// assert_eq!(output.editor_rows.get_row(2), ";c");
+    /// ```
+    pub fn split_selection_into_lines(&mut self) {
+        let Some(((start_row, start_col), (end_row, end_col))) = self.get_selection_bounds()
+            .filter(|(start, end)| start.0 < end.0)
+        else {
+            self.status_message
+                .set_message("Select several lines to split into cursors".into());
+            return;
+        };
+        let line_end = |row: usize| self.editor_rows.get_editor_row(row).char_count();
+        let first_end = line_end(start_row);
+        let lines: Vec<((usize, usize), (usize, usize))> = (start_row + 1..=end_row)
+            .map(|row| ((row, 0), (row, if row == end_row { end_col } else { line_end(row) })))
+            .collect();
+        self.carets.clear();
+        self.selection_start = Some((start_row, start_col));
+        self.selection_end = Some((start_row, first_end));
+        self.cursor_controller.cursor_y = start_row;
+        self.cursor_controller.cursor_x = first_end;
+        self.cursor_controller.reset_preferred_column();
+        for (anchor, (row, column)) in lines {
+            let mut cursor = self.cursor_controller;
+            cursor.cursor_y = row;
+            cursor.cursor_x = column;
+            self.carets.push(Caret::new(cursor, Some(anchor)));
+        }
+        self.status_message
+            .set_message(format!("{} cursors (ESC = Single cursor)", self.cursor_count()));
+    }
+
+    /// Removes every cursor except the primary one, or clears the selection
+    /// if there is only one cursor.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let rows = EditorRows::from_bytes(b"foo foo\n", &None);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, None);
+    /// output.select_all_occurrences();
+    /// assert_eq!(output.cursor_count(), 2);
+    /// // The first Escape removes the extra cursors, the second the selection
+    /// output.clear_cursors();
+    /// assert_eq!(output.cursor_count(), 1);
+    /// assert!(output.has_selection());
+    /// output.clear_cursors();
+    /// assert!(!output.has_selection());
+    /// ```
+    pub fn clear_cursors(&mut self) {
+        if self.carets.is_empty() {
+            self.clear_selection();
+        } else {
+            self.carets.clear();
+            self.status_message.set_message(String::new());
+        }
     }
 
-    pub fn is_position_selected(&self, row: usize, col: usize) -> bool {
-        if let Some(((start_row, start_col), (end_row, end_col))) = self.get_selection_bounds() {
-            if row == start_row && row == end_row {
-                // Single line selection
-                col >= start_col && col < end_col
-            } else if row == start_row {
-                // First line of multi-line selection
-                col >= start_col
-            } else if row == end_row {
-                // Last line of multi-line selection
-                col < end_col
-            } else {
-                // Middle line of multi-line selection
-                row > start_row && row < end_row
+    /// Draws the extra cursors in reverse video on top of the rendered rows.
+    fn draw_carets(&mut self, gutter_width: usize) -> crossterm::Result<()> {
+        let content_width = self.win_size.0.saturating_sub(gutter_width);
+        let row_offset = self.cursor_controller.row_offset;
+        let column_offset = self.cursor_controller.column_offset;
+        for caret in &self.carets {
+            let (row, column) = caret.position();
+            let render_x = caret.cursor.display_column(&self.editor_rows);
+            if row < row_offset
+                || row >= row_offset + self.win_size.1
+                || render_x < column_offset
+                || render_x - column_offset >= content_width
+            {
+                continue;
             }
-        } else {
-            false
+            let glyph = match self.editor_rows.row_contents.get(row).and_then(|it| it.row_content.chars().nth(column)) {
+                Some(c) if c != '\t' => c,
+                _ => ' ',
+            };
+            queue!(
+                self.editor_contents,
+                cursor::MoveTo((render_x - column_offset + gutter_width) as u16, (row - row_offset) as u16),
+                style::SetAttribute(style::Attribute::Reverse),
+                style::Print(glyph),
+                style::SetAttribute(style::Attribute::Reset)
+            )?;
         }
+        Ok(())
     }
 
     fn draw_rows(&mut self) {
@@ -702,6 +2216,7 @@ impl Output {
             0
         };
         let content_width = screen_columns.saturating_sub(gutter_width);
+        let brackets = self.cursor_controller.bracket_pair(&self.editor_rows);
 
         for i in 0..screen_rows {
             let file_row = i + self.cursor_controller.row_offset;
@@ -735,14 +2250,13 @@ impl Output {
                 let render = &row.render;
                 let column_offset = self.cursor_controller.column_offset;
 
-                // Use character-based operations for UTF-8 safety
+                // Use character-based operations for UTF-8 safety, keeping
+                // whole grapheme clusters that fit in the visible columns
                 let render_chars: Vec<char> = render.chars().collect();
-                let len = cmp::min(
-                    render_chars.len().saturating_sub(column_offset),
-                    content_width,
-                );
-                let start = if len == 0 { 0 } else { column_offset };
-                let end = start + len;
+                let (visible, visible_column) =
+                    graphemes::visible_range(render, column_offset, content_width);
+                let (start, end) = (visible.start, visible.end);
+                let len = end - start;
 
                 // Create the rendered string using character operations
                 let render = render_chars[start..end].iter().collect::<String>();
@@ -752,68 +2266,88 @@ impl Output {
                     let line_num = format!("{:>5} ", file_row + 1);
                     self.editor_contents.push_str(&line_num);
                 }
+                // Blank the columns of a wide character cut by the left edge
+                (column_offset..visible_column).for_each(|_| self.editor_contents.push(' '));
+
+                // The bracket at the cursor and its partner, as indices into the visible text
+                let bracket_indices: Vec<usize> = brackets
+                    .into_iter()
+                    .flat_map(|(bracket, partner)| [bracket, partner])
+                    .filter(|&(bracket_row, _)| bracket_row == file_row)
+                    .map(|(_, bracket_column)| row.render_index_for_char_index(bracket_column))
+                    .filter(|render_index| (start..end).contains(render_index))
+                    .map(|render_index| render_index - start)
+                    .collect();
 
                 // Draw the actual content with syntax highlighting
-                if self.config.syntax.enable_syntax_highlighting {
-                    if let Some(syntax_highlight) = &self.syntax_highlight {
-                        // Ensure highlight array has enough elements
-                        let highlight_slice =
-                            if start < row.highlight.len() && end <= row.highlight.len() {
-                                &row.highlight[start..end]
-                            } else {
-                                // Fallback to normal highlighting if indices are out of bounds
-                                &[]
-                            };
+                let syntax_highlight = self
+                    .syntax_highlight
+                    .as_ref()
+                    .filter(|_| self.config.syntax.enable_syntax_highlighting);
+                if let Some(syntax_highlight) = syntax_highlight {
+                    // Ensure highlight array has enough elements
+                    let highlight_slice =
+                        if start < row.highlight.len() && end <= row.highlight.len() {
+                            &row.highlight[start..end]
+                        } else {
+                            // Fallback to normal highlighting if indices are out of bounds
+                            &[]
+                        };
 
-                        // Apply selection highlighting over syntax highlighting
-                        let mut final_highlights = highlight_slice.to_vec();
-                        if self.has_selection() {
-                            for (char_idx, _) in render.char_indices().enumerate() {
-                                let actual_char_idx = start + char_idx;
-                                if self.is_position_selected(file_row, actual_char_idx) {
-                                    if char_idx < final_highlights.len() {
-                                        final_highlights[char_idx] = HighlightType::Selection;
-                                    } else {
-                                        final_highlights.push(HighlightType::Selection);
-                                    }
+                    // Mark the bracket at the cursor and its partner
+                    let mut final_highlights = highlight_slice.to_vec();
+                    for &index in &bracket_indices {
+                        if final_highlights.len() <= index {
+                            final_highlights.resize(index + 1, HighlightType::Normal);
+                        }
+                        final_highlights[index] = HighlightType::MatchingBracket;
+                    }
+
+                    // Apply selection highlighting over syntax highlighting
+                    if self.has_any_selection() {
+                        for char_idx in 0..len {
+                            let actual_char_idx = row.char_index_for_render_index(start + char_idx);
+                            if self.is_position_selected(file_row, actual_char_idx) {
+                                if char_idx < final_highlights.len() {
+                                    final_highlights[char_idx] = HighlightType::Selection;
+                                } else {
+                                    final_highlights.push(HighlightType::Selection);
                                 }
                             }
                         }
-
-                        syntax_highlight.color_row(
-                            &render,
-                            &final_highlights,
-                            &mut self.editor_contents,
-                        );
-                    } else {
-                        self.editor_contents.push_str(&render);
                     }
-                } else {
-                    // No syntax highlighting, but still apply selection highlighting
-                    if self.has_selection() {
-                        let mut current_color = Color::Reset;
-                        for (char_idx, c) in render.char_indices() {
-                            let actual_char_idx = start + char_idx;
-                            let color = if self.is_position_selected(file_row, actual_char_idx) {
-                                Color::White
-                            } else {
-                                Color::Reset
-                            };
 
-                            if current_color != color {
-                                current_color = color;
-                                let _ =
-                                    queue!(self.editor_contents, style::SetForegroundColor(color));
-                            }
-                            self.editor_contents.push(c);
+                    syntax_highlight.color_row(
+                        &render,
+                        &final_highlights,
+                        &mut self.editor_contents,
+                    );
+                } else if self.has_any_selection() || !bracket_indices.is_empty() {
+                    // No syntax highlighting, but still show the selection and matching brackets
+                    let mut current_color = Color::Reset;
+                    for (char_idx, c) in render.chars().enumerate() {
+                        let actual_char_idx = row.char_index_for_render_index(start + char_idx);
+                        let color = if self.is_position_selected(file_row, actual_char_idx) {
+                            Color::White
+                        } else if bracket_indices.contains(&char_idx) {
+                            Color::Red
+                        } else {
+                            Color::Reset
+                        };
+
+                        if current_color != color {
+                            current_color = color;
+                            let _ =
+                                queue!(self.editor_contents, style::SetForegroundColor(color));
                         }
-                        let _ = queue!(
-                            self.editor_contents,
-                            style::SetForegroundColor(Color::Reset)
-                        );
-                    } else {
-                        self.editor_contents.push_str(&render);
+                        self.editor_contents.push(c);
                     }
+                    let _ = queue!(
+                        self.editor_contents,
+                        style::SetForegroundColor(Color::Reset)
+                    );
+                } else {
+                    self.editor_contents.push_str(&render);
                 }
             }
             queue!(
@@ -826,8 +2360,29 @@ impl Output {
     }
 
     pub fn move_cursor(&mut self, direction: KeyCode) {
+        self.block_anchor = None;
         self.cursor_controller
             .move_cursor(direction, &self.editor_rows);
+        for caret in &mut self.carets {
+            caret.cursor.move_cursor(direction, &self.editor_rows);
+        }
+        self.merge_carets();
+        self.pending_edit = false;
+    }
+
+    /// Moves the cursor by a word, paragraph, file or bracket motion.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `motion` - The motion to perform
+    pub fn apply_motion(&mut self, motion: CursorMotion) {
+        self.block_anchor = None;
+        self.cursor_controller
+            .apply_motion(motion, &self.editor_rows);
+        for caret in &mut self.carets {
+            caret.cursor.apply_motion(motion, &self.editor_rows);
+        }
+        self.merge_carets();
         self.pending_edit = false;
     }
 
@@ -841,12 +2396,15 @@ impl Output {
             .scroll(&self.editor_rows, gutter_width);
         queue!(self.editor_contents, cursor::Hide, cursor::MoveTo(0, 0))?;
         self.draw_rows();
+        self.draw_carets(gutter_width)?;
         StatusBar::draw_status_bar(
             &mut self.editor_contents,
             self.win_size,
             &self.editor_rows.filename,
             self.dirty,
+            self.read_only,
             &self.syntax_highlight,
+            &self.editor_rows.format,
             &self.cursor_controller,
         );
         MessageBar::draw_message_bar(
@@ -867,23 +2425,57 @@ impl Output {
 
     // Undo stack methods
     pub fn push_undo(&mut self) {
+        // Every edit starts here, and edits end vertical movement
+        self.cursor_controller.reset_preferred_column();
+        // Closers are only stepped over while the typing that inserted them goes on
+        self.auto_closed.clear();
         // Store a deep copy of the editor state
-        self.undo_stack.push((
-            self.editor_rows.row_contents.clone(),
-            self.cursor_controller,
-            self.dirty,
-        ));
+        self.undo_stack.push(UndoState {
+            rows: self.editor_rows.row_contents.clone(),
+            cursor: self.cursor_controller,
+            dirty: self.dirty,
+            carets: self.carets.clone(),
+            selection_start: self.selection_start,
+            selection_end: self.selection_end,
+            is_selecting: self.is_selecting,
+            block_anchor: self.block_anchor,
+        });
         // Limit undo stack size if desired
         if self.undo_stack.len() > 100 {
             self.undo_stack.remove(0);
         }
     }
 
+    /// Restores the buffer, cursors and selection from before the last edit.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let rows = EditorRows::from_bytes(b"a a\n", &None);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, None);
+    /// output.select_all_occurrences();
+    /// output.insert_char('b');
+    /// assert_eq!(output.editor_rows.get_row(0), "b b");
+    /// output.pop_undo();
+    /// assert_eq!(output.editor_rows.get_row(0), "a a");
+    /// assert_eq!(output.cursor_count(), 2);
+    /// assert!(output.has_any_selection());
+    /// ```
     pub fn pop_undo(&mut self) {
-        if let Some((rows, cursor, dirty)) = self.undo_stack.pop() {
-            self.editor_rows.row_contents = rows;
-            self.cursor_controller = cursor;
-            self.dirty = dirty;
+        if let Some(state) = self.undo_stack.pop() {
+            self.editor_rows.row_contents = state.rows;
+            self.cursor_controller = state.cursor;
+            self.dirty = state.dirty;
+            self.carets = state.carets;
+            self.selection_start = state.selection_start;
+            self.selection_end = state.selection_end;
+            self.is_selecting = state.is_selecting;
+            self.block_anchor = state.block_anchor;
+            self.auto_closed.clear();
             // Re-render all rows for syntax highlighting
             if let Some(it) = self.syntax_highlight.as_ref() {
                 for i in 0..self.editor_rows.number_of_rows() {
@@ -894,50 +2486,602 @@ impl Output {
         self.pending_edit = false;
     }
 
+    /// Returns the text for one level of indentation in this buffer.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `tab_size` spaces with soft tabs, or a tab character otherwise.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let mut config = NinjaConfig::default();
+    /// config.editor.tab_size = 2;
+    /// let rows = EditorRows::from_bytes(b"x\n", &None);
+    /// let output = Output::with_rows(config.clone(), rows, None);
+    /// assert_eq!(output.indent_unit(), "  ");
+    /// 
+    /// // A file indented with tabs keeps using them
+    /// let rows = EditorRows::from_bytes(b"int main() {\n\treturn 0;\n}\n", &None);
+    /// let output = Output::with_rows(config, rows, None);
+    /// assert_eq!(output.indent_unit(), "\t");
+    /// ```
+    pub fn indent_unit(&self) -> String {
+        if self.config.editor.soft_tabs {
+            " ".repeat(self.config.editor.tab_size)
+        } else {
+            "\t".to_string()
+        }
+    }
+
+    /// Returns leading whitespace that reaches a display column.
+    /// 
+    /// With soft tabs this is all spaces; otherwise it is as many tabs as fit,
+    /// followed by spaces for the remainder.
+    fn indentation_for(&self, columns: usize) -> String {
+        let tab_size = self.config.editor.tab_size.max(1);
+        if self.config.editor.soft_tabs {
+            " ".repeat(columns)
+        } else {
+            "\t".repeat(columns / tab_size) + &" ".repeat(columns % tab_size)
+        }
+    }
+
+    /// Returns the indent rules of the buffer's language.
+    /// 
+    /// Buffers without a language get no rules, so indentation is only copied.
+    fn indent_rules(&self) -> IndentRules {
+        match self.syntax_highlight.as_ref() {
+            Some(it) => it.indent_rules(),
+            None => IndentRules { indent_after: &[], indent_words: &[], dedent_on: &[] },
+        }
+    }
+
+    /// Works out how to indent a line split at the cursor.
+    /// 
+    /// Inside a block comment the new line continues the comment with ` * `.
+    /// Otherwise it is indented one level deeper if the text before the
+    /// cursor ends with one of the language's indenting characters or words.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `row` - The row being split at the cursor
+    /// * `indent_level` - The display width of that row's indentation
+    /// 
+    /// # Returns
+    /// 
+    /// Returns the text to start the new line with and, when the line was
+    /// indented, the indentation for a closing bracket that follows the cursor.
+    fn language_indentation(&self, row: usize, indent_level: usize) -> (String, Option<String>) {
+        let current_row = self.editor_rows.get_editor_row(row);
+        let before = current_row.substring_by_chars(0, self.cursor_controller.cursor_x);
+        let leading = before.trim_start();
+
+        let in_block_comment = self
+            .syntax_highlight
+            .as_ref()
+            .and_then(|it| it.multiline_comment())
+            .is_some_and(|(start, _)| start == "/*")
+            && current_row.is_comment
+            && (leading.starts_with("/*") || leading.starts_with('*'));
+        if in_block_comment {
+            let mut prefix = self.indentation_for(indent_level);
+            prefix.push_str(if leading.starts_with("/*") { " * " } else { "* " });
+            return (prefix, None);
+        }
+
+        if self.indent_rules().indents_after(&before) {
+            let indented = indent_level + self.config.editor.tab_size;
+            (self.indentation_for(indented), Some(self.indentation_for(indent_level)))
+        } else {
+            (self.indentation_for(indent_level), None)
+        }
+    }
+
+    /// Lines a closing bracket typed as the first thing on the cursor's line
+    /// up with the line holding its opening bracket.
+    /// 
+    /// The line is only ever dedented, so a closer that is already at or
+    /// left of the opener's indentation stays where it was typed.
+    fn dedent_closing_char(&mut self, ch: char) {
+        if !self.config.editor.auto_indent || !self.indent_rules().dedent_on.contains(&ch) {
+            return;
+        }
+        let (y, x) = (self.cursor_controller.cursor_y, self.cursor_controller.cursor_x);
+        let content = self.editor_rows.get_row(y);
+        let leading: String = content.chars().take(x.saturating_sub(1)).collect();
+        if leading.is_empty() || !leading.chars().all(|c| c == ' ' || c == '\t') {
+            return;
+        }
+        let mut closer = self.cursor_controller;
+        closer.cursor_x = x - 1;
+        let Some((_, (opener_row, _))) = closer.bracket_pair(&self.editor_rows) else {
+            return;
+        };
+        let level = self.get_indentation_level(&leading);
+        let target = self.get_indentation_level(self.editor_rows.get_row(opener_row));
+        if level <= target {
+            return;
+        }
+        let indentation = self.indentation_for(target);
+        let length = leading.chars().count();
+        self.editor_rows.delete_between((y, 0), (y, length));
+        let row = self.editor_rows.get_editor_row_mut(y);
+        row.row_content.insert_str(0, &indentation);
+        EditorRows::render_row(row);
+        self.update_syntax_highlighting(y);
+        self.cursor_controller.cursor_x = x - length + indentation.chars().count();
+        for position in self.auto_closed.iter_mut().filter(|position| position.0 == y) {
+            position.1 = position.1 - length + indentation.chars().count();
+        }
+    }
+
+    /// Returns the display width of a row's leading whitespace, with tabs
+    /// reaching the next tab stop.
     fn get_indentation_level(&self, row_content: &str) -> usize {
+        let tab_size = self.config.editor.tab_size.max(1);
         let mut indent_level = 0;
         for ch in row_content.chars() {
-            if ch == ' ' {
-                indent_level += 1;
-            } else {
-                break;
+            match ch {
+                ' ' => indent_level += 1,
+                '\t' => indent_level += tab_size - indent_level % tab_size,
+                _ => break,
             }
         }
         indent_level
     }
 
+    /// Finds where Backspace should delete back to in a row's leading whitespace.
+    /// 
+    /// When every character before `at` is whitespace and the one just before
+    /// it is a space, the spaces back to the previous indent stop go together.
+    /// 
+    /// # Returns
+    /// 
+    /// Returns the character index to delete from, or `None` when Backspace
+    /// should remove a single character.
+    fn previous_indent_stop(&self, row_content: &str, at: usize) -> Option<usize> {
+        let leading: Vec<char> = row_content.chars().take(at).collect();
+        if leading.last() != Some(&' ') || leading.iter().any(|&ch| ch != ' ' && ch != '\t') {
+            return None;
+        }
+        let tab_size = self.config.editor.tab_size.max(1);
+        let mut column = self.get_indentation_level(&leading.iter().collect::<String>());
+        let stop = (column - 1) / tab_size * tab_size;
+        let mut start = at;
+        while start > 0 && leading[start - 1] == ' ' && column > stop {
+            start -= 1;
+            column -= 1;
+        }
+        Some(start)
+    }
+
+    /// Returns the rows covered by the selection of every cursor.
+    /// 
+    /// A cursor without a selection covers its own row. A selection ending at
+    /// the start of a row does not include that row.
+    fn selected_rows(&self) -> Vec<usize> {
+        let primary = (self.cursor_controller.cursor_y, self.cursor_controller.cursor_x);
+        let mut rows: Vec<usize> = self
+            .get_selection_bounds()
+            .into_iter()
+            .chain(std::iter::once((primary, primary)))
+            .take(1)
+            .chain(
+                self.carets
+                    .iter()
+                    .map(|caret| caret.selection_bounds().unwrap_or((caret.position(), caret.position()))),
+            )
+            .flat_map(|(start, end)| {
+                let last = if end.1 == 0 && end.0 > start.0 { end.0 - 1 } else { end.0 };
+                start.0..=last
+            })
+            .filter(|&row| row < self.editor_rows.number_of_rows())
+            .collect();
+        rows.sort_unstable();
+        rows.dedup();
+        rows
+    }
+
+    /// Indents every line covered by a selection, or the cursor's line.
+    /// 
+    /// One level of `indent_unit` is added to the start of each non-empty
+    /// line. The change undoes in one step, and selections stay on the same
+    /// text.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let rows = EditorRows::from_bytes(b"a\n\nb\n", &None);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, None);
+    /// output.start_selection();
+    /// output.set_cursor_position(2, 1);
+    /// output.update_selection();
+    /// output.indent_lines();
+    /// assert_eq!(output.editor_rows.get_row(0), "    a");
+    /// assert_eq!(output.editor_rows.get_row(1), "");
+    /// assert_eq!(output.editor_rows.get_row(2), "    b");
+    /// assert_eq!(output.get_selection_bounds(), Some(((0, 0), (2, 5))));
+    /// output.dedent_lines();
+    /// assert_eq!(output.editor_rows.get_row(0), "a");
+    /// assert_eq!(output.editor_rows.get_row(2), "b");
+    /// ```
+    pub fn indent_lines(&mut self) {
+        self.reindent_lines(true);
+    }
+
+    /// Removes one level of indentation from every line covered by a
+    /// selection, or from the cursor's line.
+    /// 
+    /// Leading spaces and tabs are removed up to `tab_size` columns. The
+    /// change undoes in one step, and selections stay on the same text.
+    pub fn dedent_lines(&mut self) {
+        self.reindent_lines(false);
+    }
+
+    fn reindent_lines(&mut self, indent: bool) {
+        if !self.ensure_writable() {
+            return;
+        }
+        self.block_to_cursors();
+        let unit = self.indent_unit();
+        let tab_size = self.config.editor.tab_size.max(1);
+        // Each change is (row, characters added, characters removed)
+        let changes: Vec<(usize, usize, usize)> = self
+            .selected_rows()
+            .into_iter()
+            .filter_map(|row| {
+                let content = self.editor_rows.get_row(row);
+                if indent {
+                    (!content.is_empty()).then(|| (row, unit.chars().count(), 0))
+                } else {
+                    let mut column = 0;
+                    let removed = content
+                        .chars()
+                        .take_while(|&ch| {
+                            let within = column < tab_size && (ch == ' ' || ch == '\t');
+                            column += if ch == '\t' { tab_size - column % tab_size } else { 1 };
+                            within
+                        })
+                        .count();
+                    (removed > 0).then_some((row, 0, removed))
+                }
+            })
+            .collect();
+        if changes.is_empty() {
+            return;
+        }
+
+        self.push_undo();
+        for &(row, added, removed) in &changes {
+            if added > 0 {
+                let editor_row = self.editor_rows.get_editor_row_mut(row);
+                editor_row.row_content.insert_str(0, &unit);
+                EditorRows::render_row(editor_row);
+            } else {
+                self.editor_rows.delete_between((row, 0), (row, removed));
+            }
+            self.update_syntax_highlighting(row);
+        }
+
+        // Keep every cursor and selection on the text it was on; positions at
+        // the start of a row stay there so whole selected lines stay selected
+        self.map_positions(|(row, column)| match changes.iter().find(|change| change.0 == row) {
+            Some(&(_, added, removed)) if column > 0 => (row, (column + added).saturating_sub(removed)),
+            _ => (row, column),
+        });
+        self.dirty += 1;
+        self.pending_edit = false;
+    }
+
+    /// Moves every cursor and selection end through `shift`, after an edit
+    /// that changed several places at once.
+    fn map_positions(&mut self, shift: impl Fn((usize, usize)) -> (usize, usize)) {
+        self.selection_start = self.selection_start.map(&shift);
+        self.selection_end = self.selection_end.map(&shift);
+        (self.cursor_controller.cursor_y, self.cursor_controller.cursor_x) =
+            shift((self.cursor_controller.cursor_y, self.cursor_controller.cursor_x));
+        for caret in &mut self.carets {
+            caret.set_position(shift(caret.position()));
+            caret.anchor = caret.anchor.map(&shift);
+        }
+    }
+
+    /// Returns the brackets and quotes that are typed in pairs in this buffer.
+    /// 
+    /// Pairs come from the buffer's language and are off without one, or when
+    /// `auto_close_pairs` is disabled.
+    fn auto_pairs(&self) -> &'static [(char, char)] {
+        match self.syntax_highlight.as_ref() {
+            Some(it) if self.config.editor.auto_close_pairs => it.auto_pairs(),
+            _ => &[],
+        }
+    }
+
+    /// Checks whether the cursor is inside a string or comment, using the
+    /// highlighting of the characters around it.
+    fn in_string_or_comment(&self) -> bool {
+        let (y, x) = (self.cursor_controller.cursor_y, self.cursor_controller.cursor_x);
+        if y >= self.editor_rows.number_of_rows() {
+            return false;
+        }
+        if x == 0 {
+            return y > 0 && self.editor_rows.get_editor_row(y - 1).is_comment;
+        }
+        let row = self.editor_rows.get_editor_row(y);
+        let after = (x < row.char_count()).then(|| row.highlight_at(x));
+        match row.highlight_at(x - 1) {
+            HighlightType::Comment => true,
+            HighlightType::MultilineComment => {
+                after.map_or(row.is_comment, |it| matches!(it, HighlightType::MultilineComment))
+            }
+            HighlightType::String | HighlightType::CharLiteral => match after {
+                Some(it) => matches!(it, HighlightType::String | HighlightType::CharLiteral),
+                None => {
+                    // At the end of the row, the string is still open unless it ends with its quote
+                    let chars: Vec<char> = row.row_content.chars().take(x).collect();
+                    let start = (0..x)
+                        .rev()
+                        .take_while(|&index| {
+                            matches!(row.highlight_at(index), HighlightType::String | HighlightType::CharLiteral)
+                        })
+                        .last()
+                        .unwrap_or(x - 1);
+                    let closed = x - start >= 2
+                        && chars[x - 1] == chars[start]
+                        && chars[x - 2] != '\\';
+                    !closed
+                }
+            },
+            _ => false,
+        }
+    }
+
+    /// Types a character at the cursor, closing brackets and quotes.
+    /// 
+    /// A closing character typed where pairing inserted the same character
+    /// steps over it instead. The closing half of a pair is only added
+    /// outside strings and comments, before whitespace, the end of the line
+    /// or another closing character, and quotes are not paired straight
+    /// after a word.
+    fn type_char(&mut self, ch: char) {
+        let (y, x) = (self.cursor_controller.cursor_y, self.cursor_controller.cursor_x);
+        let (previous, next) = match self.editor_rows.row_contents.get(y) {
+            Some(row) => {
+                let mut chars = row.row_content.chars().skip(x.saturating_sub(1));
+                let previous = if x > 0 { chars.next() } else { None };
+                (previous, chars.next())
+            }
+            None => (None, None),
+        };
+        if next == Some(ch) && self.auto_closed.contains(&(y, x)) {
+            self.auto_closed.retain(|&position| position != (y, x));
+            self.cursor_controller.cursor_x += 1;
+            return;
+        }
+
+        let pairs = self.auto_pairs();
+        let close = pairs
+            .iter()
+            .find(|(open, _)| *open == ch)
+            .map(|&(_, close)| close)
+            .filter(|&close| {
+                let before_closer = next.is_none_or(|next| {
+                    next.is_whitespace() || pairs.iter().any(|&(open, it)| it == next && open != it)
+                });
+                let after_word = close == ch
+                    && previous.is_some_and(|previous| previous.is_alphanumeric() || previous == ch);
+                before_closer && !after_word && !self.in_string_or_comment()
+            });
+        self.insert_char_core(ch);
+        self.dedent_closing_char(ch);
+        if let Some(close) = close {
+            let (y, x) = (self.cursor_controller.cursor_y, self.cursor_controller.cursor_x);
+            self.shift_auto_closed(y, x, 1);
+            self.editor_rows.get_editor_row_mut(y).insert_char(x, close);
+            self.update_syntax_highlighting(y);
+            self.auto_closed.push((y, x));
+        }
+    }
+
+    /// Moves the closers recorded on a row at or after a column by the
+    /// number of characters inserted (or, if negative, deleted) there.
+    /// Closers inside deleted text are forgotten.
+    fn shift_auto_closed(&mut self, row: usize, column: usize, delta: isize) {
+        let deleted = column..column + delta.min(0).unsigned_abs();
+        self.auto_closed
+            .retain(|&position| position.0 != row || !deleted.contains(&position.1));
+        for position in &mut self.auto_closed {
+            if position.0 == row && position.1 >= column {
+                position.1 = position.1.saturating_add_signed(delta);
+            }
+        }
+    }
+
+    /// Surrounds the selection of every cursor with a pair of characters,
+    /// keeping each selection on the text inside the pair.
+    fn wrap_selections(&mut self, open: char, close: char) {
+        let mut selections = self.cursor_selections();
+        selections.retain(|(start, end)| start != end);
+        selections.sort_unstable();
+        for &(start, end) in selections.iter().rev() {
+            self.editor_rows.get_editor_row_mut(end.0).insert_char(end.1, close);
+            self.editor_rows.get_editor_row_mut(start.0).insert_char(start.1, open);
+            self.map_positions(|(row, column)| {
+                let mut column = column;
+                if row == end.0 && column > end.1 {
+                    column += 1;
+                }
+                if row == start.0 && column >= start.1 {
+                    column += 1;
+                }
+                (row, column)
+            });
+            (start.0..=end.0).for_each(|row| self.update_syntax_highlighting(row));
+        }
+        self.dirty += 1;
+    }
+
     fn update_syntax_highlighting(&mut self, row_index: usize) {
         if let Some(it) = self.syntax_highlight.as_ref() {
             it.update_syntax(row_index, &mut self.editor_rows.row_contents);
         }
     }
 
-    fn remove_chars_from_row(&mut self, row_index: usize, start: usize, end: usize) {
-        let row = self.editor_rows.get_editor_row_mut(row_index);
-        let mut chars: Vec<char> = row.row_content.chars().collect();
-        chars.drain(start..end);
-        row.row_content = chars.into_iter().collect();
-        EditorRows::render_row(row);
-    }
-
+    /// Types a character at every cursor.
+    /// 
+    /// Opening brackets and quotes are closed, or wrap the selection, and
+    /// typing a closer that was inserted this way steps over it.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `ch` - The character to type
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let syntax = Output::select_syntax("rs");
+    /// let rows = EditorRows::from_bytes(b"", &syntax);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, syntax);
+    /// for ch in "f((".chars() {
+    ///     output.insert_char(ch);
+    /// }
+    /// assert_eq!(output.editor_rows.get_row(0), "f(())");
+    /// // Both closers are stepped over, innermost first
+    /// output.insert_char(')');
+    /// output.insert_char(')');
+    /// assert_eq!(output.editor_rows.get_row(0), "f(())");
+    /// assert_eq!(output.cursor_controller.cursor_x, 5);
+    /// 
+    /// // A closing brace typed on an empty line lines up with its opener
+    /// let syntax = Output::select_syntax("rs");
+    /// let rows = EditorRows::from_bytes(b"fn a() {\n    if x {\n        y\n", &syntax);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, syntax);
+    /// output.set_cursor_position(2, 9);
+    /// output.insert_newline();
+    /// output.delete_char();
+    /// output.insert_char('}');
+    /// assert_eq!(output.editor_rows.get_row(3), "    }");
+    /// output.insert_newline();
+    /// output.insert_char('}');
+    /// assert_eq!(output.editor_rows.get_row(4), "}");
+    /// ```
     pub fn insert_char(&mut self, ch: char) {
-        if !self.pending_edit {
+        if !self.ensure_writable() {
+            return;
+        }
+        self.block_to_cursors();
+        // An opening bracket or quote typed over a selection wraps it instead
+        if let Some(&(_, close)) = self.auto_pairs().iter().find(|(open, _)| *open == ch)
+            && self.cursor_selections().iter().any(|(start, end)| start != end)
+        {
+            self.push_undo();
+            self.wrap_selections(ch, close);
+            self.pending_edit = false;
+            return;
+        }
+        if !self.pending_edit || self.has_any_selection() {
             self.push_undo();
             self.pending_edit = true;
         }
-        self.insert_char_core(ch);
+        self.delete_selections();
+        self.for_each_cursor(|output, _| output.type_char(ch));
     }
 
     pub fn insert_newline(&mut self) {
-        if !self.pending_edit {
+        if !self.ensure_writable() {
+            return;
+        }
+        self.block_to_cursors();
+        if !self.pending_edit || self.has_any_selection() {
             self.push_undo();
             self.pending_edit = true;
         }
-        self.insert_newline_core();
+        self.delete_selections();
+        // Rows move, so earlier closers can no longer be stepped over
+        self.auto_closed.clear();
+        self.for_each_cursor(|output, _| output.insert_newline_core(true));
     }
 
+    /// Deletes the selections, or the character before every cursor.
+    /// 
+    /// Backspace between an opener and the closer typed with it removes
+    /// both halves of the pair.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use ninja::config::NinjaConfig;
+    /// use ninja::screens::editor::EditorRows;
+    /// use ninja::transput::transput::Output;
+    /// 
+    /// let syntax = Output::select_syntax("rs");
+    /// let rows = EditorRows::from_bytes(b"a a\nf()\n", &syntax);
+    /// let mut output = Output::with_rows(NinjaConfig::default(), rows, syntax);
+    /// // Two cursors on one row, each typing a pair
+    /// output.select_all_occurrences();
+    /// output.clear_selection();
+    /// output.insert_char('(');
+    /// output.insert_char('x');
+    /// assert_eq!(output.editor_rows.get_row(0), "a(x) a(x)");
+    /// output.delete_char();
+    /// output.delete_char();
+    /// assert_eq!(output.editor_rows.get_row(0), "a a");
+    /// 
+    /// // A pair that was not typed together is deleted one character at a time
+    /// output.clear_cursors();
+    /// output.set_cursor_position(1, 2);
+    /// output.delete_char();
+    /// assert_eq!(output.editor_rows.get_row(1), "f)");
+    /// 
+    /// // Backspace at the start of the file records nothing, so undo reverts the last edit
+    /// output.set_cursor_position(0, 0);
+    /// output.delete_char();
+    /// output.pop_undo();
+    /// assert_eq!(output.editor_rows.get_row(1), "f()");
+    /// ```
     pub fn delete_char(&mut self) {
+        if !self.ensure_writable() {
+            return;
+        }
+        self.block_to_cursors();
+        if !self.has_anything_to_delete() {
+            self.clear_selection();
+            return;
+        }
+        // Backspace inside an empty pair needs to know which closers were typed for it
+        let auto_closed = std::mem::take(&mut self.auto_closed);
         self.push_undo();
+        if !self.delete_selections() {
+            self.auto_closed = auto_closed;
+            self.for_each_cursor(|output, _| output.delete_char_core());
+        }
+        self.pending_edit = false;
+    }
+
+    /// Returns whether a backspace would remove anything at any cursor.
+    fn has_anything_to_delete(&self) -> bool {
+        let rows = self.editor_rows.number_of_rows();
+        let deletes = |(y, x): (usize, usize)| y < rows && (y, x) != (0, 0);
+        self.cursor_selections().iter().any(|(start, end)| start != end)
+            || deletes(self.cursor_position())
+            || self.carets.iter().any(|caret| deletes(caret.position()))
+    }
+
+    /// Deletes the character before the cursor without recording undo.
+    fn delete_char_core(&mut self) {
         if self.cursor_controller.cursor_y == self.editor_rows.number_of_rows() {
             return;
         }
@@ -945,11 +3089,31 @@ impl Output {
             return;
         }
         if self.cursor_controller.cursor_x > 0 {
-            self.editor_rows
-                .get_editor_row_mut(self.cursor_controller.cursor_y)
-                .delete_char(self.cursor_controller.cursor_x - 1);
-            self.cursor_controller.cursor_x -= 1;
+            // Remove the whole grapheme cluster, such as an accented letter or emoji sequence
+            let (y, x) = (self.cursor_controller.cursor_y, self.cursor_controller.cursor_x);
+            let row_content = &self.editor_rows.get_editor_row(y).row_content;
+            let mut around = row_content.chars().skip(x - 1);
+            let pair = (around.next(), around.next());
+            if let (Some(open), Some(close)) = pair
+                && self.auto_closed.contains(&(y, x))
+                && self.auto_pairs().contains(&(open, close))
+            {
+                // Backspace inside a pair that was just closed removes both halves
+                self.editor_rows.delete_between((y, x - 1), (y, x + 1));
+                self.shift_auto_closed(y, x - 1, -2);
+                self.cursor_controller.cursor_x = x - 1;
+            } else {
+                // Soft tabs in leading whitespace go back to the previous indent stop
+                let start = self
+                    .previous_indent_stop(row_content, x)
+                    .unwrap_or_else(|| graphemes::previous_boundary(row_content, x));
+                self.editor_rows.delete_between((y, start), (y, x));
+                self.shift_auto_closed(y, start, start as isize - x as isize);
+                self.cursor_controller.cursor_x = start;
+            }
         } else {
+            // Joined rows move their closers
+            self.auto_closed.clear();
             let previous_row = self
                 .editor_rows
                 .get_editor_row(self.cursor_controller.cursor_y - 1);
@@ -960,7 +3124,6 @@ impl Output {
         }
         self.update_syntax_highlighting(self.cursor_controller.cursor_y);
         self.dirty += 1;
-        self.pending_edit = false;
     }
 }
 
@@ -1000,10 +3163,58 @@ impl Reader {
     pub fn read_key(&self) -> crossterm::Result<KeyEvent> {
         loop {
             if event::poll(Duration::from_millis(500))? {
-                if let Event::Key(event) = event::read()? {
+                if let Event::Key(event) = event::read()?
+                    && event.kind != KeyEventKind::Release
+                {
                     return Ok(event);
                 }
             }
         }
     }
+
+    /// Waits up to `timeout` for a single terminal event.
+    /// 
+    /// Unlike [`Reader::read_key`], this method gives control back to the
+    /// caller when no event arrives in time, which lets the editor run idle
+    /// work such as writing swap files. Besides key presses, it also reports
+    /// focus changes and resizes.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `timeout` - The maximum time to wait for input
+    /// 
+    /// # Returns
+    /// 
+    /// Returns `Ok(Some(Event))` when an event arrives, `Ok(None)` if the
+    /// timeout expired, or a `crossterm::Error` if the input operation fails.
+    /// Key release events are never returned.
+    /// 
+    /// # Example
+    /// 
+    /// Polling needs a terminal to read from, so this example is not run.
+    /// 
+    /// ```rust,no_run
+    /// use crossterm::event::{Event, KeyEventKind};
+    /// use ninja::transput::transput::Reader;
+    /// use std::time::Duration;
+    /// 
+    /// match Reader.poll_event(Duration::from_millis(500)).unwrap() {
+    ///     Some(Event::Key(key)) => assert_ne!(key.kind, KeyEventKind::Release),
+    ///     Some(_) => {}
+    ///     // Nothing arrived in time: a good moment for idle work
+    ///     None => {}
+    /// }
+    /// ```
+    pub fn poll_event(&self, timeout: Duration) -> crossterm::Result<Option<Event>> {
+        if !event::poll(timeout)? {
+            return Ok(None);
+        }
+        match event::read()? {
+            Event::Key(KeyEvent {
+                kind: KeyEventKind::Release,
+                ..
+            }) => Ok(None),
+            event => Ok(Some(event)),
+        }
+    }
 }